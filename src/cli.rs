@@ -1,51 +1,451 @@
+use std::path::PathBuf;
+
+use chrono::NaiveDate;
 use clap::{Parser, Subcommand};
 
+use crate::{
+    commands::{history::RunStatus, import::ConflictStrategy},
+    file::{FileFormat, convert::ImportSource},
+    placeholder::parse_var,
+    storage::retention::parse_size,
+    ui::{HistoryFormat, ListSort, OutputFormat, PlanFormat},
+};
+
 #[derive(Parser)]
 #[command(name = "markit")]
 #[command(about = "A CLI snippet runner/bookmarker", long_about = None)]
 #[command(version)]
 pub struct Cli {
+    #[arg(
+        long,
+        global = true,
+        value_name = "NAME|PATH",
+        help = "Use a named store or a store directory instead of the default"
+    )]
+    pub store: Option<String>,
+
     #[command(subcommand)]
     pub command: Commands,
 }
 
 #[derive(Subcommand)]
 pub enum Commands {
-    #[command(about = "Save a new snippet interactively")]
-    Save { name: String },
+    #[command(about = "Save a new snippet (prompts for any field not given as a flag)")]
+    Save {
+        name: String,
+
+        #[arg(short, long, help = "Snippet description")]
+        description: Option<String>,
+
+        #[arg(
+            short,
+            long = "tag",
+            value_delimiter = ',',
+            help = "Tag (repeatable or comma-separated)"
+        )]
+        tags: Vec<String>,
+
+        #[arg(short, long, num_args = 0..=1, default_missing_value = "true", value_name = "BOOL", help = "Mark the snippet as executable")]
+        executable: Option<bool>,
+
+        #[arg(short, long, conflicts_with = "from_file", help = "Snippet content")]
+        content: Option<String>,
+
+        #[arg(
+            short,
+            long,
+            value_name = "PATH",
+            help = "Read snippet content from a file"
+        )]
+        from_file: Option<String>,
+
+        #[arg(
+            short,
+            long,
+            value_name = "LANG",
+            help = "Run the content with this interpreter, e.g. python or node"
+        )]
+        language: Option<String>,
+
+        #[arg(
+            long,
+            conflicts_with_all = ["content", "from_file"],
+            help = "Pick the content from your shell history"
+        )]
+        from_history: bool,
+    },
 
     #[command(about = "List all saved snippets (optionally filter by tag)")]
     List {
         #[arg(short, long, help = "Filter by tag")]
         tag: Option<String>,
+
+        #[arg(long, value_enum, help = "Sort order (default: as stored)")]
+        sort: Option<ListSort>,
+
+        #[arg(long, value_enum, default_value_t, help = "Output format")]
+        format: OutputFormat,
     },
 
+    #[command(about = "Fuzzy search snippets by name, description, tags and content")]
+    Search { query: String },
+
     #[command(about = "Show the full content of a snippet")]
-    Show { name: String },
+    Show {
+        name: String,
+
+        #[arg(long, value_enum, default_value_t, help = "Output format")]
+        format: OutputFormat,
+    },
 
     #[command(about = "Run a saved snippet")]
-    Run { name: String },
+    Run {
+        name: String,
+
+        #[arg(long = "var", value_name = "KEY=VALUE", value_parser = parse_var, help = "Set a placeholder value (repeatable)")]
+        vars: Vec<(String, String)>,
+
+        #[arg(long, help = "Show what would run without executing it")]
+        dry_run: bool,
+
+        #[arg(
+            long,
+            value_enum,
+            default_value_t = PlanFormat::Text,
+            requires = "dry_run",
+            help = "Output format for --dry-run"
+        )]
+        format: PlanFormat,
+
+        #[arg(long = "env", value_name = "KEY=VALUE", value_parser = parse_var, help = "Set an environment variable for the snippet (repeatable)")]
+        env: Vec<(String, String)>,
+
+        #[arg(long, value_name = "DIR", help = "Run the snippet in this directory")]
+        cwd: Option<PathBuf>,
+
+        #[arg(
+            last = true,
+            value_name = "ARGS",
+            help = "Arguments passed to the snippet as $1, $2, ..."
+        )]
+        args: Vec<String>,
+    },
+
+    #[command(about = "Show previously run snippets, newest first")]
+    History {
+        #[arg(short, long, value_name = "NAME", help = "Only runs of this snippet")]
+        snippet: Option<String>,
+
+        #[arg(long, value_enum, help = "Only successful or failed runs")]
+        status: Option<RunStatus>,
+
+        #[arg(
+            long,
+            value_name = "YYYY-MM-DD",
+            help = "Only runs on or after this date"
+        )]
+        since: Option<NaiveDate>,
+
+        #[arg(
+            long,
+            value_name = "YYYY-MM-DD",
+            help = "Only runs on or before this date"
+        )]
+        until: Option<NaiveDate>,
+
+        #[arg(short = 'n', long, help = "Show at most this many runs")]
+        limit: Option<usize>,
+
+        #[arg(long, value_enum, default_value_t = HistoryFormat::Table, help = "Output format")]
+        format: HistoryFormat,
+    },
+
+    #[command(about = "Run an entry from the history again with the same values")]
+    Rerun {
+        #[arg(
+            default_value_t = 1,
+            help = "History number as shown by `markit history` (1 is the latest run)"
+        )]
+        number: usize,
+    },
+
+    #[command(about = "Show the most used, never used and stale snippets")]
+    Stats {
+        #[arg(
+            short = 'n',
+            long,
+            default_value_t = 10,
+            help = "How many of the most used to show"
+        )]
+        limit: usize,
+
+        #[arg(
+            long,
+            default_value_t = 30,
+            help = "Snippets not used for this many days count as stale"
+        )]
+        stale_days: i64,
+    },
 
     #[command(about = "Edit a saved snippet in your default editor")]
     Edit { name: String },
 
+    #[command(about = "List the kept revisions of a snippet")]
+    Log { name: String },
+
+    #[command(about = "Show what changed between two revisions of a snippet")]
+    Diff {
+        name: String,
+
+        #[arg(help = "Older revision (default: the one before the current)")]
+        from: Option<u32>,
+
+        #[arg(help = "Newer revision (default: the current one)")]
+        to: Option<u32>,
+    },
+
+    #[command(about = "Bring back an earlier revision of a snippet")]
+    Revert {
+        name: String,
+
+        #[arg(help = "Revision number as shown by `markit log`")]
+        revision: u32,
+    },
+
     #[command(about = "Delete a snippet with confirmation prompt")]
     Delete {
         name: String,
 
-        #[arg(short, long, help = "Force delete without confirmation")]
+        #[arg(
+            short,
+            long,
+            help = "Delete permanently without confirmation instead of moving to the trash"
+        )]
         force: bool,
     },
 
+    #[command(about = "Bring back a deleted snippet from the trash")]
+    Undelete { name: String },
+
+    #[command(about = "List or empty the trash of deleted snippets")]
+    Trash {
+        #[command(subcommand)]
+        action: TrashAction,
+    },
+
     #[command(about = "Copy a snippet's content to the clipboard")]
-    Copy { name: String },
+    Copy {
+        name: String,
+
+        #[arg(long = "var", value_name = "KEY=VALUE", value_parser = parse_var, help = "Set a placeholder value (repeatable)")]
+        vars: Vec<(String, String)>,
+    },
+
+    #[command(about = "Export snippets to a YAML, JSON or TOML file (all of them unless filtered)")]
+    Export {
+        #[arg(
+            required_unless_present = "stdout",
+            conflicts_with = "stdout",
+            help = "File to write, or - for stdout"
+        )]
+        path: Option<String>,
+
+        #[arg(short, long = "name", help = "Export this snippet (repeatable)")]
+        names: Vec<String>,
+
+        #[arg(
+            short,
+            long = "tag",
+            value_delimiter = ',',
+            help = "Export snippets with this tag (repeatable or comma-separated)"
+        )]
+        tags: Vec<String>,
 
-    #[command(about = "Export all snippets to a YAML file")]
-    Export { path: String },
+        #[arg(
+            long = "exclude-tag",
+            value_name = "TAG",
+            value_delimiter = ',',
+            help = "Leave out snippets with this tag (repeatable or comma-separated)"
+        )]
+        exclude_tags: Vec<String>,
 
-    #[command(about = "Import snippets from a YAML file")]
-    Import { path: String },
+        #[arg(
+            long,
+            value_name = "QUERY",
+            help = "Export snippets matching a fuzzy search"
+        )]
+        search: Option<String>,
 
-    #[command(about = "Restore a previous backup")]
-    Restore,
+        #[arg(long, help = "Leave out creation and update times")]
+        strip_timestamps: bool,
+
+        #[arg(long, help = "Write to stdout instead of a file")]
+        stdout: bool,
+
+        #[arg(
+            long,
+            value_enum,
+            help = "File format (default: from the extension, else YAML)"
+        )]
+        format: Option<FileFormat>,
+    },
+
+    #[command(about = "Import snippets from a YAML, JSON or TOML file, or from navi or pet")]
+    Import {
+        #[arg(help = "File to read, - for stdin, or a directory of navi cheat sheets")]
+        path: String,
+
+        #[arg(
+            long,
+            value_enum,
+            default_value_t,
+            help = "What to do with snippets that already exist"
+        )]
+        on_conflict: ConflictStrategy,
+
+        #[arg(long, help = "Show what would be imported without changing anything")]
+        dry_run: bool,
+
+        #[arg(
+            long,
+            value_enum,
+            help = "File format (default: from the extension, else YAML)"
+        )]
+        format: Option<FileFormat>,
+
+        #[arg(
+            long,
+            value_enum,
+            conflicts_with = "format",
+            help = "Convert snippets from another tool"
+        )]
+        from: Option<ImportSource>,
+    },
+
+    #[command(about = "Save commands picked from your bash or zsh history as snippets")]
+    ImportHistory {
+        #[arg(help = "Only offer commands matching this fuzzy search")]
+        query: Option<String>,
+
+        #[arg(
+            short = 'n',
+            long,
+            default_value_t = 100,
+            help = "How many of the latest commands to offer"
+        )]
+        limit: usize,
+    },
+
+    #[command(about = "Preview and restore a previous backup")]
+    Restore {
+        #[arg(short, long, help = "Choose individual snippets to restore")]
+        select: bool,
+    },
+
+    #[command(about = "Browse, filter and act on snippets in a full-screen UI")]
+    Ui,
+
+    #[command(about = "Check the snippet store and repair it from a backup")]
+    Doctor {
+        #[arg(
+            long,
+            help = "Restore the latest readable backup if the store is corrupt"
+        )]
+        repair: bool,
+    },
+
+    #[command(about = "List, prune or delete automatic backups")]
+    Backups {
+        #[command(subcommand)]
+        action: BackupsAction,
+    },
+
+    #[command(about = "Show or change settings in config.yml")]
+    Config {
+        #[command(subcommand)]
+        action: ConfigAction,
+    },
+
+    #[command(about = "List, create or switch snippet stores")]
+    Stores {
+        #[command(subcommand)]
+        action: StoresAction,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum TrashAction {
+    #[command(about = "List deleted snippets, newest first")]
+    List,
+
+    #[command(about = "Permanently delete every snippet in the trash")]
+    Empty {
+        #[arg(short, long, help = "Empty without confirmation")]
+        force: bool,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum BackupsAction {
+    #[command(about = "List backups with their snippet count and size")]
+    List,
+
+    #[command(about = "Delete backups not kept by the retention policy")]
+    Prune {
+        #[arg(long, help = "Always keep this many of the most recent backups")]
+        keep_last: Option<usize>,
+
+        #[arg(long, help = "Keep the newest backup of this many days")]
+        keep_daily: Option<usize>,
+
+        #[arg(long, help = "Keep the newest backup of this many weeks")]
+        keep_weekly: Option<usize>,
+
+        #[arg(long, help = "Keep the newest backup of this many months")]
+        keep_monthly: Option<usize>,
+
+        #[arg(long, value_parser = parse_size, help = "Cap the total size of kept backups (e.g. 50MB)")]
+        max_size: Option<u64>,
+
+        #[arg(long, help = "Show what would be deleted without deleting")]
+        dry_run: bool,
+    },
+
+    #[command(about = "Delete specific backups by file name")]
+    Delete {
+        #[arg(required = true)]
+        names: Vec<String>,
+
+        #[arg(short, long, help = "Force delete without confirmation")]
+        force: bool,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum StoresAction {
+    #[command(about = "List stores and their snippet count")]
+    List,
+
+    #[command(about = "Create a new named store")]
+    Create { name: String },
+
+    #[command(about = "Make a store the default")]
+    Switch { name: String },
+}
+
+#[derive(Subcommand)]
+pub enum ConfigAction {
+    #[command(about = "List every setting")]
+    List,
+
+    #[command(about = "Print a setting, e.g. backups.keep_last")]
+    Get { key: String },
+
+    #[command(about = "Change a setting (lists are comma-separated, null resets to the default)")]
+    Set {
+        key: String,
+
+        #[arg(allow_hyphen_values = true)]
+        value: String,
+    },
 }
@@ -1,17 +1,28 @@
 use std::process::Command;
 
+use crate::config::ClipboardBackend;
+
 pub trait ClipboardProvider {
     fn set_text(&mut self, text: &str) -> Result<(), String>;
 }
 
 pub struct SmartClipboard {
     native: Option<arboard::Clipboard>,
+    providers: Vec<ClipboardBackend>,
 }
 
 impl SmartClipboard {
-    pub fn new() -> Self {
-        let native = arboard::Clipboard::new().ok();
-        Self { native }
+    pub fn new(providers: &[ClipboardBackend]) -> Self {
+        let native = if providers.contains(&ClipboardBackend::Native) {
+            arboard::Clipboard::new().ok()
+        } else {
+            None
+        };
+
+        Self {
+            native,
+            providers: providers.to_vec(),
+        }
     }
 
     fn is_command_available(&self, cmd: &str) -> bool {
@@ -46,37 +57,46 @@ impl SmartClipboard {
         }
     }
 
-    fn fallback_copy(&self, text: &str) -> Result<(), String> {
-        if self.is_command_available("wl-copy") {
-            return self.run_copy_command("wl-copy", text, &[]);
-        }
-
-        if self.is_command_available("xclip") {
-            return self.run_copy_command("xclip", text, &["-selection", "clipboard"]);
-        }
+    fn copy_with(&mut self, backend: ClipboardBackend, text: &str) -> Result<(), String> {
+        let (cmd, args): (&str, &[&str]) = match backend {
+            ClipboardBackend::Native => {
+                return match &mut self.native {
+                    Some(clipboard) => clipboard
+                        .set_text(text.to_string())
+                        .map_err(|e| format!("arboard failed: {}", e)),
+                    None => Err("native clipboard unavailable".into()),
+                };
+            }
+            ClipboardBackend::WlCopy => ("wl-copy", &[]),
+            ClipboardBackend::Xclip => ("xclip", &["-selection", "clipboard"]),
+            ClipboardBackend::Xsel => ("xsel", &["--clipboard", "--input"]),
+            ClipboardBackend::Pbcopy => ("pbcopy", &[]),
+        };
 
-        if self.is_command_available("xsel") {
-            return self.run_copy_command("xsel", text, &["--clipboard", "--input"]);
+        if !self.is_command_available(cmd) {
+            return Err(format!("{} not found", cmd));
         }
-
-        Err(
-            "No clipboard provider available (arboard failed and no wl-copy, xclip, or xsel)"
-                .into(),
-        )
+        self.run_copy_command(cmd, text, args)
     }
 }
 
 impl ClipboardProvider for SmartClipboard {
     fn set_text(&mut self, text: &str) -> Result<(), String> {
-        if let Some(clipboard) = &mut self.native {
-            if let Err(e) = clipboard.set_text(text.to_string()) {
-                eprintln!("⚠️ arboard failed, falling back: {}", e);
-                return self.fallback_copy(text);
-            } else {
-                return Ok(());
+        let mut errors = Vec::new();
+
+        for backend in self.providers.clone() {
+            match self.copy_with(backend, text) {
+                Ok(()) => return Ok(()),
+                Err(e) => errors.push(e),
             }
         }
 
-        self.fallback_copy(text)
+        if errors.is_empty() {
+            return Err("No clipboard provider configured".into());
+        }
+        Err(format!(
+            "No clipboard provider worked ({})",
+            errors.join("; ")
+        ))
     }
 }
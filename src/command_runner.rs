@@ -1,15 +1,291 @@
+use std::{collections::BTreeMap, ffi::OsString, io::Write, path::PathBuf, process::Stdio};
+
+use serde::Serialize;
+
+use crate::config::{Interpreters, ShellConfig};
+
+/// Stands in for the temporary script file in `program` and `args` until
+/// the plan is executed.
+pub const SCRIPT_PATH: &str = "{script}";
+
+/// Everything that determines how a command is executed.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct ExecutionPlan {
+    pub program: String,
+    pub args: Vec<String>,
+    pub cwd: PathBuf,
+    pub env: BTreeMap<String, String>,
+    /// Written to an executable temporary file that replaces [`SCRIPT_PATH`].
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub script: Option<String>,
+}
+
+impl ExecutionPlan {
+    /// The plan as a shell-quoted command line, e.g. `/bin/sh -c 'ls -la'`.
+    pub fn command_line(&self) -> String {
+        std::iter::once(&self.program)
+            .chain(&self.args)
+            .map(|arg| {
+                if arg == SCRIPT_PATH {
+                    arg.clone()
+                } else {
+                    shell_quote(arg)
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+/// A resolved snippet and everything passed through to it from the CLI.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RunRequest {
+    /// Snippet name, exposed to the script as `$0`.
+    pub name: String,
+    pub command: String,
+    pub language: Option<String>,
+    /// Positional parameters (`$1`, `$@`, ...).
+    pub args: Vec<String>,
+    pub env: BTreeMap<String, String>,
+    /// Defaults to markit's working directory.
+    pub cwd: Option<PathBuf>,
+}
+
 pub trait CommandRunner {
-    fn run(&self, command: &str) -> Result<std::process::ExitStatus, std::io::Error>;
+    fn plan(&self, request: &RunRequest) -> Result<ExecutionPlan, String>;
+    fn execute(&self, plan: &ExecutionPlan) -> Result<std::process::ExitStatus, std::io::Error>;
 }
 
-pub struct ShellCommandRunner;
+pub struct ShellCommandRunner {
+    program: String,
+    args: Vec<String>,
+    interpreters: Interpreters,
+}
+
+impl ShellCommandRunner {
+    pub fn new(config: &ShellConfig, interpreters: &Interpreters) -> Self {
+        // Not `$SHELL`: snippets are POSIX shell unless they say otherwise, and
+        // a login shell such as fish would not run them.
+        let program = config.program.clone().unwrap_or_else(|| "/bin/sh".into());
+
+        Self {
+            program,
+            args: config.args.clone(),
+            interpreters: interpreters.clone(),
+        }
+    }
+}
 
 impl CommandRunner for ShellCommandRunner {
-    fn run(&self, command: &str) -> Result<std::process::ExitStatus, std::io::Error> {
-        let shell = std::env::var("SHELL").unwrap_or_else(|_| "/bin/sh".into());
-        std::process::Command::new(shell)
-            .arg("-c")
-            .arg(command)
+    fn plan(&self, request: &RunRequest) -> Result<ExecutionPlan, String> {
+        let (program, mut args, script) = if request.command.starts_with("#!") {
+            (
+                SCRIPT_PATH.to_string(),
+                vec![],
+                Some(request.command.clone()),
+            )
+        } else if let Some(language) = &request.language {
+            let mut command = self.interpreters.command(language).ok_or_else(|| {
+                format!(
+                    "No interpreter configured for '{}'. Add one with `markit config set interpreters.{} <command>`.",
+                    language, language
+                )
+            })?;
+            let program = command.remove(0);
+            command.push(SCRIPT_PATH.to_string());
+            (program, command, Some(request.command.clone()))
+        } else {
+            let mut args = self.args.clone();
+            args.push(request.command.clone());
+            if !request.args.is_empty() {
+                args.push(request.name.clone());
+            }
+            (self.program.clone(), args, None)
+        };
+        args.extend(request.args.iter().cloned());
+
+        let cwd = request
+            .cwd
+            .clone()
+            .or_else(|| std::env::current_dir().ok())
+            .unwrap_or_else(|| PathBuf::from("."));
+
+        Ok(ExecutionPlan {
+            program,
+            args,
+            cwd,
+            env: request.env.clone(),
+            script,
+        })
+    }
+
+    fn execute(&self, plan: &ExecutionPlan) -> Result<std::process::ExitStatus, std::io::Error> {
+        // Removed again when dropped, after the command has finished.
+        let script = plan.script.as_deref().map(write_script).transpose()?;
+        let resolve = |arg: &String| -> OsString {
+            match &script {
+                Some(path) if arg == SCRIPT_PATH => path.as_os_str().to_owned(),
+                _ => arg.into(),
+            }
+        };
+
+        std::process::Command::new(resolve(&plan.program))
+            .args(plan.args.iter().map(resolve))
+            .current_dir(&plan.cwd)
+            .envs(&plan.env)
+            .stdin(Stdio::inherit())
             .status()
     }
 }
+
+fn write_script(content: &str) -> Result<tempfile::TempPath, std::io::Error> {
+    let mut file = tempfile::Builder::new().prefix("markit-").tempfile()?;
+    file.write_all(content.as_bytes())?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        file.as_file()
+            .set_permissions(std::fs::Permissions::from_mode(0o700))?;
+    }
+
+    // Closing the file first avoids "text file busy" when executing it.
+    Ok(file.into_temp_path())
+}
+
+fn shell_quote(arg: &str) -> String {
+    let safe = !arg.is_empty()
+        && arg
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_./=:,+@%".contains(c));
+
+    if safe {
+        arg.to_string()
+    } else {
+        format!("'{}'", arg.replace('\'', r"'\''"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plan_uses_configured_shell() {
+        let runner = ShellCommandRunner::new(
+            &ShellConfig {
+                program: Some("/bin/bash".to_string()),
+                args: vec!["-e".to_string(), "-c".to_string()],
+            },
+            &Interpreters::default(),
+        );
+
+        let plan = runner
+            .plan(&RunRequest {
+                command: "echo 'hi' && ls".to_string(),
+                ..Default::default()
+            })
+            .unwrap();
+
+        assert_eq!(plan.program, "/bin/bash");
+        assert_eq!(plan.args, vec!["-e", "-c", "echo 'hi' && ls"]);
+        assert_eq!(
+            plan.command_line(),
+            r"/bin/bash -e -c 'echo '\''hi'\'' && ls'"
+        );
+    }
+
+    #[test]
+    fn test_plan_defaults_to_posix_shell() {
+        let runner = ShellCommandRunner::new(&ShellConfig::default(), &Interpreters::default());
+
+        let plan = runner
+            .plan(&RunRequest {
+                command: "ls".to_string(),
+                ..Default::default()
+            })
+            .unwrap();
+
+        assert_eq!(plan.program, "/bin/sh");
+        assert_eq!(plan.args, vec!["-c", "ls"]);
+    }
+
+    #[test]
+    fn test_plan_passes_positional_args() {
+        let runner = ShellCommandRunner::new(
+            &ShellConfig {
+                program: Some("/bin/sh".to_string()),
+                args: vec!["-c".to_string()],
+            },
+            &Interpreters::default(),
+        );
+
+        let plan = runner
+            .plan(&RunRequest {
+                name: "deploy".to_string(),
+                command: "echo \"$@\"".to_string(),
+                args: vec!["staging".to_string(), "--force".to_string()],
+                env: BTreeMap::from([("STAGE".to_string(), "staging".to_string())]),
+                cwd: Some(PathBuf::from("/tmp")),
+                ..Default::default()
+            })
+            .unwrap();
+
+        assert_eq!(
+            plan.args,
+            vec!["-c", "echo \"$@\"", "deploy", "staging", "--force"]
+        );
+        assert_eq!(plan.cwd, PathBuf::from("/tmp"));
+        assert_eq!(plan.env["STAGE"], "staging");
+    }
+
+    #[test]
+    fn test_plan_uses_language_interpreter() {
+        let runner = ShellCommandRunner::new(&ShellConfig::default(), &Interpreters::default());
+
+        let plan = runner
+            .plan(&RunRequest {
+                command: "print('hi')".to_string(),
+                language: Some("python".to_string()),
+                args: vec!["a".to_string()],
+                ..Default::default()
+            })
+            .unwrap();
+
+        assert_eq!(plan.program, "python3");
+        assert_eq!(plan.args, vec![SCRIPT_PATH, "a"]);
+        assert_eq!(plan.script.as_deref(), Some("print('hi')"));
+
+        let unknown = runner.plan(&RunRequest {
+            command: "x".to_string(),
+            language: Some("cobol".to_string()),
+            ..Default::default()
+        });
+        assert!(unknown.is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_execute_runs_shebang_scripts() {
+        let runner = ShellCommandRunner::new(&ShellConfig::default(), &Interpreters::default());
+        let dir = tempfile::tempdir().unwrap();
+
+        let plan = runner
+            .plan(&RunRequest {
+                command: "#!/bin/sh\necho \"$1\" > out.txt\nexit 3\n".to_string(),
+                args: vec!["hello".to_string()],
+                cwd: Some(dir.path().to_path_buf()),
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(plan.program, SCRIPT_PATH);
+
+        let status = runner.execute(&plan).unwrap();
+
+        assert_eq!(status.code(), Some(3));
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join("out.txt")).unwrap(),
+            "hello\n"
+        );
+    }
+}
@@ -0,0 +1,289 @@
+use crate::{
+    commands::error::{CommandError, CommandResult},
+    storage::{
+        BackupManager,
+        retention::{RetentionPolicy, format_size},
+    },
+    ui::{ConfirmPrompt, TableUI},
+};
+
+pub fn list_backups_command(
+    manager: &dyn BackupManager,
+    table_ui: &mut dyn TableUI,
+) -> CommandResult {
+    let backups = match manager.get_backup_details() {
+        Ok(b) => b,
+        Err(_) => {
+            println!("📭 No backups created yet.");
+            return Ok(());
+        }
+    };
+
+    if backups.is_empty() {
+        println!("📭 No backups found.");
+        return Ok(());
+    }
+
+    let total: u64 = backups.iter().map(|b| b.size).sum();
+    let count = backups.len();
+
+    let table = table_ui.with_backup_list(backups);
+    println!("{table}");
+    println!("📦 {} backup(s), {} total", count, format_size(total));
+    Ok(())
+}
+
+pub fn prune_backups_command(
+    manager: &dyn BackupManager,
+    policy: &RetentionPolicy,
+    dry_run: bool,
+) -> CommandResult {
+    let pruned = match manager.prune_backups(policy, dry_run) {
+        Ok(p) => p,
+        Err(e) => {
+            eprintln!("⛔ Failed to prune backups: {}", e);
+            return Err(CommandError::Storage);
+        }
+    };
+
+    if pruned.is_empty() {
+        println!("✅ Nothing to prune.");
+        return Ok(());
+    }
+
+    for path in &pruned {
+        let name = path.file_name().unwrap_or_default().to_string_lossy();
+        if dry_run {
+            println!("🔍 Would delete {}", name);
+        } else {
+            println!("🗑️ Deleted {}", name);
+        }
+    }
+
+    if dry_run {
+        println!("🔍 {} backup(s) would be pruned.", pruned.len());
+    } else {
+        println!("✅ Pruned {} backup(s).", pruned.len());
+    }
+    Ok(())
+}
+
+pub fn delete_backups_command(
+    manager: &dyn BackupManager,
+    confirm: &dyn ConfirmPrompt,
+    names: Vec<String>,
+    force: bool,
+) -> CommandResult {
+    let backups = match manager.get_backup_details() {
+        Ok(b) => b,
+        Err(_) => {
+            println!("📭 No backups created yet.");
+            return Err(CommandError::NotFound);
+        }
+    };
+
+    let mut selected = Vec::new();
+    for name in &names {
+        match backups.iter().find(|b| &b.name() == name) {
+            Some(backup) => selected.push(backup),
+            None => {
+                eprintln!("⛔ Backup '{}' not found.", name);
+                return Err(CommandError::NotFound);
+            }
+        }
+    }
+
+    if !force {
+        let prompt = format!(
+            "❗ Are you sure you want to delete {} backup(s)? This cannot be undone.",
+            selected.len()
+        );
+        if !confirm.confirm(&prompt) {
+            println!("🚫 Deletion cancelled.");
+            return Err(CommandError::Cancelled);
+        }
+    }
+
+    let mut result = Ok(());
+    for backup in selected {
+        match manager.delete_backup(&backup.path) {
+            Ok(_) => println!("🗑️ Backup '{}' deleted.", backup.name()),
+            Err(e) => {
+                eprintln!("⛔ Failed to delete '{}': {}", backup.name(), e);
+                result = Err(CommandError::Storage);
+            }
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::{
+        StorageError, history::HistoryEntry, retention::BackupInfo, usage::UsageStats,
+    };
+    use chrono::Utc;
+    use std::{
+        cell::RefCell,
+        path::{Path, PathBuf},
+    };
+
+    struct MockBackupManager {
+        backups: Vec<BackupInfo>,
+        deleted: RefCell<Vec<PathBuf>>,
+        pruned_with: RefCell<Option<(RetentionPolicy, bool)>>,
+    }
+
+    impl MockBackupManager {
+        fn new(names: &[&str]) -> Self {
+            Self {
+                backups: names
+                    .iter()
+                    .map(|name| BackupInfo {
+                        path: PathBuf::from("backups").join(name),
+                        timestamp: Utc::now(),
+                        size: 10,
+                        snippet_count: Some(1),
+                    })
+                    .collect(),
+                deleted: RefCell::new(vec![]),
+                pruned_with: RefCell::new(None),
+            }
+        }
+    }
+
+    impl BackupManager for MockBackupManager {
+        fn get_backup_details(&self) -> Result<Vec<BackupInfo>, StorageError> {
+            Ok(self.backups.clone())
+        }
+
+        fn prune_backups(
+            &self,
+            policy: &RetentionPolicy,
+            dry_run: bool,
+        ) -> Result<Vec<PathBuf>, StorageError> {
+            self.pruned_with.replace(Some((policy.clone(), dry_run)));
+            Ok(self
+                .backups
+                .iter()
+                .skip(1)
+                .map(|b| b.path.clone())
+                .collect())
+        }
+
+        fn delete_backup(&self, path: &Path) -> Result<(), StorageError> {
+            self.deleted.borrow_mut().push(path.to_path_buf());
+            Ok(())
+        }
+    }
+
+    struct MockTableUI {
+        listed: usize,
+    }
+
+    impl TableUI for MockTableUI {
+        fn with_snippet_list(
+            &mut self,
+            _: Vec<crate::models::Snippet>,
+            _: &UsageStats,
+        ) -> comfy_table::Table {
+            comfy_table::Table::new()
+        }
+
+        fn with_search_results(
+            &mut self,
+            _: Vec<crate::storage::filter::FuzzyMatch>,
+        ) -> comfy_table::Table {
+            comfy_table::Table::new()
+        }
+
+        fn with_backup_list(&mut self, backups: Vec<BackupInfo>) -> comfy_table::Table {
+            self.listed = backups.len();
+            comfy_table::Table::new()
+        }
+
+        fn with_history(&mut self, _: Vec<(usize, HistoryEntry)>) -> comfy_table::Table {
+            comfy_table::Table::new()
+        }
+    }
+
+    struct MockConfirmPrompt {
+        result: bool,
+    }
+
+    impl ConfirmPrompt for MockConfirmPrompt {
+        fn confirm(&self, _: &str) -> bool {
+            self.result
+        }
+    }
+
+    #[test]
+    fn test_list_backups() {
+        let manager = MockBackupManager::new(&["b.yml", "a.yml"]);
+        let mut table_ui = MockTableUI { listed: 0 };
+
+        assert!(list_backups_command(&manager, &mut table_ui).is_ok());
+
+        assert_eq!(table_ui.listed, 2);
+    }
+
+    #[test]
+    fn test_prune_backups_passes_policy() {
+        let manager = MockBackupManager::new(&["b.yml", "a.yml"]);
+        let policy = RetentionPolicy {
+            keep_last: 1,
+            ..RetentionPolicy::default()
+        };
+
+        assert!(prune_backups_command(&manager, &policy, true).is_ok());
+
+        assert_eq!(*manager.pruned_with.borrow(), Some((policy, true)));
+    }
+
+    #[test]
+    fn test_delete_backups_confirmed() {
+        let manager = MockBackupManager::new(&["b.yml", "a.yml"]);
+        let confirm = MockConfirmPrompt { result: true };
+
+        assert!(
+            delete_backups_command(&manager, &confirm, vec!["a.yml".to_string()], false).is_ok()
+        );
+
+        assert_eq!(
+            *manager.deleted.borrow(),
+            vec![PathBuf::from("backups").join("a.yml")]
+        );
+    }
+
+    #[test]
+    fn test_delete_backups_cancelled() {
+        let manager = MockBackupManager::new(&["a.yml"]);
+        let confirm = MockConfirmPrompt { result: false };
+
+        assert_eq!(
+            delete_backups_command(&manager, &confirm, vec!["a.yml".to_string()], false),
+            Err(CommandError::Cancelled)
+        );
+
+        assert!(manager.deleted.borrow().is_empty());
+    }
+
+    #[test]
+    fn test_delete_backups_unknown_name() {
+        let manager = MockBackupManager::new(&["a.yml"]);
+        let confirm = MockConfirmPrompt { result: true };
+
+        assert_eq!(
+            delete_backups_command(
+                &manager,
+                &confirm,
+                vec!["a.yml".to_string(), "missing.yml".to_string()],
+                true,
+            ),
+            Err(CommandError::NotFound)
+        );
+
+        assert!(manager.deleted.borrow().is_empty());
+    }
+}
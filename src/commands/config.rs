@@ -0,0 +1,150 @@
+use crate::{
+    commands::error::{CommandError, CommandResult},
+    config::ConfigStore,
+};
+
+pub fn config_list_command(store: &dyn ConfigStore) -> CommandResult {
+    let config = match store.load() {
+        Ok(c) => c,
+        Err(e) => {
+            eprintln!("⛔ {}", e);
+            return Err(CommandError::Validation);
+        }
+    };
+
+    println!("⚙️ {}", store.path().display());
+    for (key, value) in config.entries() {
+        println!("{} = {}", key, value);
+    }
+    Ok(())
+}
+
+pub fn config_get_command(store: &dyn ConfigStore, key: String) -> CommandResult {
+    let config = match store.load() {
+        Ok(c) => c,
+        Err(e) => {
+            eprintln!("⛔ {}", e);
+            return Err(CommandError::Validation);
+        }
+    };
+
+    match config.get(&key) {
+        Ok(Some(value)) => {
+            println!("{}", value);
+            Ok(())
+        }
+        Ok(None) => {
+            eprintln!("📭 '{}' is not set.", key);
+            Err(CommandError::NotFound)
+        }
+        Err(e) => {
+            eprintln!("⛔ {}", e);
+            Err(CommandError::Validation)
+        }
+    }
+}
+
+pub fn config_set_command(store: &dyn ConfigStore, key: String, value: String) -> CommandResult {
+    let config = match store.load() {
+        Ok(c) => c,
+        Err(e) => {
+            eprintln!("⛔ {}", e);
+            return Err(CommandError::Validation);
+        }
+    };
+
+    let updated = match config.set(&key, &value) {
+        Ok(c) => c,
+        Err(e) => {
+            eprintln!("⛔ {}", e);
+            return Err(CommandError::Validation);
+        }
+    };
+
+    match store.save(&updated) {
+        Ok(_) => {
+            println!(
+                "✅ Set {} = {}",
+                key,
+                updated.get(&key).ok().flatten().unwrap_or_default()
+            );
+            Ok(())
+        }
+        Err(e) => {
+            eprintln!("⛔ Failed to save config: {}", e);
+            Err(CommandError::Failed)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use std::{cell::RefCell, path::Path};
+
+    struct MockConfigStore {
+        config: Config,
+        saved: RefCell<Option<Config>>,
+    }
+
+    impl MockConfigStore {
+        fn new() -> Self {
+            Self {
+                config: Config::default(),
+                saved: RefCell::new(None),
+            }
+        }
+    }
+
+    impl ConfigStore for MockConfigStore {
+        fn path(&self) -> &Path {
+            Path::new("config.yml")
+        }
+
+        fn load(&self) -> Result<Config, String> {
+            Ok(self.config.clone())
+        }
+
+        fn save(&self, config: &Config) -> Result<(), String> {
+            self.saved.replace(Some(config.clone()));
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_config_list_and_get() {
+        let store = MockConfigStore::new();
+
+        assert!(config_list_command(&store).is_ok());
+        assert!(config_get_command(&store, "backups.keep_last".to_string()).is_ok());
+
+        assert!(store.saved.borrow().is_none());
+    }
+
+    #[test]
+    fn test_config_set_saves() {
+        let store = MockConfigStore::new();
+
+        assert!(config_set_command(&store, "editor".to_string(), "nvim".to_string()).is_ok());
+
+        let saved = store.saved.borrow();
+        assert_eq!(saved.as_ref().unwrap().editor.as_deref(), Some("nvim"));
+    }
+
+    #[test]
+    fn test_config_set_invalid_does_not_save() {
+        let store = MockConfigStore::new();
+
+        assert_eq!(
+            config_set_command(&store, "backups.keep_last".to_string(), "lots".to_string()),
+            Err(CommandError::Validation)
+        );
+        assert_eq!(
+            config_set_command(&store, "nope".to_string(), "1".to_string()),
+            Err(CommandError::Validation)
+        );
+
+        assert!(store.saved.borrow().is_none());
+    }
+}
@@ -1,32 +1,34 @@
 use crate::{
-    clipboard_provider::ClipboardProvider, commands::helper::get_snippet, storage::Storage,
+    clipboard_provider::ClipboardProvider,
+    commands::error::{CommandError, CommandResult},
+    commands::helper::{get_snippet, load_store, record_usage, resolve_content},
+    input::VariableInput,
+    storage::{Storage, usage::UsageKind},
     ui::SelectionUI,
 };
 
 pub fn copy_command(
     storage: &dyn Storage,
     selection_ui: &dyn SelectionUI,
+    variable_input: &dyn VariableInput,
     clipboard: &mut dyn ClipboardProvider,
     name: String,
-) {
-    let store = match storage.load() {
-        Ok(s) => s,
-        Err(_) => {
-            println!("📭 No snippets saved yet.");
-            return;
-        }
-    };
+    vars: &[(String, String)],
+) -> CommandResult {
+    let store = load_store(storage)?;
+
+    let snippet = get_snippet(storage, &store, selection_ui, name)?;
 
-    let Some(snippet) = get_snippet(&store, selection_ui, name) else {
-        return;
-    };
+    let content = resolve_content(&snippet.content, variable_input, vars)?;
 
-    if let Err(e) = clipboard.set_text(snippet.content.as_str()) {
+    if let Err(e) = clipboard.set_text(content.as_str()) {
         eprintln!("⛔ Failed to copy to clipboard: {}", e);
-        return;
+        return Err(CommandError::Failed);
     }
 
+    record_usage(storage, &snippet.id, UsageKind::Copy);
     println!("📋 Snippet '{}' copied to clipboard", snippet.name);
+    Ok(())
 }
 
 #[cfg(test)]
@@ -50,6 +52,7 @@ mod tests {
             } else {
                 Ok(SnippetStore {
                     snippets: self.snippets.clone(),
+                    trash: vec![],
                 })
             }
         }
@@ -83,6 +86,18 @@ mod tests {
         fn with_backup_list(&self, _: &[String]) -> Option<usize> {
             Some(0)
         }
+
+        fn with_multi_select(&self, _: &str, _: &[String]) -> Vec<usize> {
+            vec![]
+        }
+    }
+
+    struct MockVariableInput;
+
+    impl VariableInput for MockVariableInput {
+        fn get_value(&self, name: &str, default: Option<&str>) -> Option<String> {
+            default.map(str::to_string).or(Some(format!("<{}>", name)))
+        }
     }
 
     struct MockClipboard {
@@ -103,13 +118,16 @@ mod tests {
 
     fn sample_snippet() -> Snippet {
         Snippet {
+            id: Snippet::new_id(),
             name: "test".to_string(),
             description: "desc".to_string(),
             content: "echo hello".to_string(),
+            language: None,
             executable: true,
             tags: vec!["dev".to_string()],
             created_at: chrono::Utc::now(),
             updated_at: chrono::Utc::now(),
+            revisions: vec![],
         }
     }
 
@@ -131,11 +149,16 @@ mod tests {
             fail: false,
         };
 
-        copy_command(
-            &storage,
-            &selection_ui,
-            &mut clipboard,
-            snippet.name.clone(),
+        assert!(
+            copy_command(
+                &storage,
+                &selection_ui,
+                &MockVariableInput,
+                &mut clipboard,
+                snippet.name.clone(),
+                &[],
+            )
+            .is_ok()
         );
 
         assert_eq!(
@@ -160,7 +183,17 @@ mod tests {
             fail: false,
         };
 
-        copy_command(&storage, &selection_ui, &mut clipboard, "test".to_string());
+        assert_eq!(
+            copy_command(
+                &storage,
+                &selection_ui,
+                &MockVariableInput,
+                &mut clipboard,
+                "test".to_string(),
+                &[],
+            ),
+            Err(CommandError::Storage)
+        );
 
         assert!(clipboard.last_text.borrow().is_none());
     }
@@ -183,7 +216,17 @@ mod tests {
             fail: false,
         };
 
-        copy_command(&storage, &selection_ui, &mut clipboard, "test".to_string());
+        assert_eq!(
+            copy_command(
+                &storage,
+                &selection_ui,
+                &MockVariableInput,
+                &mut clipboard,
+                "missing".to_string(),
+                &[],
+            ),
+            Err(CommandError::NotFound)
+        );
 
         assert!(clipboard.last_text.borrow().is_none());
     }
@@ -206,8 +249,55 @@ mod tests {
             fail: true,
         };
 
-        copy_command(&storage, &selection_ui, &mut clipboard, "test".to_string());
+        assert_eq!(
+            copy_command(
+                &storage,
+                &selection_ui,
+                &MockVariableInput,
+                &mut clipboard,
+                "test".to_string(),
+                &[],
+            ),
+            Err(CommandError::Failed)
+        );
 
         assert!(clipboard.last_text.borrow().is_none());
     }
+
+    #[test]
+    fn test_copy_substitutes_variables() {
+        let mut snippet = sample_snippet();
+        snippet.content = "psql -h {{host}} -p {{port:5432}} {{db}}".to_string();
+
+        let storage = MockStorage {
+            snippets: vec![snippet.clone()],
+            should_fail: false,
+        };
+
+        let selection_ui = MockSelectionUI {
+            selected: RefCell::new(Some(snippet)),
+        };
+
+        let mut clipboard = MockClipboard {
+            last_text: RefCell::new(None),
+            fail: false,
+        };
+
+        assert!(
+            copy_command(
+                &storage,
+                &selection_ui,
+                &MockVariableInput,
+                &mut clipboard,
+                "test".to_string(),
+                &[("db".to_string(), "orders".to_string())],
+            )
+            .is_ok()
+        );
+
+        assert_eq!(
+            clipboard.last_text.borrow().as_deref(),
+            Some("psql -h <host> -p 5432 orders")
+        );
+    }
 }
@@ -1,61 +1,84 @@
 use crate::{
-    commands::helper::get_snippet,
+    commands::{
+        error::{CommandError, CommandResult},
+        helper::{get_snippet, load_store, lock_storage},
+    },
     storage::Storage,
     ui::{ConfirmPrompt, SelectionUI},
 };
 
+/// Moves a snippet to the trash, or removes it for good with `force`.
 pub fn delete_command(
     storage: &dyn Storage,
     selection_ui: &dyn SelectionUI,
     confirm: &dyn ConfirmPrompt,
     name: String,
     force: bool,
-) {
-    let mut store = match storage.load() {
-        Ok(s) => s,
-        Err(_) => {
-            println!("📭 No snippets saved yet.");
-            return;
-        }
-    };
+) -> CommandResult {
+    let store = load_store(storage)?;
 
-    let delete_snippet = match get_snippet(&store, selection_ui, name) {
-        Some(s) => s,
-        None => {
-            return;
-        }
-    };
+    let delete_snippet = get_snippet(storage, &store, selection_ui, name)?;
 
     if !force {
-        let prompt = format!(
-            "❗ Are you sure you want to delete '{}'? This cannot be undone.",
-            delete_snippet.name
-        );
+        let prompt = format!("❗ Move '{}' to the trash?", delete_snippet.name);
         if !confirm.confirm(&prompt) {
             println!("🚫 Deletion cancelled.");
-            return;
+            return Err(CommandError::Cancelled);
         }
     }
 
-    store.snippets.retain(|s| s.name != delete_snippet.name);
+    let _lock = lock_storage(storage)?;
+
+    let mut store = load_store(storage)?;
+
+    let found = if force {
+        let before = store.snippets.len();
+        store.snippets.retain(|s| s.id != delete_snippet.id);
+        store.snippets.len() < before
+    } else {
+        store.move_to_trash(&delete_snippet.id, chrono::Utc::now())
+    };
+    if !found {
+        eprintln!(
+            "⛔ Snippet '{}' was removed in the meantime.",
+            delete_snippet.name
+        );
+        return Err(CommandError::NotFound);
+    }
 
     if let Err(err) = storage.save_all(&store) {
         eprintln!("⛔ Failed to update snippets file: {:?}", err);
-    } else {
-        println!("🗑️ Snippet '{}' deleted.", delete_snippet.name);
+        return Err(CommandError::Storage);
+    }
+
+    if !force {
+        println!("🗑️ Snippet '{}' moved to the trash.", delete_snippet.name);
+        println!(
+            "💡 Bring it back with `markit undelete {}`.",
+            delete_snippet.name
+        );
+        return Ok(());
     }
+
+    if let Err(e) = storage.update_usage(&mut |usage| usage.remove(&delete_snippet.id)) {
+        eprintln!("⚠️ Failed to clear usage statistics: {}", e);
+    }
+
+    println!("🗑️ Snippet '{}' deleted.", delete_snippet.name);
+    Ok(())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::models::{Snippet, SnippetStore};
+    use crate::models::{Snippet, SnippetStore, TrashedSnippet};
     use crate::storage::{Storage, StorageError};
     use crate::ui::{ConfirmPrompt, SelectionUI};
     use std::cell::RefCell;
 
     struct MockStorage {
         pub snippets: RefCell<Vec<Snippet>>,
+        pub trash: RefCell<Vec<TrashedSnippet>>,
         pub should_fail_load: bool,
         pub should_fail_save: bool,
     }
@@ -68,6 +91,7 @@ mod tests {
 
             Ok(SnippetStore {
                 snippets: self.snippets.borrow().clone(),
+                trash: self.trash.borrow().clone(),
             })
         }
 
@@ -81,6 +105,7 @@ mod tests {
             }
 
             self.snippets.replace(store.snippets.clone());
+            self.trash.replace(store.trash.clone());
             Ok(())
         }
 
@@ -105,6 +130,10 @@ mod tests {
         fn with_backup_list(&self, _: &[String]) -> Option<usize> {
             Some(0)
         }
+
+        fn with_multi_select(&self, _: &str, _: &[String]) -> Vec<usize> {
+            vec![]
+        }
     }
 
     struct MockConfirmPrompt {
@@ -119,13 +148,16 @@ mod tests {
 
     fn sample_snippet(name: &str) -> Snippet {
         Snippet {
+            id: Snippet::new_id(),
             name: name.to_string(),
             description: "desc".into(),
             content: "echo test".into(),
+            language: None,
             executable: true,
             tags: vec!["tag".into()],
             created_at: chrono::Utc::now(),
             updated_at: chrono::Utc::now(),
+            revisions: vec![],
         }
     }
 
@@ -134,6 +166,7 @@ mod tests {
         let snippet = sample_snippet("test");
         let storage = MockStorage {
             snippets: RefCell::new(vec![snippet.clone()]),
+            trash: RefCell::new(vec![]),
             should_fail_load: false,
             should_fail_save: false,
         };
@@ -144,9 +177,12 @@ mod tests {
             confirm_result: false,
         }; // Should be skipped
 
-        delete_command(&storage, &selection_ui, &confirm, "test".to_string(), true);
+        assert!(
+            delete_command(&storage, &selection_ui, &confirm, "test".to_string(), true).is_ok()
+        );
 
         assert!(storage.snippets.borrow().is_empty());
+        assert!(storage.trash.borrow().is_empty());
     }
 
     #[test]
@@ -154,6 +190,7 @@ mod tests {
         let snippet = sample_snippet("test");
         let storage = MockStorage {
             snippets: RefCell::new(vec![snippet.clone()]),
+            trash: RefCell::new(vec![]),
             should_fail_load: false,
             should_fail_save: false,
         };
@@ -164,9 +201,12 @@ mod tests {
             confirm_result: true,
         };
 
-        delete_command(&storage, &selection_ui, &confirm, "test".to_string(), false);
+        assert!(
+            delete_command(&storage, &selection_ui, &confirm, "test".to_string(), false).is_ok()
+        );
 
         assert!(storage.snippets.borrow().is_empty());
+        assert_eq!(storage.trash.borrow()[0].snippet.name, "test");
     }
 
     #[test]
@@ -174,6 +214,7 @@ mod tests {
         let snippet = sample_snippet("test");
         let storage = MockStorage {
             snippets: RefCell::new(vec![snippet.clone()]),
+            trash: RefCell::new(vec![]),
             should_fail_load: false,
             should_fail_save: false,
         };
@@ -184,7 +225,10 @@ mod tests {
             confirm_result: false,
         };
 
-        delete_command(&storage, &selection_ui, &confirm, "test".to_string(), false);
+        assert_eq!(
+            delete_command(&storage, &selection_ui, &confirm, "test".to_string(), false),
+            Err(CommandError::Cancelled)
+        );
 
         assert_eq!(storage.snippets.borrow().len(), 1);
     }
@@ -193,6 +237,7 @@ mod tests {
     fn test_delete_snippet_not_found() {
         let storage = MockStorage {
             snippets: RefCell::new(vec![]),
+            trash: RefCell::new(vec![]),
             should_fail_load: false,
             should_fail_save: false,
         };
@@ -201,21 +246,58 @@ mod tests {
             confirm_result: true,
         };
 
-        delete_command(
-            &storage,
-            &selection_ui,
-            &confirm,
-            "missing".to_string(),
-            true,
+        assert_eq!(
+            delete_command(
+                &storage,
+                &selection_ui,
+                &confirm,
+                "missing".to_string(),
+                true,
+            ),
+            Err(CommandError::NotFound)
         );
 
         assert!(storage.snippets.borrow().is_empty());
     }
 
+    /// Confirms, but another process deletes the snippet while it asks.
+    struct RacingConfirmPrompt<'a> {
+        storage: &'a MockStorage,
+    }
+
+    impl ConfirmPrompt for RacingConfirmPrompt<'_> {
+        fn confirm(&self, _: &str) -> bool {
+            self.storage.snippets.borrow_mut().clear();
+            true
+        }
+    }
+
+    #[test]
+    fn test_delete_snippet_removed_in_the_meantime() {
+        let snippet = sample_snippet("test");
+        let storage = MockStorage {
+            snippets: RefCell::new(vec![snippet.clone()]),
+            trash: RefCell::new(vec![]),
+            should_fail_load: false,
+            should_fail_save: false,
+        };
+        let selection_ui = MockSelectionUI {
+            snippet: Some(snippet),
+        };
+        let confirm = RacingConfirmPrompt { storage: &storage };
+
+        assert_eq!(
+            delete_command(&storage, &selection_ui, &confirm, "test".to_string(), false),
+            Err(CommandError::NotFound)
+        );
+        assert!(storage.trash.borrow().is_empty());
+    }
+
     #[test]
     fn test_delete_load_failure() {
         let storage = MockStorage {
             snippets: RefCell::new(vec![]),
+            trash: RefCell::new(vec![]),
             should_fail_load: true,
             should_fail_save: false,
         };
@@ -226,7 +308,10 @@ mod tests {
             confirm_result: true,
         };
 
-        delete_command(&storage, &selection_ui, &confirm, "test".to_string(), true);
+        assert_eq!(
+            delete_command(&storage, &selection_ui, &confirm, "test".to_string(), true),
+            Err(CommandError::Storage)
+        );
     }
 
     #[test]
@@ -234,6 +319,7 @@ mod tests {
         let snippet = sample_snippet("test");
         let storage = MockStorage {
             snippets: RefCell::new(vec![snippet.clone()]),
+            trash: RefCell::new(vec![]),
             should_fail_load: false,
             should_fail_save: true,
         };
@@ -244,6 +330,9 @@ mod tests {
             confirm_result: true,
         };
 
-        delete_command(&storage, &selection_ui, &confirm, "test".to_string(), false);
+        assert_eq!(
+            delete_command(&storage, &selection_ui, &confirm, "test".to_string(), false),
+            Err(CommandError::Storage)
+        );
     }
 }
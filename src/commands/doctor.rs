@@ -0,0 +1,250 @@
+use crate::{
+    commands::error::{CommandError, CommandResult},
+    file::FileReader,
+    storage::{Storage, StorageError},
+    ui::ConfirmPrompt,
+};
+
+pub fn doctor_command(
+    storage: &dyn Storage,
+    reader: &dyn FileReader,
+    confirm: &dyn ConfirmPrompt,
+    repair: bool,
+) -> CommandResult {
+    let error = match storage.load() {
+        Ok(store) => {
+            println!(
+                "✅ Snippet store is healthy ({} snippets).",
+                store.snippets.len()
+            );
+            return Ok(());
+        }
+        Err(e @ StorageError::Corrupt { .. }) => e,
+        Err(e) => {
+            eprintln!("⛔ Failed to load snippets: {}", e);
+            return Err(CommandError::Storage);
+        }
+    };
+
+    eprintln!("⛔ {}", error);
+
+    if !repair {
+        println!("💡 Run `markit doctor --repair` to recover from the latest readable backup.");
+        return Err(CommandError::Storage);
+    }
+
+    let backups = match storage.get_backups() {
+        Ok(b) => b,
+        Err(_) => {
+            println!("📭 No backups created yet.");
+            return Err(CommandError::NotFound);
+        }
+    };
+
+    let Some((path, store)) = backups.iter().find_map(|path| {
+        reader
+            .read_yaml(&path.to_string_lossy())
+            .ok()
+            .map(|store| (path, store))
+    }) else {
+        eprintln!("⛔ No readable backup found. Fix the file by hand or delete it to start over.");
+        return Err(CommandError::NotFound);
+    };
+
+    let name = path.file_name().unwrap_or_default().to_string_lossy();
+    println!(
+        "📦 Latest readable backup: {} ({} snippets)",
+        name,
+        store.snippets.len()
+    );
+
+    let prompt = format!(
+        "❗ Restore '{}'? The corrupt store will be kept as a backup.",
+        name
+    );
+    if !confirm.confirm(&prompt) {
+        println!("🚫 Repair cancelled.");
+        return Err(CommandError::Cancelled);
+    }
+
+    match storage.restore_backup(path) {
+        Ok(_) => {
+            println!("✅ Snippet store repaired from '{}'.", name);
+            Ok(())
+        }
+        Err(e) => {
+            eprintln!("⛔ Failed to restore backup: {}", e);
+            Err(CommandError::Storage)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::file::{
+        FileFormat,
+        convert::{Conversion, ImportSource},
+    };
+    use crate::models::{Snippet, SnippetStore};
+    use std::{
+        cell::RefCell,
+        path::{Path, PathBuf},
+    };
+
+    struct MockStorage {
+        corrupt: bool,
+        backups: Vec<PathBuf>,
+        restore_called_with: RefCell<Option<PathBuf>>,
+    }
+
+    impl MockStorage {
+        fn new(corrupt: bool, backups: Vec<&str>) -> Self {
+            Self {
+                corrupt,
+                backups: backups.into_iter().map(PathBuf::from).collect(),
+                restore_called_with: RefCell::new(None),
+            }
+        }
+    }
+
+    impl Storage for MockStorage {
+        fn load(&self) -> Result<SnippetStore, StorageError> {
+            if self.corrupt {
+                Err(StorageError::Corrupt {
+                    path: PathBuf::from("bookmarks.yml"),
+                    line: Some(3),
+                    column: Some(5),
+                    message: "mapping values are not allowed here".to_string(),
+                })
+            } else {
+                Ok(SnippetStore::default())
+            }
+        }
+
+        fn save(&self, _: Snippet) -> Result<(), StorageError> {
+            Ok(())
+        }
+
+        fn save_all(&self, _: &SnippetStore) -> Result<(), StorageError> {
+            Ok(())
+        }
+
+        fn get_backups(&self) -> Result<Vec<PathBuf>, StorageError> {
+            Ok(self.backups.clone())
+        }
+
+        fn restore_backup(&self, path: &Path) -> Result<(), StorageError> {
+            self.restore_called_with.replace(Some(path.to_path_buf()));
+            Ok(())
+        }
+    }
+
+    struct MockFileReader {
+        unreadable: Vec<&'static str>,
+    }
+
+    impl FileReader for MockFileReader {
+        fn read(&self, path: &str, _: FileFormat) -> Result<SnippetStore, StorageError> {
+            if self.unreadable.contains(&path) {
+                Err(StorageError::Io(std::io::Error::other("unreadable")))
+            } else {
+                Ok(SnippetStore::default())
+            }
+        }
+
+        fn convert(&self, _: &str, _: ImportSource) -> Result<Conversion, StorageError> {
+            Ok(Conversion::default())
+        }
+    }
+
+    struct MockConfirmPrompt {
+        result: bool,
+    }
+
+    impl ConfirmPrompt for MockConfirmPrompt {
+        fn confirm(&self, _: &str) -> bool {
+            self.result
+        }
+    }
+
+    #[test]
+    fn test_doctor_healthy_store() {
+        let storage = MockStorage::new(false, vec!["b2.yml"]);
+        let reader = MockFileReader { unreadable: vec![] };
+
+        assert!(
+            doctor_command(&storage, &reader, &MockConfirmPrompt { result: true }, true).is_ok()
+        );
+
+        assert!(storage.restore_called_with.borrow().is_none());
+    }
+
+    #[test]
+    fn test_doctor_without_repair_does_not_restore() {
+        let storage = MockStorage::new(true, vec!["b2.yml"]);
+        let reader = MockFileReader { unreadable: vec![] };
+
+        assert_eq!(
+            doctor_command(
+                &storage,
+                &reader,
+                &MockConfirmPrompt { result: true },
+                false,
+            ),
+            Err(CommandError::Storage)
+        );
+
+        assert!(storage.restore_called_with.borrow().is_none());
+    }
+
+    #[test]
+    fn test_doctor_repair_skips_unreadable_backups() {
+        let storage = MockStorage::new(true, vec!["b3.yml", "b2.yml", "b1.yml"]);
+        let reader = MockFileReader {
+            unreadable: vec!["b3.yml"],
+        };
+
+        assert!(
+            doctor_command(&storage, &reader, &MockConfirmPrompt { result: true }, true).is_ok()
+        );
+
+        assert_eq!(
+            storage.restore_called_with.borrow().as_deref(),
+            Some(Path::new("b2.yml"))
+        );
+    }
+
+    #[test]
+    fn test_doctor_repair_cancelled() {
+        let storage = MockStorage::new(true, vec!["b1.yml"]);
+        let reader = MockFileReader { unreadable: vec![] };
+
+        assert_eq!(
+            doctor_command(
+                &storage,
+                &reader,
+                &MockConfirmPrompt { result: false },
+                true,
+            ),
+            Err(CommandError::Cancelled)
+        );
+
+        assert!(storage.restore_called_with.borrow().is_none());
+    }
+
+    #[test]
+    fn test_doctor_repair_without_readable_backup() {
+        let storage = MockStorage::new(true, vec!["b1.yml"]);
+        let reader = MockFileReader {
+            unreadable: vec!["b1.yml"],
+        };
+
+        assert_eq!(
+            doctor_command(&storage, &reader, &MockConfirmPrompt { result: true }, true),
+            Err(CommandError::NotFound)
+        );
+
+        assert!(storage.restore_called_with.borrow().is_none());
+    }
+}
@@ -1,5 +1,9 @@
 use crate::{
-    commands::helper::{get_snippet, redact_snippet},
+    commands::{
+        error::{CommandError, CommandResult},
+        helper::{get_snippet, load_store, lock_storage, redact_snippet},
+    },
+    diff::same_snippet,
     file::EditorLauncher,
     models::{PartialSnippet, Snippet},
     storage::Storage,
@@ -11,21 +15,10 @@ pub fn edit_command(
     selection_ui: &dyn SelectionUI,
     editor: &dyn EditorLauncher,
     name: String,
-) {
-    let mut store = match storage.load() {
-        Ok(s) => s,
-        Err(_) => {
-            println!("📭 No snippets saved yet.");
-            return;
-        }
-    };
+) -> CommandResult {
+    let store = load_store(storage)?;
 
-    let mut original = match get_snippet(&store, selection_ui, name) {
-        Some(s) => s,
-        None => {
-            return;
-        }
-    };
+    let original = get_snippet(storage, &store, selection_ui, name)?;
 
     let editable = redact_snippet(&original);
 
@@ -33,40 +26,60 @@ pub fn edit_command(
         Ok(s) => s,
         Err(e) => {
             eprintln!("⛔ {}", e);
-            return;
+            return Err(CommandError::Failed);
         }
     };
 
+    let _lock = lock_storage(storage)?;
+
+    let mut store = load_store(storage)?;
+
+    let Some(mut original) = store.snippets.iter().find(|s| s.id == original.id).cloned() else {
+        eprintln!(
+            "⛔ Snippet '{}' was removed while it was being edited.",
+            original.name
+        );
+        return Err(CommandError::NotFound);
+    };
+
     if store
         .snippets
         .iter()
-        .any(|s| s.name.eq_ignore_ascii_case(&edited.name) && s.name != original.name)
+        .any(|s| s.name.eq_ignore_ascii_case(&edited.name) && s.id != original.id)
     {
         eprintln!(
             "⛔ Another snippet with the name '{}' already exists.",
             edited.name
         );
-        return;
+        return Err(CommandError::Validation);
     }
 
-    store.snippets.retain(|s| s.name != original.name);
+    store.snippets.retain(|s| s.id != original.id);
     apply_edits(&mut original, edited);
     store.snippets.push(original.clone());
 
     if let Err(err) = storage.save_all(&store) {
         eprintln!("⛔ Failed to update snippet: {:?}", err);
-    } else {
-        println!("✏️ Snippet '{}' updated.", original.name);
+        return Err(CommandError::Storage);
     }
+
+    println!("✏️ Snippet '{}' updated.", original.name);
+    Ok(())
 }
 
 fn apply_edits(original: &mut Snippet, edited: PartialSnippet) {
+    let previous = original.clone();
     original.name = edited.name;
     original.description = edited.description;
     original.content = edited.content;
+    original.language = edited.language;
     original.executable = edited.executable;
     original.updated_at = chrono::Utc::now();
     original.tags = edited.tags;
+
+    if !same_snippet(&previous, original) {
+        original.keep_revision(&previous);
+    }
 }
 
 #[cfg(test)]
@@ -121,6 +134,10 @@ mod tests {
         fn with_backup_list(&self, _: &[String]) -> Option<usize> {
             Some(0)
         }
+
+        fn with_multi_select(&self, _: &str, _: &[String]) -> Vec<usize> {
+            vec![]
+        }
     }
 
     struct MockEditorLauncher {
@@ -135,13 +152,16 @@ mod tests {
 
     fn make_test_snippet() -> Snippet {
         Snippet {
+            id: Snippet::new_id(),
             name: "test".into(),
             description: "desc".into(),
             content: "echo hello".into(),
+            language: None,
             executable: true,
             tags: vec!["tag1".into()],
             created_at: chrono::Utc::now(),
             updated_at: chrono::Utc::now(),
+            revisions: vec![],
         }
     }
 
@@ -150,6 +170,7 @@ mod tests {
             name: "test-edited".into(),
             description: "new desc".into(),
             content: "echo world".into(),
+            language: None,
             executable: false,
             tags: vec!["tag2".into()],
         }
@@ -160,6 +181,7 @@ mod tests {
         let original = make_test_snippet();
         let store = SnippetStore {
             snippets: vec![original.clone()],
+            trash: vec![],
         };
 
         let storage = MockStorage {
@@ -175,13 +197,16 @@ mod tests {
             result: Ok(make_partial_snippet()),
         };
 
-        edit_command(&storage, &selection_ui, &editor, original.name.clone());
+        assert!(edit_command(&storage, &selection_ui, &editor, original.name.clone()).is_ok());
         let updated = &storage.store.borrow().snippets[0];
         assert_eq!(updated.name, "test-edited");
         assert_eq!(updated.description, "new desc");
         assert_eq!(updated.content, "echo world");
         assert!(!updated.executable);
         assert_eq!(updated.tags, vec!["tag2"]);
+        assert_eq!(updated.revisions.len(), 1);
+        assert_eq!(updated.revisions[0].content, "echo hello");
+        assert_eq!(updated.revision_number(), 2);
     }
 
     #[test]
@@ -189,6 +214,7 @@ mod tests {
         let original = make_test_snippet();
         let store = SnippetStore {
             snippets: vec![original.clone()],
+            trash: vec![],
         };
 
         let storage = MockStorage {
@@ -204,7 +230,10 @@ mod tests {
             result: Err("Editor error".into()),
         };
 
-        edit_command(&storage, &selection_ui, &editor, original.name.clone());
+        assert_eq!(
+            edit_command(&storage, &selection_ui, &editor, original.name.clone()),
+            Err(CommandError::Failed)
+        );
 
         // Should not have changed
         let unchanged = &storage.store.borrow().snippets[0];
@@ -215,10 +244,12 @@ mod tests {
     fn test_edit_command_duplicate_name() {
         let snippet1 = make_test_snippet();
         let mut snippet2 = snippet1.clone();
+        snippet2.id = Snippet::new_id();
         snippet2.name = "other".into();
 
         let store = SnippetStore {
             snippets: vec![snippet1.clone(), snippet2.clone()],
+            trash: vec![],
         };
 
         let storage = MockStorage {
@@ -237,7 +268,10 @@ mod tests {
             result: Ok(partial),
         };
 
-        edit_command(&storage, &selection_ui, &editor, snippet2.name.clone());
+        assert_eq!(
+            edit_command(&storage, &selection_ui, &editor, snippet2.name.clone()),
+            Err(CommandError::Validation)
+        );
 
         // Should still have both original names
         let names: Vec<_> = storage
@@ -0,0 +1,80 @@
+use std::process::ExitStatus;
+
+/// Why a command failed. Commands report the details themselves; this only
+/// decides markit's exit status.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CommandError {
+    /// Any other failure, e.g. the clipboard or an editor could not be used.
+    Failed,
+    /// No snippet, backup or store matched.
+    NotFound,
+    /// Several snippets matched and there was no terminal to choose from.
+    Ambiguous,
+    /// The store could not be read, locked or written.
+    Storage,
+    /// The input was invalid, e.g. a missing placeholder value.
+    Validation,
+    /// The user declined a confirmation or aborted a prompt.
+    Cancelled,
+    /// The snippet ran but exited with this status.
+    Exited(i32),
+}
+
+pub type CommandResult = Result<(), CommandError>;
+
+impl CommandError {
+    pub fn exit_code(self) -> i32 {
+        match self {
+            CommandError::Failed => 1,
+            CommandError::NotFound => 3,
+            CommandError::Ambiguous => 4,
+            CommandError::Storage => 5,
+            CommandError::Validation => 6,
+            CommandError::Cancelled => 7,
+            CommandError::Exited(code) => code,
+        }
+    }
+
+    /// The child's exit code, or 128 + the signal that killed it.
+    pub fn from_status(status: ExitStatus) -> Self {
+        if let Some(code) = status.code() {
+            return CommandError::Exited(code);
+        }
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::process::ExitStatusExt;
+            if let Some(signal) = status.signal() {
+                return CommandError::Exited(128 + signal);
+            }
+        }
+
+        CommandError::Failed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_status_uses_child_code() {
+        let status = std::process::Command::new("sh")
+            .args(["-c", "exit 42"])
+            .status()
+            .unwrap();
+
+        assert_eq!(CommandError::from_status(status).exit_code(), 42);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_from_status_maps_signals() {
+        let status = std::process::Command::new("sh")
+            .args(["-c", "kill -TERM $$"])
+            .status()
+            .unwrap();
+
+        assert_eq!(CommandError::from_status(status).exit_code(), 128 + 15);
+    }
+}
@@ -1,20 +1,163 @@
-use crate::{file::FileWriter, storage::Storage};
+use std::collections::HashSet;
 
-pub fn export_command(storage: &dyn Storage, writer: &dyn FileWriter, file_path: &str) {
-    let store = match storage.load() {
-        Ok(s) => s,
-        Err(_) => {
-            println!("📭 No snippets saved yet.");
-            return;
+use crate::{
+    commands::{
+        error::{CommandError, CommandResult},
+        helper::load_store,
+    },
+    file::{FileFormat, FileWriter, STDIO_PATH},
+    models::{Snippet, SnippetStore},
+    storage::{Storage, filter::fuzzy_rank},
+};
+
+/// Which snippets to export. Repeated names or tags match any of them; the
+/// different kinds of filter must all match.
+#[derive(Default)]
+pub struct ExportOptions {
+    pub names: Vec<String>,
+    pub tags: Vec<String>,
+    pub exclude_tags: Vec<String>,
+    pub search: Option<String>,
+    /// Leave out when snippets and their revisions were created or updated,
+    /// so an export of unchanged snippets is always the same file.
+    pub strip_timestamps: bool,
+    /// Detected from the file extension if not given.
+    pub format: Option<FileFormat>,
+}
+
+impl ExportOptions {
+    fn has_filters(&self) -> bool {
+        !self.names.is_empty()
+            || !self.tags.is_empty()
+            || !self.exclude_tags.is_empty()
+            || self.search.is_some()
+    }
+
+    fn matches(&self, snippet: &Snippet) -> bool {
+        (self.names.is_empty()
+            || self
+                .names
+                .iter()
+                .any(|name| snippet.name.eq_ignore_ascii_case(name)))
+            && (self.tags.is_empty() || has_any_tag(snippet, &self.tags))
+            && !has_any_tag(snippet, &self.exclude_tags)
+    }
+}
+
+fn has_any_tag(snippet: &Snippet, tags: &[String]) -> bool {
+    snippet
+        .tags
+        .iter()
+        .any(|t| tags.iter().any(|tag| t.eq_ignore_ascii_case(tag)))
+}
+
+/// Writes the selected snippets to `file_path`, or to stdout if it is `-`.
+pub fn export_command(
+    storage: &dyn Storage,
+    writer: &dyn FileWriter,
+    file_path: &str,
+    options: &ExportOptions,
+) -> CommandResult {
+    let store = load_store(storage)?;
+
+    if let Some(missing) = options.names.iter().find(|name| {
+        !store
+            .snippets
+            .iter()
+            .any(|s| s.name.eq_ignore_ascii_case(name))
+    }) {
+        eprintln!("⛔ Snippet '{}' not found.", missing);
+        return Err(CommandError::NotFound);
+    }
+
+    let found: Option<HashSet<String>> = options.search.as_deref().map(|query| {
+        fuzzy_rank(&store, query)
+            .into_iter()
+            .map(|m| m.snippet.id)
+            .collect()
+    });
+
+    // Deleted snippets stay behind.
+    let selected = SnippetStore {
+        snippets: store
+            .snippets
+            .into_iter()
+            .filter(|s| options.matches(s))
+            .filter(|s| found.as_ref().is_none_or(|ids| ids.contains(&s.id)))
+            .collect(),
+        trash: vec![],
+    };
+
+    // Without filters an empty store is exported as it is.
+    if selected.snippets.is_empty() && options.has_filters() {
+        eprintln!("📭 No snippets matched, nothing exported.");
+        return Err(CommandError::NotFound);
+    }
+
+    let document = match to_document(&selected, options.strip_timestamps) {
+        Ok(document) => document,
+        Err(e) => {
+            eprintln!("⛔ Failed to export snippets: {e}");
+            return Err(CommandError::Failed);
         }
     };
 
-    match writer.write_yaml(file_path, &store) {
-        Ok(_) => println!("📦 Snippets exported to {file_path}"),
-        Err(e) => eprintln!("⛔ Failed to export snippets: {e}"),
+    match writer.write(
+        file_path,
+        options
+            .format
+            .unwrap_or_else(|| FileFormat::detect(file_path)),
+        &document,
+    ) {
+        Ok(_) if file_path == STDIO_PATH => {
+            eprintln!("📦 {} snippet(s) exported.", selected.snippets.len());
+            Ok(())
+        }
+        Ok(_) => {
+            println!(
+                "📦 {} snippet(s) exported to {file_path}",
+                selected.snippets.len()
+            );
+            Ok(())
+        }
+        Err(e) => {
+            eprintln!("⛔ Failed to export snippets: {e}");
+            Err(CommandError::Failed)
+        }
     }
 }
 
+fn to_document(
+    store: &SnippetStore,
+    strip_timestamps: bool,
+) -> Result<serde_yaml::Value, serde_yaml::Error> {
+    let mut document = serde_yaml::to_value(store)?;
+    if !strip_timestamps {
+        return Ok(document);
+    }
+
+    let snippets = document
+        .get_mut("snippets")
+        .and_then(|s| s.as_sequence_mut())
+        .into_iter()
+        .flatten()
+        .filter_map(|s| s.as_mapping_mut());
+    for snippet in snippets {
+        snippet.remove("created_at");
+        snippet.remove("updated_at");
+        let revisions = snippet
+            .get_mut("revisions")
+            .and_then(|r| r.as_sequence_mut())
+            .into_iter()
+            .flatten()
+            .filter_map(|r| r.as_mapping_mut());
+        for revision in revisions {
+            revision.remove("saved_at");
+        }
+    }
+    Ok(document)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -35,6 +178,7 @@ mod tests {
             } else {
                 Ok(SnippetStore {
                     snippets: self.snippets.clone(),
+                    trash: vec![],
                 })
             }
         }
@@ -59,11 +203,43 @@ mod tests {
     struct MockFileWriter {
         should_fail: bool,
         called_with: RefCell<Option<String>>, // Track what was passed
+        written: RefCell<Option<serde_yaml::Value>>,
+        format: RefCell<Option<FileFormat>>,
+    }
+
+    impl MockFileWriter {
+        fn new(should_fail: bool) -> Self {
+            MockFileWriter {
+                should_fail,
+                called_with: RefCell::new(None),
+                written: RefCell::new(None),
+                format: RefCell::new(None),
+            }
+        }
+
+        fn exported(&self) -> SnippetStore {
+            serde_yaml::from_value(self.written.borrow().clone().unwrap()).unwrap()
+        }
+
+        fn exported_names(&self) -> Vec<String> {
+            self.exported()
+                .snippets
+                .into_iter()
+                .map(|s| s.name)
+                .collect()
+        }
     }
 
     impl FileWriter for MockFileWriter {
-        fn write_yaml(&self, file_path: &str, _store: &SnippetStore) -> Result<(), String> {
+        fn write(
+            &self,
+            file_path: &str,
+            format: FileFormat,
+            document: &serde_yaml::Value,
+        ) -> Result<(), String> {
             self.called_with.replace(Some(file_path.to_string()));
+            self.format.replace(Some(format));
+            self.written.replace(Some(document.clone()));
             if self.should_fail {
                 Err("Failed to write".to_string())
             } else {
@@ -72,30 +248,44 @@ mod tests {
         }
     }
 
-    #[test]
-    fn test_export_command_success() {
-        let snippet = Snippet {
-            name: "example".to_string(),
+    fn snippet(name: &str, tags: &[&str]) -> Snippet {
+        Snippet {
+            id: Snippet::new_id(),
+            name: name.to_string(),
             description: "desc".to_string(),
-            content: "echo hi".to_string(),
+            content: format!("echo {}", name),
+            language: None,
             executable: true,
-            tags: vec!["tag".to_string()],
+            tags: tags.iter().map(|t| t.to_string()).collect(),
             created_at: chrono::Utc::now(),
             updated_at: chrono::Utc::now(),
-        };
+            revisions: vec![],
+        }
+    }
 
-        let storage = MockStorage {
-            snippets: vec![snippet],
+    fn storage() -> MockStorage {
+        MockStorage {
+            snippets: vec![
+                snippet("deploy", &["ops", "prod"]),
+                snippet("backup", &["ops"]),
+                snippet("greet", &["fun"]),
+            ],
             should_fail: false,
-        };
+        }
+    }
 
-        let writer = MockFileWriter {
+    #[test]
+    fn test_export_command_success() {
+        let storage = MockStorage {
+            snippets: vec![snippet("example", &["tag"])],
             should_fail: false,
-            called_with: RefCell::new(None),
         };
 
-        export_command(&storage, &writer, "output.yml");
+        let writer = MockFileWriter::new(false);
+
+        assert!(export_command(&storage, &writer, "output.yml", &ExportOptions::default()).is_ok());
         assert_eq!(writer.called_with.borrow().as_deref(), Some("output.yml"));
+        assert_eq!(writer.exported_names(), vec!["example"]);
     }
 
     #[test]
@@ -105,38 +295,148 @@ mod tests {
             should_fail: true,
         };
 
-        let writer = MockFileWriter {
+        let writer = MockFileWriter::new(false);
+
+        assert_eq!(
+            export_command(&storage, &writer, "output.yml", &ExportOptions::default()),
+            Err(CommandError::Storage)
+        );
+        assert!(writer.called_with.borrow().is_none());
+    }
+
+    #[test]
+    fn test_export_command_writer_failure() {
+        let storage = MockStorage {
+            snippets: vec![snippet("example", &["tag"])],
             should_fail: false,
-            called_with: RefCell::new(None),
         };
 
-        export_command(&storage, &writer, "output.yml");
+        let writer = MockFileWriter::new(true);
+
+        assert_eq!(
+            export_command(&storage, &writer, "output.yml", &ExportOptions::default()),
+            Err(CommandError::Failed)
+        );
+        assert_eq!(writer.called_with.borrow().as_deref(), Some("output.yml"));
+    }
+
+    #[test]
+    fn test_export_filters_by_tag_and_name() {
+        let writer = MockFileWriter::new(false);
+        let options = ExportOptions {
+            tags: vec!["OPS".to_string()],
+            exclude_tags: vec!["prod".to_string()],
+            ..Default::default()
+        };
+
+        assert!(export_command(&storage(), &writer, "-", &options).is_ok());
+        assert_eq!(writer.exported_names(), vec!["backup"]);
+
+        let options = ExportOptions {
+            names: vec!["greet".to_string(), "Deploy".to_string()],
+            ..Default::default()
+        };
+
+        assert!(export_command(&storage(), &writer, "-", &options).is_ok());
+        assert_eq!(writer.exported_names(), vec!["deploy", "greet"]);
+    }
+
+    #[test]
+    fn test_export_search() {
+        let writer = MockFileWriter::new(false);
+        let options = ExportOptions {
+            search: Some("echo gre".to_string()),
+            ..Default::default()
+        };
+
+        assert!(export_command(&storage(), &writer, "-", &options).is_ok());
+        assert_eq!(writer.exported_names(), vec!["greet"]);
+    }
+
+    #[test]
+    fn test_export_unknown_name() {
+        let writer = MockFileWriter::new(false);
+        let options = ExportOptions {
+            names: vec!["deploy".to_string(), "missing".to_string()],
+            ..Default::default()
+        };
+
+        assert_eq!(
+            export_command(&storage(), &writer, "-", &options),
+            Err(CommandError::NotFound)
+        );
         assert!(writer.called_with.borrow().is_none());
     }
 
     #[test]
-    fn test_export_command_writer_failure() {
-        let snippet = Snippet {
-            name: "example".to_string(),
-            description: "desc".to_string(),
-            content: "echo hi".to_string(),
-            executable: true,
-            tags: vec!["tag".to_string()],
-            created_at: chrono::Utc::now(),
-            updated_at: chrono::Utc::now(),
+    fn test_export_nothing_matches() {
+        let writer = MockFileWriter::new(false);
+        let options = ExportOptions {
+            tags: vec!["none".to_string()],
+            ..Default::default()
         };
 
+        assert_eq!(
+            export_command(&storage(), &writer, "-", &options),
+            Err(CommandError::NotFound)
+        );
+        assert!(writer.called_with.borrow().is_none());
+    }
+
+    #[test]
+    fn test_export_empty_store() {
         let storage = MockStorage {
-            snippets: vec![snippet],
+            snippets: vec![],
             should_fail: false,
         };
+        let writer = MockFileWriter::new(false);
 
-        let writer = MockFileWriter {
-            should_fail: true,
-            called_with: RefCell::new(None),
+        assert!(export_command(&storage, &writer, "out.yml", &ExportOptions::default()).is_ok());
+        assert_eq!(writer.called_with.borrow().as_deref(), Some("out.yml"));
+        assert!(writer.exported_names().is_empty());
+    }
+
+    #[test]
+    fn test_export_strip_timestamps() {
+        let mut edited = snippet("deploy", &[]);
+        let previous = edited.clone();
+        edited.content = "deploy.sh".to_string();
+        edited.keep_revision(&previous);
+        let storage = MockStorage {
+            snippets: vec![edited],
+            should_fail: false,
+        };
+        let writer = MockFileWriter::new(false);
+        let options = ExportOptions {
+            strip_timestamps: true,
+            ..Default::default()
         };
 
-        export_command(&storage, &writer, "output.yml");
-        assert_eq!(writer.called_with.borrow().as_deref(), Some("output.yml"));
+        assert!(export_command(&storage, &writer, "-", &options).is_ok());
+
+        let yaml = serde_yaml::to_string(writer.written.borrow().as_ref().unwrap()).unwrap();
+        assert!(!yaml.contains("created_at"));
+        assert!(!yaml.contains("updated_at"));
+        assert!(!yaml.contains("saved_at"));
+        let exported = writer.exported();
+        assert_eq!(exported.snippets[0].content, "deploy.sh");
+        assert_eq!(exported.snippets[0].revisions.len(), 1);
+    }
+
+    #[test]
+    fn test_export_format() {
+        let writer = MockFileWriter::new(false);
+
+        assert!(
+            export_command(&storage(), &writer, "team.JSON", &ExportOptions::default()).is_ok()
+        );
+        assert_eq!(*writer.format.borrow(), Some(FileFormat::Json));
+
+        let options = ExportOptions {
+            format: Some(FileFormat::Toml),
+            ..Default::default()
+        };
+        assert!(export_command(&storage(), &writer, "-", &options).is_ok());
+        assert_eq!(*writer.format.borrow(), Some(FileFormat::Toml));
     }
 }
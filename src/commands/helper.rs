@@ -1,23 +1,158 @@
+use std::{
+    cmp::Reverse,
+    collections::{BTreeMap, HashMap},
+};
+
 use crate::{
+    commands::error::CommandError,
+    input::VariableInput,
     models::{PartialSnippet, Snippet, SnippetStore},
-    storage::filter::{self, Filter},
+    placeholder,
+    storage::{
+        Storage, StorageError, StoreLock, filter,
+        usage::{UsageKind, UsageStats},
+    },
     ui::SelectionUI,
 };
 
 pub fn get_snippet(
+    storage: &dyn Storage,
     store: &SnippetStore,
     selection_ui: &dyn SelectionUI,
     name: String,
-) -> Option<Snippet> {
-    let filtered = filter::apply_filter(store, Filter::Name(name.clone()));
+) -> Result<Snippet, CommandError> {
+    if let Some(exact) = store
+        .snippets
+        .iter()
+        .find(|s| s.name.eq_ignore_ascii_case(&name))
+    {
+        return Ok(exact.clone());
+    }
+
+    let mut ranked = filter::fuzzy_rank(store, &name);
+
+    if ranked.is_empty() {
+        println!("⛔ Snippet '{}' not found.", name);
+        return Err(CommandError::NotFound);
+    }
+
+    // Frecency only breaks ties, so the best match stays preselected even
+    // when a weaker one is used more often.
+    if ranked.len() > 1 {
+        load_usage(storage).sort_by_frecency(&mut ranked, |m| &m.snippet.id);
+        ranked.sort_by_key(|m| Reverse(m.score));
+    }
+    let filtered: Vec<Snippet> = ranked.into_iter().map(|m| m.snippet).collect();
+
+    // Only an exact name skips the prompt; a fuzzy match, even a lone one,
+    // is preselected for the user to confirm.
+    if filtered.len() == 1 && !selection_ui.is_interactive() {
+        eprintln!(
+            "⛔ Snippet '{}' not found. Did you mean '{}'?",
+            name, filtered[0].name
+        );
+        return Err(CommandError::NotFound);
+    }
+
+    if !selection_ui.is_interactive() {
+        eprintln!("⛔ '{}' matches several snippets:", name);
+        for snippet in &filtered {
+            eprintln!("   {}", snippet.name);
+        }
+        return Err(CommandError::Ambiguous);
+    }
 
     match selection_ui.with_snippet_list(filtered) {
-        Some(s) => Some(s),
+        Some(s) => Ok(s),
         None => {
-            println!("⛔ Snippet '{}' not found.", name);
-            None
+            println!("🚫 No snippet selected.");
+            Err(CommandError::Cancelled)
+        }
+    }
+}
+
+/// Loads the store, reporting why it could not be loaded. A corrupt store is
+/// reported separately so it is not mistaken for an empty one.
+pub fn load_store(storage: &dyn Storage) -> Result<SnippetStore, CommandError> {
+    match storage.load() {
+        Ok(store) => Ok(store),
+        Err(e @ StorageError::Corrupt { .. }) => {
+            eprintln!("⛔ {}", e);
+            eprintln!(
+                "💡 Run `markit doctor --repair` to recover from the latest readable backup."
+            );
+            Err(CommandError::Storage)
+        }
+        Err(e) => {
+            eprintln!("⛔ Failed to load snippets: {}", e);
+            Err(CommandError::Storage)
+        }
+    }
+}
+
+/// Usage only affects ordering, so failing to read it is reported but not
+/// fatal.
+pub fn load_usage(storage: &dyn Storage) -> UsageStats {
+    storage.load_usage().unwrap_or_else(|e| {
+        eprintln!("⚠️ Failed to load usage statistics: {}", e);
+        UsageStats::default()
+    })
+}
+
+pub fn record_usage(storage: &dyn Storage, id: &str, kind: UsageKind) {
+    let now = chrono::Utc::now();
+    if let Err(e) = storage.update_usage(&mut |usage| usage.record(id, kind, now)) {
+        eprintln!("⚠️ Failed to record usage: {}", e);
+    }
+}
+
+pub fn lock_storage(storage: &dyn Storage) -> Result<StoreLock, CommandError> {
+    storage.lock().map_err(|e| {
+        eprintln!("⛔ {}", e);
+        CommandError::Storage
+    })
+}
+
+pub fn resolve_content(
+    content: &str,
+    variable_input: &dyn VariableInput,
+    vars: &[(String, String)],
+) -> Result<String, CommandError> {
+    let values = resolve_variables(content, variable_input, vars)?;
+    Ok(placeholder::substitute(
+        content,
+        &values.into_iter().collect(),
+    ))
+}
+
+/// Collects a value for every placeholder in `content`, taking `--var` values
+/// first and prompting for the rest.
+pub fn resolve_variables(
+    content: &str,
+    variable_input: &dyn VariableInput,
+    vars: &[(String, String)],
+) -> Result<BTreeMap<String, String>, CommandError> {
+    let given: HashMap<&str, &str> = vars.iter().map(|(k, v)| (k.as_str(), v.as_str())).collect();
+    let mut values = BTreeMap::new();
+
+    for placeholder in placeholder::parse_placeholders(content) {
+        if let Some(value) = given.get(placeholder.name.as_str()) {
+            values.insert(placeholder.name, value.to_string());
+            continue;
+        }
+
+        match variable_input.get_value(&placeholder.name, placeholder.default.as_deref()) {
+            Some(value) => {
+                values.insert(placeholder.name, value);
+            }
+            None => {
+                eprintln!("⛔ No value provided for '{}'.", placeholder.name);
+                return Err(CommandError::Validation);
+            }
         }
     }
+
+    Ok(values)
 }
 
 pub fn redact_snippet(snippet: &Snippet) -> PartialSnippet {
@@ -25,7 +160,105 @@ pub fn redact_snippet(snippet: &Snippet) -> PartialSnippet {
         name: snippet.name.clone(),
         description: snippet.description.clone(),
         content: snippet.content.clone(),
+        language: snippet.language.clone(),
         executable: snippet.executable,
         tags: snippet.tags.clone(),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use std::cell::RefCell;
+
+    struct MockStorage {
+        usage: UsageStats,
+    }
+
+    impl Storage for MockStorage {
+        fn load(&self) -> Result<SnippetStore, StorageError> {
+            Ok(SnippetStore::default())
+        }
+
+        fn save(&self, _: Snippet) -> Result<(), StorageError> {
+            Ok(())
+        }
+
+        fn save_all(&self, _: &SnippetStore) -> Result<(), StorageError> {
+            Ok(())
+        }
+
+        fn get_backups(&self) -> Result<Vec<std::path::PathBuf>, StorageError> {
+            Ok(vec![])
+        }
+
+        fn restore_backup(&self, _: &std::path::Path) -> Result<(), StorageError> {
+            Ok(())
+        }
+
+        fn load_usage(&self) -> Result<UsageStats, StorageError> {
+            Ok(self.usage.clone())
+        }
+    }
+
+    /// Picks the first snippet offered and records the order.
+    struct MockSelectionUI {
+        offered: RefCell<Vec<String>>,
+    }
+
+    impl SelectionUI for MockSelectionUI {
+        fn with_snippet_list(&self, snippets: Vec<Snippet>) -> Option<Snippet> {
+            self.offered
+                .replace(snippets.iter().map(|s| s.name.clone()).collect());
+            snippets.into_iter().next()
+        }
+
+        fn with_backup_list(&self, _: &[String]) -> Option<usize> {
+            None
+        }
+
+        fn with_multi_select(&self, _: &str, _: &[String]) -> Vec<usize> {
+            vec![]
+        }
+    }
+
+    fn snippet(name: &str) -> Snippet {
+        Snippet {
+            id: format!("id-{}", name),
+            name: name.to_string(),
+            description: String::new(),
+            content: "echo hi".to_string(),
+            language: None,
+            executable: true,
+            tags: vec![],
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            revisions: vec![],
+        }
+    }
+
+    #[test]
+    fn test_strong_match_beats_frequently_used_weak_match() {
+        let store = SnippetStore {
+            snippets: vec![snippet("dev-playground-yaml"), snippet("deploy-prod")],
+            trash: vec![],
+        };
+        let mut usage = UsageStats::default();
+        for _ in 0..50 {
+            usage.record("id-dev-playground-yaml", UsageKind::Run, Utc::now());
+        }
+        let storage = MockStorage { usage };
+        let selection_ui = MockSelectionUI {
+            offered: RefCell::new(vec![]),
+        };
+
+        let chosen = get_snippet(&storage, &store, &selection_ui, "deploy".to_string()).unwrap();
+
+        assert_eq!(chosen.name, "deploy-prod");
+        assert_eq!(
+            *selection_ui.offered.borrow(),
+            vec!["deploy-prod", "dev-playground-yaml"]
+        );
+    }
+}
@@ -0,0 +1,436 @@
+use std::collections::BTreeMap;
+
+use chrono::{Local, NaiveDate};
+use clap::ValueEnum;
+use serde::Serialize;
+
+use crate::{
+    command_runner::{CommandRunner, RunRequest},
+    commands::{
+        error::{CommandError, CommandResult},
+        run::execute_and_record,
+    },
+    storage::{HistoryLog, Storage, history::HistoryEntry},
+    ui::{HistoryFormat, TableUI},
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, ValueEnum)]
+pub enum RunStatus {
+    Success,
+    Failed,
+}
+
+#[derive(Default)]
+pub struct HistoryFilter {
+    pub snippet: Option<String>,
+    pub status: Option<RunStatus>,
+    /// Local dates, inclusive.
+    pub since: Option<NaiveDate>,
+    pub until: Option<NaiveDate>,
+    pub limit: Option<usize>,
+}
+
+impl HistoryFilter {
+    /// `snippet_id` is the id of the snippet currently called `self.snippet`,
+    /// so runs from before a rename match too.
+    fn matches(&self, entry: &HistoryEntry, snippet_id: Option<&str>) -> bool {
+        let date = entry.started_at.with_timezone(&Local).date_naive();
+
+        self.snippet.as_deref().is_none_or(|name| {
+            entry.snippet.eq_ignore_ascii_case(name)
+                || snippet_id.is_some_and(|id| entry.snippet_id.as_deref() == Some(id))
+        }) && self.status.is_none_or(|status| match status {
+            RunStatus::Success => entry.succeeded(),
+            RunStatus::Failed => !entry.succeeded(),
+        }) && self.since.is_none_or(|since| date >= since)
+            && self.until.is_none_or(|until| date <= until)
+    }
+}
+
+#[derive(Serialize)]
+struct NumberedEntry<'a> {
+    number: usize,
+    #[serde(flatten)]
+    entry: &'a HistoryEntry,
+}
+
+pub fn history_command(
+    storage: &dyn Storage,
+    history: &dyn HistoryLog,
+    table_ui: &mut dyn TableUI,
+    filter: &HistoryFilter,
+    format: HistoryFormat,
+) -> CommandResult {
+    let entries = load_history(history)?;
+
+    // A missing or unreadable store only loses the match on renamed snippets.
+    let snippet_id = filter.snippet.as_deref().and_then(|name| {
+        storage
+            .load()
+            .ok()?
+            .snippets
+            .into_iter()
+            .find(|s| s.name.eq_ignore_ascii_case(name))
+            .map(|s| s.id)
+    });
+
+    let matching: Vec<(usize, HistoryEntry)> = numbered(entries)
+        .filter(|(_, entry)| filter.matches(entry, snippet_id.as_deref()))
+        .take(filter.limit.unwrap_or(usize::MAX))
+        .collect();
+
+    if format == HistoryFormat::Json {
+        let numbered: Vec<NumberedEntry> = matching
+            .iter()
+            .map(|(number, entry)| NumberedEntry {
+                number: *number,
+                entry,
+            })
+            .collect();
+        return match serde_json::to_string_pretty(&numbered) {
+            Ok(json) => {
+                println!("{}", json);
+                Ok(())
+            }
+            Err(e) => {
+                eprintln!("⛔ Failed to format history: {}", e);
+                Err(CommandError::Failed)
+            }
+        };
+    }
+
+    if matching.is_empty() {
+        println!("📭 No runs found.");
+        return Ok(());
+    }
+
+    let table = table_ui.with_history(matching);
+    println!("{table}");
+    Ok(())
+}
+
+/// Runs entry `number` of the history (1 is the latest) again with the same
+/// command, arguments and working directory. Values given with `--env` are
+/// not kept, so those variables come from the current environment.
+pub fn rerun_command(
+    history: &dyn HistoryLog,
+    runner: &dyn CommandRunner,
+    number: usize,
+) -> CommandResult {
+    let entries = load_history(history)?;
+
+    let Some((_, entry)) = numbered(entries).find(|(n, _)| *n == number) else {
+        eprintln!("⛔ No run #{} in the history.", number);
+        return Err(CommandError::NotFound);
+    };
+
+    let request = RunRequest {
+        name: entry.snippet.clone(),
+        command: entry.command.clone(),
+        language: entry.language.clone(),
+        args: entry.args.clone(),
+        env: BTreeMap::new(),
+        cwd: Some(entry.cwd.clone()),
+    };
+    for key in &entry.env_keys {
+        if std::env::var_os(key).is_none() {
+            eprintln!(
+                "⚠️ {} was set with --env for that run, but its value is not kept and it is not set now.",
+                key
+            );
+        }
+    }
+    let plan = match runner.plan(&request) {
+        Ok(plan) => plan,
+        Err(e) => {
+            eprintln!("⛔ {}", e);
+            return Err(CommandError::Validation);
+        }
+    };
+
+    println!("🔁 Re-running #{}: {}", number, entry.snippet);
+    println!("📋 {}", entry.command);
+
+    execute_and_record(runner, history, &plan, entry)
+}
+
+fn load_history(history: &dyn HistoryLog) -> Result<Vec<HistoryEntry>, CommandError> {
+    history.entries().map_err(|e| {
+        eprintln!("⛔ Failed to read history: {}", e);
+        CommandError::Storage
+    })
+}
+
+/// Entries newest first, numbered from 1.
+fn numbered(entries: Vec<HistoryEntry>) -> impl Iterator<Item = (usize, HistoryEntry)> {
+    entries
+        .into_iter()
+        .rev()
+        .enumerate()
+        .map(|(i, entry)| (i + 1, entry))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        command_runner::ExecutionPlan,
+        models::{Snippet, SnippetStore},
+        storage::{StorageError, filter::FuzzyMatch, retention::BackupInfo, usage::UsageStats},
+    };
+    use chrono::{TimeZone, Utc};
+    use std::{cell::RefCell, collections::BTreeMap, path::PathBuf, process::ExitStatus};
+
+    struct MockHistory {
+        entries: RefCell<Vec<HistoryEntry>>,
+    }
+
+    impl HistoryLog for MockHistory {
+        fn append(&self, entry: &HistoryEntry) -> Result<(), StorageError> {
+            self.entries.borrow_mut().push(entry.clone());
+            Ok(())
+        }
+
+        fn entries(&self) -> Result<Vec<HistoryEntry>, StorageError> {
+            Ok(self.entries.borrow().clone())
+        }
+    }
+
+    /// Holds "deploy", renamed to "release".
+    struct MockStorage;
+
+    impl Storage for MockStorage {
+        fn load(&self) -> Result<SnippetStore, StorageError> {
+            Ok(SnippetStore {
+                snippets: vec![Snippet {
+                    id: "id-deploy".to_string(),
+                    name: "release".to_string(),
+                    description: String::new(),
+                    content: "deploy.sh".to_string(),
+                    language: None,
+                    executable: true,
+                    tags: vec![],
+                    created_at: Utc::now(),
+                    updated_at: Utc::now(),
+                    revisions: vec![],
+                }],
+                trash: vec![],
+            })
+        }
+
+        fn save(&self, _: Snippet) -> Result<(), StorageError> {
+            Ok(())
+        }
+
+        fn save_all(&self, _: &SnippetStore) -> Result<(), StorageError> {
+            Ok(())
+        }
+
+        fn get_backups(&self) -> Result<Vec<PathBuf>, StorageError> {
+            Ok(vec![])
+        }
+
+        fn restore_backup(&self, _: &std::path::Path) -> Result<(), StorageError> {
+            Ok(())
+        }
+    }
+
+    struct MockTableUI {
+        rows: RefCell<Vec<usize>>,
+    }
+
+    impl TableUI for MockTableUI {
+        fn with_snippet_list(&mut self, _: Vec<Snippet>, _: &UsageStats) -> comfy_table::Table {
+            comfy_table::Table::new()
+        }
+
+        fn with_search_results(&mut self, _: Vec<FuzzyMatch>) -> comfy_table::Table {
+            comfy_table::Table::new()
+        }
+
+        fn with_backup_list(&mut self, _: Vec<BackupInfo>) -> comfy_table::Table {
+            comfy_table::Table::new()
+        }
+
+        fn with_history(&mut self, entries: Vec<(usize, HistoryEntry)>) -> comfy_table::Table {
+            self.rows.replace(entries.iter().map(|(n, _)| *n).collect());
+            comfy_table::Table::new()
+        }
+    }
+
+    struct MockCommandRunner {
+        last_request: RefCell<Option<RunRequest>>,
+    }
+
+    impl CommandRunner for MockCommandRunner {
+        fn plan(&self, request: &RunRequest) -> Result<ExecutionPlan, String> {
+            self.last_request.replace(Some(request.clone()));
+            Ok(ExecutionPlan {
+                program: "sh".to_string(),
+                args: vec!["-c".to_string(), request.command.clone()],
+                cwd: PathBuf::from("."),
+                env: request.env.clone(),
+                script: None,
+            })
+        }
+
+        fn execute(&self, _: &ExecutionPlan) -> Result<ExitStatus, std::io::Error> {
+            std::process::Command::new("true").status()
+        }
+    }
+
+    fn entry(snippet: &str, day: u32, exit_code: Option<i32>) -> HistoryEntry {
+        HistoryEntry {
+            snippet: snippet.to_string(),
+            snippet_id: Some(format!("id-{}", snippet)),
+            command: format!("deploy.sh {}", snippet),
+            language: None,
+            variables: BTreeMap::new(),
+            args: vec!["--force".to_string()],
+            env_keys: vec!["MARKIT_TEST_UNSET_STAGE".to_string()],
+            cwd: PathBuf::from("/srv"),
+            started_at: Utc.with_ymd_and_hms(2025, 3, day, 12, 0, 0).unwrap(),
+            duration_ms: 100,
+            exit_code,
+        }
+    }
+
+    fn history() -> MockHistory {
+        MockHistory {
+            entries: RefCell::new(vec![
+                entry("deploy", 1, Some(0)),
+                entry("backup", 2, Some(1)),
+                entry("deploy", 3, None),
+            ]),
+        }
+    }
+
+    #[test]
+    fn test_history_numbers_newest_first() {
+        let mut table_ui = MockTableUI {
+            rows: RefCell::new(vec![]),
+        };
+
+        assert!(
+            history_command(
+                &MockStorage,
+                &history(),
+                &mut table_ui,
+                &HistoryFilter::default(),
+                HistoryFormat::Table
+            )
+            .is_ok()
+        );
+
+        assert_eq!(*table_ui.rows.borrow(), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_history_filters_keep_numbers() {
+        let mut table_ui = MockTableUI {
+            rows: RefCell::new(vec![]),
+        };
+        let filter = HistoryFilter {
+            snippet: Some("deploy".to_string()),
+            status: Some(RunStatus::Failed),
+            ..Default::default()
+        };
+
+        assert!(
+            history_command(
+                &MockStorage,
+                &history(),
+                &mut table_ui,
+                &filter,
+                HistoryFormat::Table
+            )
+            .is_ok()
+        );
+
+        assert_eq!(*table_ui.rows.borrow(), vec![1]);
+    }
+
+    #[test]
+    fn test_history_follows_renamed_snippets() {
+        let mut table_ui = MockTableUI {
+            rows: RefCell::new(vec![]),
+        };
+        let filter = HistoryFilter {
+            snippet: Some("release".to_string()),
+            ..Default::default()
+        };
+
+        assert!(
+            history_command(
+                &MockStorage,
+                &history(),
+                &mut table_ui,
+                &filter,
+                HistoryFormat::Table
+            )
+            .is_ok()
+        );
+
+        assert_eq!(*table_ui.rows.borrow(), vec![1, 3]);
+    }
+
+    #[test]
+    fn test_history_filters_by_date() {
+        let mut table_ui = MockTableUI {
+            rows: RefCell::new(vec![]),
+        };
+        let filter = HistoryFilter {
+            since: NaiveDate::from_ymd_opt(2025, 3, 2),
+            until: NaiveDate::from_ymd_opt(2025, 3, 2),
+            ..Default::default()
+        };
+
+        assert!(
+            history_command(
+                &MockStorage,
+                &history(),
+                &mut table_ui,
+                &filter,
+                HistoryFormat::Table
+            )
+            .is_ok()
+        );
+
+        assert_eq!(*table_ui.rows.borrow(), vec![2]);
+    }
+
+    #[test]
+    fn test_rerun_replays_recorded_values() {
+        let history = history();
+        let runner = MockCommandRunner {
+            last_request: RefCell::new(None),
+        };
+
+        assert!(rerun_command(&history, &runner, 2).is_ok());
+
+        let request = runner.last_request.borrow().clone().unwrap();
+        assert_eq!(request.name, "backup");
+        assert_eq!(request.command, "deploy.sh backup");
+        assert_eq!(request.args, vec!["--force"]);
+        assert!(request.env.is_empty());
+        assert_eq!(request.cwd, Some(PathBuf::from("/srv")));
+
+        let entries = history.entries.borrow();
+        assert_eq!(entries.len(), 4);
+        assert_eq!(entries[3].snippet, "backup");
+        assert_eq!(entries[3].exit_code, Some(0));
+    }
+
+    #[test]
+    fn test_rerun_unknown_number() {
+        let runner = MockCommandRunner {
+            last_request: RefCell::new(None),
+        };
+
+        assert_eq!(
+            rerun_command(&history(), &runner, 9),
+            Err(CommandError::NotFound)
+        );
+        assert!(runner.last_request.borrow().is_none());
+    }
+}
@@ -1,46 +1,238 @@
-use crate::{file::FileReader, storage::Storage};
+use chrono::Utc;
+use clap::ValueEnum;
+
+use crate::{
+    commands::{
+        error::{CommandError, CommandResult},
+        helper::{load_store, lock_storage},
+    },
+    diff::{describe_modification, same_snippet},
+    file::{
+        FileFormat, FileReader, STDIO_PATH,
+        convert::{Conversion, ImportSource},
+    },
+    models::{Snippet, SnippetStore},
+    storage::Storage,
+    ui::ConfirmPrompt,
+};
+
+/// What to do with an imported snippet that already exists, matched by id or
+/// by name (ignoring case).
+#[derive(Clone, Copy, Debug, Default, PartialEq, ValueEnum)]
+pub enum ConflictStrategy {
+    #[default]
+    Skip,
+    Overwrite,
+    /// Import it under a free name such as `deploy-2`.
+    Rename,
+    /// Overwrite only if the imported snippet was updated more recently.
+    Newer,
+    Prompt,
+}
+
+#[derive(Default)]
+pub struct ImportOptions {
+    pub on_conflict: ConflictStrategy,
+    pub dry_run: bool,
+    /// Detected from the file extension if not given.
+    pub format: Option<FileFormat>,
+    /// Convert another tool's snippets instead of reading a markit file.
+    pub from: Option<ImportSource>,
+}
+
+#[derive(Debug, PartialEq)]
+enum ImportAction {
+    Added,
+    Updated,
+    Renamed(String),
+    Skipped(&'static str),
+}
 
-pub fn import_command(storage: &dyn Storage, reader: &dyn FileReader, file_path: &str) {
-    let imported = match reader.read_yaml(file_path) {
-        Ok(store) => store,
+pub fn import_command(
+    storage: &dyn Storage,
+    reader: &dyn FileReader,
+    confirm: &dyn ConfirmPrompt,
+    file_path: &str,
+    options: &ImportOptions,
+) -> CommandResult {
+    let read = match options.from {
+        Some(source) => reader.convert(file_path, source),
+        None => reader
+            .read(
+                file_path,
+                options
+                    .format
+                    .unwrap_or_else(|| FileFormat::detect(file_path)),
+            )
+            .map(|store| Conversion {
+                store,
+                unmapped: vec![],
+            }),
+    };
+    let Conversion {
+        store: imported,
+        unmapped,
+    } = match read {
+        Ok(conversion) => conversion,
         Err(e) => {
             eprintln!("⛔ Failed to read import file: {}", e);
-            return;
+            return Err(CommandError::Validation);
         }
     };
 
-    let mut store = match storage.load() {
-        Ok(s) => s,
-        Err(_) => {
-            println!("📭 No snippets saved yet.");
-            return;
-        }
-    };
+    let _lock = lock_storage(storage)?;
+
+    let mut store = load_store(storage)?;
 
-    let mut added = 0;
+    let mut report = Vec::new();
     for snippet in imported.snippets {
-        if !store.snippets.iter().any(|s| s.name == snippet.name) {
-            store.snippets.push(snippet);
-            added += 1;
+        let name = snippet.name.clone();
+        let action = merge(&mut store, snippet, options, confirm);
+        report.push((name, action));
+    }
+
+    print_report(file_path, &report, options.dry_run);
+
+    if !unmapped.is_empty() {
+        println!("⚠️ Could not be mapped:");
+        for item in &unmapped {
+            println!("  {}", item);
         }
     }
 
+    if options.dry_run {
+        println!("🔍 Dry run: nothing was written.");
+        return Ok(());
+    }
+
     if let Err(err) = storage.save_all(&store) {
         eprintln!("⛔ Failed to update storage: {:?}", err);
+        return Err(CommandError::Storage);
+    }
+    Ok(())
+}
+
+/// Merges one imported snippet into `store` and returns what was done.
+fn merge(
+    store: &mut SnippetStore,
+    mut snippet: Snippet,
+    options: &ImportOptions,
+    confirm: &dyn ConfirmPrompt,
+) -> ImportAction {
+    let existing = store.snippets.iter().position(|s| {
+        (!snippet.id.is_empty() && s.id == snippet.id) || s.name.eq_ignore_ascii_case(&snippet.name)
+    });
+
+    let Some(index) = existing else {
+        if snippet.id.is_empty() || store.trash.iter().any(|t| t.snippet.id == snippet.id) {
+            snippet.id = Snippet::new_id();
+        }
+        store.snippets.push(snippet);
+        return ImportAction::Added;
+    };
+
+    let current = &store.snippets[index];
+    if same_snippet(current, &snippet) {
+        return ImportAction::Skipped("unchanged");
+    }
+
+    let overwrite = match options.on_conflict {
+        ConflictStrategy::Skip => return ImportAction::Skipped("already exists"),
+        ConflictStrategy::Overwrite => true,
+        ConflictStrategy::Newer if snippet.updated_at > current.updated_at => true,
+        ConflictStrategy::Newer => return ImportAction::Skipped("not newer"),
+        ConflictStrategy::Rename => {
+            let name = free_name(store, &snippet.name);
+            snippet.id = Snippet::new_id();
+            snippet.name = name.clone();
+            store.snippets.push(snippet);
+            return ImportAction::Renamed(name);
+        }
+        ConflictStrategy::Prompt if options.dry_run => return ImportAction::Skipped("would ask"),
+        ConflictStrategy::Prompt => {
+            println!("~ {}", current.name);
+            for line in describe_modification(current, &snippet) {
+                println!("    {}", line);
+            }
+            let prompt = format!("❗ Overwrite '{}' with the imported version?", current.name);
+            confirm.confirm(&prompt)
+        }
+    };
+
+    if !overwrite {
+        return ImportAction::Skipped("kept local version");
+    }
+
+    // The local snippet keeps its id and name and the replaced version is
+    // kept as a revision.
+    let current = &mut store.snippets[index];
+    let previous = current.clone();
+    current.description = snippet.description;
+    current.content = snippet.content;
+    current.language = snippet.language;
+    current.executable = snippet.executable;
+    current.tags = snippet.tags;
+    current.updated_at = snippet.updated_at.max(Utc::now());
+    current.keep_revision(&previous);
+    ImportAction::Updated
+}
+
+/// `name-2`, `name-3`, ... whichever is not taken yet.
+fn free_name(store: &SnippetStore, name: &str) -> String {
+    (2..)
+        .map(|n| format!("{}-{}", name, n))
+        .find(|candidate| {
+            !store
+                .snippets
+                .iter()
+                .any(|s| s.name.eq_ignore_ascii_case(candidate))
+        })
+        .unwrap_or_else(|| name.to_string())
+}
+
+fn print_report(file_path: &str, report: &[(String, ImportAction)], dry_run: bool) {
+    let verb = |done: &'static str, planned: &'static str| if dry_run { planned } else { done };
+    let width = report.iter().map(|(name, _)| name.len()).max().unwrap_or(0);
+
+    let source = if file_path == STDIO_PATH {
+        "stdin"
     } else {
-        println!("📥 Imported {added} new snippet(s) from {file_path}");
+        file_path
+    };
+    println!("📥 Import from {}:", source);
+    for (name, action) in report {
+        let (sign, description) = match action {
+            ImportAction::Added => ("+", verb("added", "would be added").to_string()),
+            ImportAction::Updated => ("~", verb("updated", "would be updated").to_string()),
+            ImportAction::Renamed(new_name) => (
+                "+",
+                format!("{} as '{}'", verb("added", "would be added"), new_name),
+            ),
+            ImportAction::Skipped(reason) => ("=", format!("skipped ({})", reason)),
+        };
+        println!(
+            "  {} {:<width$}  {}",
+            sign,
+            name,
+            description,
+            width = width
+        );
     }
+
+    let count = |f: fn(&ImportAction) -> bool| report.iter().filter(|(_, a)| f(a)).count();
+    println!(
+        "📦 {} added, {} updated, {} skipped.",
+        count(|a| matches!(a, ImportAction::Added | ImportAction::Renamed(_))),
+        count(|a| matches!(a, ImportAction::Updated)),
+        count(|a| matches!(a, ImportAction::Skipped(_))),
+    );
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{
-        file::FileReader,
-        import::import_command,
-        models::{Snippet, SnippetStore},
-        storage::{Storage, StorageError},
-    };
-    use chrono::Utc;
+    use super::*;
+    use crate::storage::StorageError;
+    use chrono::Duration;
     use std::cell::RefCell;
 
     struct MockStorage {
@@ -88,24 +280,54 @@ mod tests {
     }
 
     impl FileReader for MockFileReader {
-        fn read_yaml(&self, _path: &str) -> Result<SnippetStore, StorageError> {
+        fn read(&self, _path: &str, _: FileFormat) -> Result<SnippetStore, StorageError> {
             if self.should_fail {
                 Err(StorageError::Io(std::io::Error::other("mock error")))
             } else {
                 Ok(self.store.clone())
             }
         }
+
+        fn convert(&self, _: &str, _: ImportSource) -> Result<Conversion, StorageError> {
+            Ok(Conversion {
+                store: self.store.clone(),
+                unmapped: vec!["tool.cfg: output is not kept".to_string()],
+            })
+        }
+    }
+
+    struct MockConfirmPrompt {
+        answer: bool,
+    }
+
+    impl ConfirmPrompt for MockConfirmPrompt {
+        fn confirm(&self, _: &str) -> bool {
+            self.answer
+        }
+    }
+
+    fn import(storage: &MockStorage, reader: &MockFileReader, path: &str) -> CommandResult {
+        import_command(
+            storage,
+            reader,
+            &MockConfirmPrompt { answer: false },
+            path,
+            &ImportOptions::default(),
+        )
     }
 
     fn test_snippet(name: &str) -> Snippet {
         Snippet {
+            id: Snippet::new_id(),
             name: name.to_string(),
             description: "desc".into(),
             content: "echo hi".into(),
+            language: None,
             executable: false,
             tags: vec![],
             created_at: Utc::now(),
             updated_at: Utc::now(),
+            revisions: vec![],
         }
     }
 
@@ -113,10 +335,12 @@ mod tests {
     fn test_import_success() {
         let initial = SnippetStore {
             snippets: vec![test_snippet("a")],
+            trash: vec![],
         };
 
         let imported = SnippetStore {
             snippets: vec![test_snippet("a"), test_snippet("b")],
+            trash: vec![],
         };
 
         let storage = MockStorage {
@@ -131,7 +355,7 @@ mod tests {
             store: imported,
         };
 
-        import_command(&storage, &reader, "test.yml");
+        assert!(import(&storage, &reader, "test.yml").is_ok());
         assert_eq!(storage.store.borrow().snippets.len(), 2);
         assert_eq!(*storage.save_calls.borrow(), 1);
     }
@@ -150,7 +374,10 @@ mod tests {
             store: SnippetStore::default(), // safe fallback
         };
 
-        import_command(&storage, &reader, "nonexistent.yml");
+        assert_eq!(
+            import(&storage, &reader, "nonexistent.yml"),
+            Err(CommandError::Validation)
+        );
         assert_eq!(*storage.save_calls.borrow(), 0);
     }
 
@@ -158,6 +385,7 @@ mod tests {
     fn test_import_storage_load_failure() {
         let imported = SnippetStore {
             snippets: vec![test_snippet("new")],
+            trash: vec![],
         };
 
         let storage = MockStorage {
@@ -172,7 +400,10 @@ mod tests {
             store: imported,
         };
 
-        import_command(&storage, &reader, "test.yml");
+        assert_eq!(
+            import(&storage, &reader, "test.yml"),
+            Err(CommandError::Storage)
+        );
         assert_eq!(*storage.save_calls.borrow(), 0);
     }
 
@@ -182,11 +413,13 @@ mod tests {
 
         let imported = SnippetStore {
             snippets: vec![snippet.clone()],
+            trash: vec![],
         };
 
         let storage = MockStorage {
             store: RefCell::new(SnippetStore {
                 snippets: vec![snippet],
+                trash: vec![],
             }),
             fail_load: false,
             fail_save: false,
@@ -198,15 +431,49 @@ mod tests {
             store: imported,
         };
 
-        import_command(&storage, &reader, "test.yml");
+        assert!(import(&storage, &reader, "test.yml").is_ok());
         assert_eq!(storage.store.borrow().snippets.len(), 1);
         assert_eq!(*storage.save_calls.borrow(), 1);
     }
 
+    #[test]
+    fn test_import_matches_renamed_snippets_by_id() {
+        let mut renamed = test_snippet("old-name");
+        let exported = renamed.clone();
+        renamed.name = "new-name".to_string();
+        let mut without_id = test_snippet("fresh");
+        without_id.id = String::new();
+
+        let storage = MockStorage {
+            store: RefCell::new(SnippetStore {
+                snippets: vec![renamed],
+                trash: vec![],
+            }),
+            fail_load: false,
+            fail_save: false,
+            save_calls: RefCell::new(0),
+        };
+        let reader = MockFileReader {
+            should_fail: false,
+            store: SnippetStore {
+                snippets: vec![exported, without_id],
+                trash: vec![],
+            },
+        };
+
+        assert!(import(&storage, &reader, "test.yml").is_ok());
+
+        let store = storage.store.borrow();
+        let names: Vec<&str> = store.snippets.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["new-name", "fresh"]);
+        assert!(!store.snippets[1].id.is_empty());
+    }
+
     #[test]
     fn test_import_save_all_failure() {
         let imported = SnippetStore {
             snippets: vec![test_snippet("new")],
+            trash: vec![],
         };
 
         let storage = MockStorage {
@@ -221,8 +488,180 @@ mod tests {
             store: imported,
         };
 
-        import_command(&storage, &reader, "test.yml");
+        assert_eq!(
+            import(&storage, &reader, "test.yml"),
+            Err(CommandError::Storage)
+        );
         assert_eq!(storage.store.borrow().snippets.len(), 1);
         assert_eq!(*storage.save_calls.borrow(), 1);
     }
+
+    /// A store holding "deploy" and an import with a changed "Deploy".
+    fn conflict() -> (MockStorage, MockFileReader) {
+        let local = test_snippet("deploy");
+        let mut incoming = test_snippet("Deploy");
+        incoming.content = "echo new".into();
+        incoming.updated_at = local.updated_at + Duration::hours(1);
+
+        let storage = MockStorage {
+            store: RefCell::new(SnippetStore {
+                snippets: vec![local],
+                trash: vec![],
+            }),
+            fail_load: false,
+            fail_save: false,
+            save_calls: RefCell::new(0),
+        };
+        let reader = MockFileReader {
+            should_fail: false,
+            store: SnippetStore {
+                snippets: vec![incoming],
+                trash: vec![],
+            },
+        };
+        (storage, reader)
+    }
+
+    fn import_with(strategy: ConflictStrategy, answer: bool, dry_run: bool) -> MockStorage {
+        let (storage, reader) = conflict();
+        let options = ImportOptions {
+            on_conflict: strategy,
+            dry_run,
+            ..Default::default()
+        };
+        assert!(
+            import_command(
+                &storage,
+                &reader,
+                &MockConfirmPrompt { answer },
+                "test.yml",
+                &options
+            )
+            .is_ok()
+        );
+        storage
+    }
+
+    fn contents(storage: &MockStorage) -> Vec<(String, String)> {
+        storage
+            .store
+            .borrow()
+            .snippets
+            .iter()
+            .map(|s| (s.name.clone(), s.content.clone()))
+            .collect()
+    }
+
+    #[test]
+    fn test_conflicts_match_names_ignoring_case() {
+        let storage = import_with(ConflictStrategy::Skip, true, false);
+
+        assert_eq!(
+            contents(&storage),
+            vec![("deploy".into(), "echo hi".into())]
+        );
+    }
+
+    #[test]
+    fn test_overwrite_keeps_local_identity_and_a_revision() {
+        let storage = import_with(ConflictStrategy::Overwrite, false, false);
+
+        let store = storage.store.borrow();
+        assert_eq!(store.snippets.len(), 1);
+        assert_eq!(store.snippets[0].name, "deploy");
+        assert_eq!(store.snippets[0].content, "echo new");
+        assert_eq!(store.snippets[0].revisions[0].content, "echo hi");
+    }
+
+    #[test]
+    fn test_rename_adds_a_copy() {
+        let storage = import_with(ConflictStrategy::Rename, false, false);
+
+        assert_eq!(
+            contents(&storage),
+            vec![
+                ("deploy".into(), "echo hi".into()),
+                ("Deploy-2".into(), "echo new".into())
+            ]
+        );
+    }
+
+    #[test]
+    fn test_newer_compares_updated_at() {
+        let storage = import_with(ConflictStrategy::Newer, false, false);
+        assert_eq!(contents(&storage)[0].1, "echo new");
+
+        let (storage, mut reader) = conflict();
+        reader.store.snippets[0].updated_at -= Duration::hours(2);
+        let options = ImportOptions {
+            on_conflict: ConflictStrategy::Newer,
+            dry_run: false,
+            ..Default::default()
+        };
+        let confirm = MockConfirmPrompt { answer: true };
+        assert!(import_command(&storage, &reader, &confirm, "test.yml", &options).is_ok());
+        assert_eq!(contents(&storage)[0].1, "echo hi");
+    }
+
+    #[test]
+    fn test_prompt_uses_the_answer() {
+        assert_eq!(
+            contents(&import_with(ConflictStrategy::Prompt, true, false))[0].1,
+            "echo new"
+        );
+        assert_eq!(
+            contents(&import_with(ConflictStrategy::Prompt, false, false))[0].1,
+            "echo hi"
+        );
+    }
+
+    #[test]
+    fn test_dry_run_writes_nothing() {
+        let storage = import_with(ConflictStrategy::Overwrite, false, true);
+
+        assert_eq!(*storage.save_calls.borrow(), 0);
+        assert_eq!(contents(&storage)[0].1, "echo hi");
+    }
+
+    #[test]
+    fn test_import_converted_snippets() {
+        let storage = MockStorage {
+            store: RefCell::new(SnippetStore {
+                snippets: vec![test_snippet("a")],
+                trash: vec![],
+            }),
+            fail_load: false,
+            fail_save: false,
+            save_calls: RefCell::new(0),
+        };
+        let mut converted = test_snippet("b");
+        converted.id = String::new();
+        let reader = MockFileReader {
+            should_fail: false,
+            store: SnippetStore {
+                snippets: vec![converted, test_snippet("A")],
+                trash: vec![],
+            },
+        };
+        let options = ImportOptions {
+            from: Some(ImportSource::Pet),
+            ..Default::default()
+        };
+
+        assert!(
+            import_command(
+                &storage,
+                &reader,
+                &MockConfirmPrompt { answer: false },
+                "snippet.toml",
+                &options
+            )
+            .is_ok()
+        );
+
+        let store = storage.store.borrow();
+        assert_eq!(store.snippets.len(), 2);
+        assert_eq!(store.snippets[1].name, "b");
+        assert!(!store.snippets[1].id.is_empty());
+    }
 }
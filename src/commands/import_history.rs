@@ -0,0 +1,365 @@
+use chrono::Utc;
+
+use crate::{
+    commands::{
+        error::{CommandError, CommandResult},
+        helper::{load_store, lock_storage},
+    },
+    input::HistoryInput,
+    models::{Snippet, SnippetStore},
+    shell_history::{ShellHistory, unique_newest_first},
+    storage::{Storage, filter::fuzzy_filter},
+    ui::SelectionUI,
+};
+
+/// Lets the user fuzzy-pick one command from their shell history, for
+/// `save --from-history`.
+pub fn pick_from_history(
+    history: &dyn ShellHistory,
+    selection_ui: &dyn SelectionUI,
+) -> Result<String, CommandError> {
+    let commands = load_commands(history, selection_ui)?;
+
+    match selection_ui.with_fuzzy_select("📜 Pick a command from your shell history:", &commands)
+    {
+        Some(index) => Ok(commands[index].clone()),
+        None => {
+            println!("🚫 No command selected.");
+            Err(CommandError::Cancelled)
+        }
+    }
+}
+
+/// Saves commands picked from the shell history as executable snippets,
+/// asking for a name, description and tags for each. `query` narrows the
+/// list down first and `limit` caps how many of the latest commands are
+/// offered.
+pub fn import_history_command(
+    storage: &dyn Storage,
+    history: &dyn ShellHistory,
+    selection_ui: &dyn SelectionUI,
+    input: &dyn HistoryInput,
+    query: Option<String>,
+    limit: usize,
+) -> CommandResult {
+    let mut commands = load_commands(history, selection_ui)?;
+
+    if let Some(query) = query {
+        commands = fuzzy_filter(&commands, &query)
+            .into_iter()
+            .map(|i| commands[i].clone())
+            .collect();
+        if commands.is_empty() {
+            println!("📭 No command in your shell history matches '{}'.", query);
+            return Ok(());
+        }
+    }
+    commands.truncate(limit);
+
+    let chosen = selection_ui.with_multi_select("📜 Select the commands to save:", &commands);
+    if chosen.is_empty() {
+        println!("🚫 No commands selected.");
+        return Err(CommandError::Cancelled);
+    }
+
+    let store = load_store(storage)?;
+    let mut snippets: Vec<Snippet> = vec![];
+    for command in chosen.into_iter().filter_map(|i| commands.get(i)) {
+        println!("📋 {}", command);
+        let Some(name) = ask_name(&store, &snippets, input) else {
+            println!("⏭️ Skipped.");
+            continue;
+        };
+
+        let now = Utc::now();
+        snippets.push(Snippet {
+            id: Snippet::new_id(),
+            name,
+            description: input.get_description(),
+            content: command.clone(),
+            language: None,
+            executable: true,
+            tags: input.get_tags(),
+            created_at: now,
+            updated_at: now,
+            revisions: vec![],
+        });
+    }
+
+    if snippets.is_empty() {
+        println!("📭 Nothing saved.");
+        return Ok(());
+    }
+
+    let _lock = lock_storage(storage)?;
+
+    let mut store = load_store(storage)?;
+    let mut saved = 0;
+    for snippet in snippets {
+        if name_taken(&store, &[], &snippet.name) {
+            eprintln!(
+                "⚠️ A snippet named '{}' was saved in the meantime, skipping it.",
+                snippet.name
+            );
+            continue;
+        }
+        store.snippets.push(snippet);
+        saved += 1;
+    }
+
+    if let Err(e) = storage.save_all(&store) {
+        eprintln!("⛔ Failed to save snippets: {}", e);
+        return Err(CommandError::Storage);
+    }
+
+    println!("✅ Saved {} snippet(s) from your shell history.", saved);
+    Ok(())
+}
+
+fn load_commands(
+    history: &dyn ShellHistory,
+    selection_ui: &dyn SelectionUI,
+) -> Result<Vec<String>, CommandError> {
+    if !selection_ui.is_interactive() {
+        eprintln!("⛔ Picking from the shell history needs an interactive terminal.");
+        return Err(CommandError::Validation);
+    }
+
+    let commands = match history.commands() {
+        Ok(commands) => unique_newest_first(commands),
+        Err(e) => {
+            eprintln!("⛔ {}", e);
+            return Err(CommandError::NotFound);
+        }
+    };
+
+    if commands.is_empty() {
+        println!("📭 Your shell history is empty.");
+        return Err(CommandError::NotFound);
+    }
+    Ok(commands)
+}
+
+/// Asks until the name is free or empty, which skips the command.
+fn ask_name(store: &SnippetStore, pending: &[Snippet], input: &dyn HistoryInput) -> Option<String> {
+    loop {
+        let name = input.get_name();
+        if name.is_empty() {
+            return None;
+        }
+        if !name_taken(store, pending, &name) {
+            return Some(name);
+        }
+        eprintln!("⛔ A snippet with the name '{}' already exists.", name);
+    }
+}
+
+fn name_taken(store: &SnippetStore, pending: &[Snippet], name: &str) -> bool {
+    store
+        .snippets
+        .iter()
+        .chain(pending)
+        .any(|s| s.name.eq_ignore_ascii_case(name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::StorageError;
+    use std::cell::RefCell;
+
+    struct MockStorage {
+        store: RefCell<SnippetStore>,
+    }
+
+    impl Storage for MockStorage {
+        fn load(&self) -> Result<SnippetStore, StorageError> {
+            Ok(self.store.borrow().clone())
+        }
+
+        fn save(&self, _: Snippet) -> Result<(), StorageError> {
+            Ok(())
+        }
+
+        fn save_all(&self, store: &SnippetStore) -> Result<(), StorageError> {
+            self.store.replace(store.clone());
+            Ok(())
+        }
+
+        fn get_backups(&self) -> Result<Vec<std::path::PathBuf>, StorageError> {
+            Ok(vec![])
+        }
+
+        fn restore_backup(&self, _: &std::path::Path) -> Result<(), StorageError> {
+            Ok(())
+        }
+    }
+
+    struct MockHistory(Vec<&'static str>);
+
+    impl ShellHistory for MockHistory {
+        fn commands(&self) -> Result<Vec<String>, String> {
+            Ok(self.0.iter().map(|c| c.to_string()).collect())
+        }
+    }
+
+    /// Picks the given positions and records the items it was offered.
+    struct MockSelection {
+        picks: Vec<usize>,
+        offered: RefCell<Vec<String>>,
+    }
+
+    impl MockSelection {
+        fn new(picks: Vec<usize>) -> Self {
+            Self {
+                picks,
+                offered: RefCell::new(vec![]),
+            }
+        }
+    }
+
+    impl SelectionUI for MockSelection {
+        fn with_snippet_list(&self, _: Vec<Snippet>) -> Option<Snippet> {
+            None
+        }
+
+        fn with_backup_list(&self, _: &[String]) -> Option<usize> {
+            None
+        }
+
+        fn with_multi_select(&self, _: &str, items: &[String]) -> Vec<usize> {
+            self.offered.replace(items.to_vec());
+            self.picks.clone()
+        }
+
+        fn with_fuzzy_select(&self, _: &str, items: &[String]) -> Option<usize> {
+            self.offered.replace(items.to_vec());
+            self.picks.first().copied()
+        }
+    }
+
+    /// Answers the name prompts in order.
+    struct MockInput {
+        names: RefCell<Vec<&'static str>>,
+    }
+
+    impl MockInput {
+        fn new(names: Vec<&'static str>) -> Self {
+            Self {
+                names: RefCell::new(names),
+            }
+        }
+    }
+
+    impl HistoryInput for MockInput {
+        fn get_name(&self) -> String {
+            self.names.borrow_mut().remove(0).to_string()
+        }
+
+        fn get_description(&self) -> String {
+            "From history".to_string()
+        }
+
+        fn get_tags(&self) -> Vec<String> {
+            vec!["history".to_string()]
+        }
+    }
+
+    fn storage() -> MockStorage {
+        let now = Utc::now();
+        MockStorage {
+            store: RefCell::new(SnippetStore {
+                snippets: vec![Snippet {
+                    id: Snippet::new_id(),
+                    name: "deploy".to_string(),
+                    description: String::new(),
+                    content: "deploy.sh".to_string(),
+                    language: None,
+                    executable: true,
+                    tags: vec![],
+                    created_at: now,
+                    updated_at: now,
+                    revisions: vec![],
+                }],
+                trash: vec![],
+            }),
+        }
+    }
+
+    fn history() -> MockHistory {
+        MockHistory(vec!["ls -la", "git status", "ls -la", "docker ps -a"])
+    }
+
+    #[test]
+    fn test_pick_from_history_offers_unique_commands_newest_first() {
+        let selection = MockSelection::new(vec![1]);
+
+        assert_eq!(
+            pick_from_history(&history(), &selection),
+            Ok("ls -la".to_string())
+        );
+        assert_eq!(
+            *selection.offered.borrow(),
+            vec!["docker ps -a", "ls -la", "git status"]
+        );
+    }
+
+    #[test]
+    fn test_pick_from_history_cancelled() {
+        let selection = MockSelection::new(vec![]);
+
+        assert_eq!(
+            pick_from_history(&history(), &selection),
+            Err(CommandError::Cancelled)
+        );
+    }
+
+    #[test]
+    fn test_import_history_saves_executable_snippets() {
+        let storage = storage();
+        let selection = MockSelection::new(vec![0, 1, 2]);
+        // "deploy" is taken, so the first command is asked again; the last
+        // one is skipped.
+        let input = MockInput::new(vec!["deploy", "containers", "list", ""]);
+
+        assert!(
+            import_history_command(&storage, &history(), &selection, &input, None, 100).is_ok()
+        );
+
+        let store = storage.store.borrow();
+        let saved: Vec<(&str, &str)> = store.snippets[1..]
+            .iter()
+            .map(|s| (s.name.as_str(), s.content.as_str()))
+            .collect();
+        assert_eq!(
+            saved,
+            vec![("containers", "docker ps -a"), ("list", "ls -la")]
+        );
+        assert!(store.snippets[1].executable);
+        assert_eq!(store.snippets[1].tags, vec!["history"]);
+    }
+
+    #[test]
+    fn test_import_history_query_and_limit() {
+        let storage = storage();
+        let selection = MockSelection::new(vec![]);
+        let input = MockInput::new(vec![]);
+
+        assert_eq!(
+            import_history_command(
+                &storage,
+                &history(),
+                &selection,
+                &input,
+                Some("ls".to_string()),
+                100
+            ),
+            Err(CommandError::Cancelled)
+        );
+        assert_eq!(*selection.offered.borrow(), vec!["ls -la"]);
+
+        let _ = import_history_command(&storage, &history(), &selection, &input, None, 2);
+        assert_eq!(*selection.offered.borrow(), vec!["docker ps -a", "ls -la"]);
+        assert_eq!(storage.store.borrow().snippets.len(), 1);
+    }
+}
@@ -1,26 +1,54 @@
+use std::cmp::Reverse;
+
 use crate::{
+    commands::{
+        error::{CommandError, CommandResult},
+        helper::{load_store, load_usage},
+    },
     models::Snippet,
     storage::{
         Storage,
         filter::{Filter, apply_filter},
     },
-    ui::TableUI,
+    ui::{ListSort, OutputFormatter, TableUI},
 };
 
-pub fn list_command(storage: &dyn Storage, table_ui: &mut dyn TableUI, tag: Option<String>) {
-    let store = match storage.load() {
-        Ok(s) => s,
-        Err(_) => {
-            println!("📭 No snippets saved yet.");
-            return;
-        }
-    };
+pub fn list_command(
+    storage: &dyn Storage,
+    table_ui: &mut dyn TableUI,
+    formatter: Option<&dyn OutputFormatter>,
+    tag: Option<String>,
+    sort: Option<ListSort>,
+) -> CommandResult {
+    let store = load_store(storage)?;
+    let usage = load_usage(storage);
 
-    let snippets: Vec<Snippet> = match tag.as_deref() {
+    let mut snippets: Vec<Snippet> = match tag.as_deref() {
         Some(tag) => apply_filter(&store, Filter::Tag(tag.to_string())),
         None => apply_filter(&store, Filter::All),
     };
 
+    match sort {
+        Some(ListSort::Name) => snippets.sort_by_key(|s| s.name.to_lowercase()),
+        Some(ListSort::Created) => snippets.sort_by_key(|s| s.created_at),
+        Some(ListSort::Updated) => snippets.sort_by_key(|s| Reverse(s.updated_at)),
+        Some(ListSort::Frecency) => usage.sort_by_frecency(&mut snippets, |s| &s.id),
+        None => {}
+    }
+
+    if let Some(formatter) = formatter {
+        return match formatter.format_snippets(&snippets) {
+            Ok(output) => {
+                println!("{output}");
+                Ok(())
+            }
+            Err(e) => {
+                eprintln!("⛔ Failed to format snippets: {}", e);
+                Err(CommandError::Failed)
+            }
+        };
+    }
+
     if snippets.is_empty() {
         if let Some(tag) = tag {
             println!("📭 No snippets found for tag: {}.", tag);
@@ -28,9 +56,10 @@ pub fn list_command(storage: &dyn Storage, table_ui: &mut dyn TableUI, tag: Opti
             println!("📭 No snippets saved yet.");
         }
     } else {
-        let table = table_ui.with_snippet_list(snippets);
+        let table = table_ui.with_snippet_list(snippets, &usage);
         println!("{table}");
     }
+    Ok(())
 }
 
 #[cfg(test)]
@@ -41,12 +70,19 @@ mod tests {
     use crate::{
         commands::list::list_command,
         models::{Snippet, SnippetStore},
-        storage::{Storage, StorageError},
-        ui::TableUI,
+        storage::{
+            Storage, StorageError,
+            filter::FuzzyMatch,
+            history::HistoryEntry,
+            retention::BackupInfo,
+            usage::{UsageKind, UsageStats},
+        },
+        ui::{ListSort, OutputFormatter, TableUI},
     };
 
     struct MockStorage {
         store: SnippetStore,
+        usage: UsageStats,
     }
 
     impl Storage for MockStorage {
@@ -62,6 +98,10 @@ mod tests {
             Ok(())
         }
 
+        fn load_usage(&self) -> Result<UsageStats, StorageError> {
+            Ok(self.usage.clone())
+        }
+
         fn get_backups(&self) -> Result<Vec<std::path::PathBuf>, StorageError> {
             Ok(vec![])
         }
@@ -76,39 +116,76 @@ mod tests {
     }
 
     impl TableUI for MockTableUI {
-        fn with_snippet_list(&mut self, _: Vec<Snippet>) -> comfy_table::Table {
+        fn with_snippet_list(&mut self, _: Vec<Snippet>, _: &UsageStats) -> comfy_table::Table {
             *self.printed_table.borrow_mut() = true;
             comfy_table::Table::new()
         }
+
+        fn with_backup_list(&mut self, _: Vec<BackupInfo>) -> comfy_table::Table {
+            comfy_table::Table::new()
+        }
+
+        fn with_search_results(&mut self, _: Vec<FuzzyMatch>) -> comfy_table::Table {
+            comfy_table::Table::new()
+        }
+
+        fn with_history(&mut self, _: Vec<(usize, HistoryEntry)>) -> comfy_table::Table {
+            comfy_table::Table::new()
+        }
+    }
+
+    struct MockFormatter {
+        formatted: RefCell<Vec<String>>,
+    }
+
+    impl OutputFormatter for MockFormatter {
+        fn format_snippets(&self, snippets: &[Snippet]) -> Result<String, String> {
+            self.formatted
+                .replace(snippets.iter().map(|s| s.name.clone()).collect());
+            Ok(String::new())
+        }
+
+        fn format_snippet(&self, snippet: &Snippet) -> Result<String, String> {
+            self.format_snippets(std::slice::from_ref(snippet))
+        }
     }
 
     #[test]
     fn test_list_command_no_snippets_in_store() {
         let storage = MockStorage {
-            store: SnippetStore { snippets: vec![] },
+            usage: UsageStats::default(),
+            store: SnippetStore {
+                snippets: vec![],
+                trash: vec![],
+            },
         };
 
         let mut table_ui = MockTableUI {
             printed_table: Rc::new(RefCell::new(false)),
         };
 
-        list_command(&storage, &mut table_ui, None);
+        assert!(list_command(&storage, &mut table_ui, None, None, None).is_ok());
         assert!(!*table_ui.printed_table.borrow());
     }
 
     #[test]
     fn test_list_command_snippets_with_tag_no_match() {
         let storage = MockStorage {
+            usage: UsageStats::default(),
             store: SnippetStore {
                 snippets: vec![Snippet {
+                    id: Snippet::new_id(),
                     name: "test".to_string(),
                     description: "test desc".to_string(),
                     content: "ls".to_string(),
+                    language: None,
                     executable: true,
                     tags: vec!["dev".to_string()],
                     created_at: chrono::Utc::now(),
                     updated_at: chrono::Utc::now(),
+                    revisions: vec![],
                 }],
+                trash: vec![],
             },
         };
 
@@ -116,23 +193,37 @@ mod tests {
             printed_table: Rc::new(RefCell::new(false)),
         };
 
-        list_command(&storage, &mut table_ui, Some("nonexistent".to_string()));
+        assert!(
+            list_command(
+                &storage,
+                &mut table_ui,
+                None,
+                Some("nonexistent".to_string()),
+                None,
+            )
+            .is_ok()
+        );
         assert!(!*table_ui.printed_table.borrow());
     }
 
     #[test]
     fn test_list_command_snippets_with_results() {
         let storage = MockStorage {
+            usage: UsageStats::default(),
             store: SnippetStore {
                 snippets: vec![Snippet {
+                    id: Snippet::new_id(),
                     name: "test".to_string(),
                     description: "test desc".to_string(),
                     content: "ls".to_string(),
+                    language: None,
                     executable: true,
                     tags: vec!["dev".to_string()],
                     created_at: chrono::Utc::now(),
                     updated_at: chrono::Utc::now(),
+                    revisions: vec![],
                 }],
+                trash: vec![],
             },
         };
 
@@ -140,7 +231,88 @@ mod tests {
             printed_table: Rc::new(RefCell::new(false)),
         };
 
-        list_command(&storage, &mut table_ui, None);
+        assert!(list_command(&storage, &mut table_ui, None, None, None).is_ok());
         assert!(*table_ui.printed_table.borrow());
     }
+
+    #[test]
+    fn test_list_command_uses_formatter() {
+        let storage = MockStorage {
+            usage: UsageStats::default(),
+            store: SnippetStore {
+                snippets: vec![Snippet {
+                    id: Snippet::new_id(),
+                    name: "test".to_string(),
+                    description: "test desc".to_string(),
+                    content: "ls".to_string(),
+                    language: None,
+                    executable: true,
+                    tags: vec!["dev".to_string()],
+                    created_at: chrono::Utc::now(),
+                    updated_at: chrono::Utc::now(),
+                    revisions: vec![],
+                }],
+                trash: vec![],
+            },
+        };
+
+        let mut table_ui = MockTableUI {
+            printed_table: Rc::new(RefCell::new(false)),
+        };
+
+        let formatter = MockFormatter {
+            formatted: RefCell::new(vec![]),
+        };
+
+        assert!(list_command(&storage, &mut table_ui, Some(&formatter), None, None).is_ok());
+        assert!(!*table_ui.printed_table.borrow());
+        assert_eq!(*formatter.formatted.borrow(), vec!["test".to_string()]);
+    }
+
+    fn snippet(name: &str, days_old: i64) -> Snippet {
+        let created = chrono::Utc::now() - chrono::Duration::days(days_old);
+        Snippet {
+            id: name.to_string(),
+            name: name.to_string(),
+            description: String::new(),
+            content: "ls".to_string(),
+            language: None,
+            executable: true,
+            tags: vec![],
+            created_at: created,
+            updated_at: created,
+            revisions: vec![],
+        }
+    }
+
+    #[test]
+    fn test_list_command_sorts() {
+        let mut usage = UsageStats::default();
+        usage.record("b", UsageKind::Run, chrono::Utc::now());
+        let storage = MockStorage {
+            usage,
+            store: SnippetStore {
+                snippets: vec![snippet("c", 1), snippet("a", 3), snippet("b", 2)],
+                trash: vec![],
+            },
+        };
+        let mut table_ui = MockTableUI {
+            printed_table: Rc::new(RefCell::new(false)),
+        };
+        let formatter = MockFormatter {
+            formatted: RefCell::new(vec![]),
+        };
+
+        for (sort, expected) in [
+            (ListSort::Name, ["a", "b", "c"]),
+            (ListSort::Created, ["a", "b", "c"]),
+            (ListSort::Updated, ["c", "b", "a"]),
+            (ListSort::Frecency, ["b", "c", "a"]),
+        ] {
+            assert!(
+                list_command(&storage, &mut table_ui, Some(&formatter), None, Some(sort)).is_ok()
+            );
+            assert_eq!(*formatter.formatted.borrow(), expected);
+        }
+    }
 }
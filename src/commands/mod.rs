@@ -1,11 +1,23 @@
+pub mod backups;
+pub mod config;
 pub mod copy;
 pub mod delete;
+pub mod doctor;
 pub mod edit;
+pub mod error;
 pub mod export;
 pub mod helper;
+pub mod history;
 pub mod import;
+pub mod import_history;
 pub mod list;
 pub mod restore;
+pub mod revisions;
 pub mod run;
 pub mod save;
+pub mod search;
 pub mod show;
+pub mod stats;
+pub mod stores;
+pub mod trash;
+pub mod tui;
@@ -1,18 +1,30 @@
-use crate::storage::Storage;
-use crate::ui::SelectionUI;
-
-pub fn restore_command(storage: &dyn Storage, selection_ui: &dyn SelectionUI) {
+use std::path::Path;
+
+use crate::commands::error::{CommandError, CommandResult};
+use crate::commands::helper::lock_storage;
+use crate::diff::{SnippetChange, apply_changes, describe_modification, diff_stores};
+use crate::file::FileReader;
+use crate::storage::{Storage, StorageError};
+use crate::ui::{ConfirmPrompt, SelectionUI};
+
+pub fn restore_command(
+    storage: &dyn Storage,
+    selection_ui: &dyn SelectionUI,
+    confirm: &dyn ConfirmPrompt,
+    reader: &dyn FileReader,
+    select: bool,
+) -> CommandResult {
     let backups = match storage.get_backups() {
         Ok(s) => s,
         Err(_) => {
             println!("📭 No backups created yet.");
-            return;
+            return Err(CommandError::NotFound);
         }
     };
 
     if backups.is_empty() {
         println!("📭 No backups found.");
-        return;
+        return Err(CommandError::NotFound);
     }
 
     let display_names: Vec<String> = backups
@@ -22,22 +34,140 @@ pub fn restore_command(storage: &dyn Storage, selection_ui: &dyn SelectionUI) {
 
     let selected_index = match selection_ui.with_backup_list(&display_names) {
         Some(i) => i,
-        None => return,
+        None => return Err(CommandError::Cancelled),
     };
 
     let full_path = backups.get(selected_index).unwrap().clone();
+    let backup_name = &display_names[selected_index];
+
+    let backup = match reader.read_yaml(&full_path.to_string_lossy()) {
+        Ok(store) => store,
+        Err(e) => {
+            eprintln!("⛔ Failed to read backup '{}': {}", backup_name, e);
+            return Err(CommandError::Storage);
+        }
+    };
 
-    match storage.restore_backup(&full_path) {
-        Ok(_) => println!("✅ Backup restored successfully."),
-        Err(e) => eprintln!("⛔ Failed to restore backup: {}", e),
+    let _lock = lock_storage(storage)?;
+
+    let mut current = match storage.load() {
+        Ok(store) => store,
+        Err(e @ StorageError::Corrupt { .. }) if select => {
+            eprintln!("⛔ {}", e);
+            eprintln!(
+                "💡 Single snippets cannot be restored into an unreadable store; run `markit restore` without --select to replace it."
+            );
+            return Err(CommandError::Storage);
+        }
+        Err(e @ StorageError::Corrupt { .. }) => {
+            eprintln!("⚠️ {}", e);
+            eprintln!("⚠️ Your current snippets cannot be read, so no changes can be shown.");
+            return restore_whole_backup(storage, confirm, &full_path, backup_name);
+        }
+        Err(e) => {
+            eprintln!("⛔ Failed to load snippets: {}", e);
+            return Err(CommandError::Storage);
+        }
     };
+
+    let changes = diff_stores(&current, &backup);
+    if changes.is_empty() {
+        println!("✅ Backup '{}' matches your current snippets.", backup_name);
+        return Ok(());
+    }
+
+    println!("📦 Restoring '{}' would make these changes:", backup_name);
+    print_changes(&changes);
+
+    if !select {
+        return restore_whole_backup(storage, confirm, &full_path, backup_name);
+    }
+
+    let labels: Vec<String> = changes.iter().map(|c| c.label()).collect();
+    let chosen: Vec<SnippetChange> = selection_ui
+        .with_multi_select("📋 Select the snippets to restore:", &labels)
+        .into_iter()
+        .filter_map(|i| changes.get(i).cloned())
+        .collect();
+
+    if chosen.is_empty() {
+        println!("🚫 No snippets selected.");
+        return Err(CommandError::Cancelled);
+    }
+
+    let prompt = format!(
+        "❗ Restore {} snippet(s) from '{}'? Your current snippets will be backed up first.",
+        chosen.len(),
+        backup_name
+    );
+    if !confirm.confirm(&prompt) {
+        println!("🚫 Restore cancelled.");
+        return Err(CommandError::Cancelled);
+    }
+
+    apply_changes(&mut current, &chosen);
+
+    match storage.save_all(&current) {
+        Ok(_) => {
+            for change in &chosen {
+                println!("✅ Restored '{}'.", change.name());
+            }
+            Ok(())
+        }
+        Err(e) => {
+            eprintln!("⛔ Failed to restore snippets: {}", e);
+            Err(CommandError::Storage)
+        }
+    }
+}
+
+fn restore_whole_backup(
+    storage: &dyn Storage,
+    confirm: &dyn ConfirmPrompt,
+    path: &Path,
+    backup_name: &str,
+) -> CommandResult {
+    let prompt = format!(
+        "❗ Restore '{}'? Your current snippets will be backed up first.",
+        backup_name
+    );
+    if !confirm.confirm(&prompt) {
+        println!("🚫 Restore cancelled.");
+        return Err(CommandError::Cancelled);
+    }
+
+    match storage.restore_backup(path) {
+        Ok(_) => {
+            println!("✅ Backup restored successfully.");
+            Ok(())
+        }
+        Err(e) => {
+            eprintln!("⛔ Failed to restore backup: {}", e);
+            Err(CommandError::Storage)
+        }
+    }
+}
+
+fn print_changes(changes: &[SnippetChange]) {
+    for change in changes {
+        println!("{}", change.label());
+        if let SnippetChange::Modified { current, target } = change {
+            for line in describe_modification(current, target) {
+                println!("    {}", line);
+            }
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::file::{
+        FileFormat,
+        convert::{Conversion, ImportSource},
+    };
     use crate::{
-        models::Snippet,
+        models::{Snippet, SnippetStore},
         storage::{Storage, StorageError},
         ui::SelectionUI,
     };
@@ -47,22 +177,48 @@ mod tests {
     };
 
     struct MockStorage {
+        current: SnippetStore,
         backups: Vec<PathBuf>,
         restore_called_with: RefCell<Option<PathBuf>>,
+        saved: RefCell<Option<SnippetStore>>,
         fail_get: bool,
         fail_restore: bool,
+        corrupt: bool,
+    }
+
+    impl MockStorage {
+        fn new(backups: Vec<PathBuf>) -> Self {
+            Self {
+                current: SnippetStore::default(),
+                backups,
+                restore_called_with: RefCell::new(None),
+                saved: RefCell::new(None),
+                fail_get: false,
+                fail_restore: false,
+                corrupt: false,
+            }
+        }
     }
 
     impl Storage for MockStorage {
-        fn load(&self) -> Result<crate::models::SnippetStore, StorageError> {
-            Ok(crate::models::SnippetStore::default())
+        fn load(&self) -> Result<SnippetStore, StorageError> {
+            if self.corrupt {
+                return Err(StorageError::Corrupt {
+                    path: PathBuf::from("bookmarks.yml"),
+                    line: Some(2),
+                    column: Some(3),
+                    message: "did not find expected key".to_string(),
+                });
+            }
+            Ok(self.current.clone())
         }
 
         fn save(&self, _: Snippet) -> Result<(), StorageError> {
             Ok(())
         }
 
-        fn save_all(&self, _: &crate::models::SnippetStore) -> Result<(), StorageError> {
+        fn save_all(&self, store: &SnippetStore) -> Result<(), StorageError> {
+            self.saved.replace(Some(store.clone()));
             Ok(())
         }
 
@@ -88,6 +244,7 @@ mod tests {
 
     struct MockSelectionUI {
         selected_index: Option<usize>,
+        multi_selection: Vec<usize>,
     }
 
     impl SelectionUI for MockSelectionUI {
@@ -98,98 +255,310 @@ mod tests {
         fn with_backup_list(&self, _backups: &[String]) -> Option<usize> {
             self.selected_index
         }
+
+        fn with_multi_select(&self, _: &str, _: &[String]) -> Vec<usize> {
+            self.multi_selection.clone()
+        }
+    }
+
+    struct MockConfirmPrompt {
+        result: bool,
+    }
+
+    impl ConfirmPrompt for MockConfirmPrompt {
+        fn confirm(&self, _: &str) -> bool {
+            self.result
+        }
+    }
+
+    struct MockFileReader {
+        store: SnippetStore,
+    }
+
+    impl FileReader for MockFileReader {
+        fn read(&self, _: &str, _: FileFormat) -> Result<SnippetStore, StorageError> {
+            Ok(self.store.clone())
+        }
+
+        fn convert(&self, _: &str, _: ImportSource) -> Result<Conversion, StorageError> {
+            Ok(Conversion::default())
+        }
+    }
+
+    fn snippet(name: &str, content: &str) -> Snippet {
+        Snippet {
+            id: format!("id-{}", name),
+            name: name.to_string(),
+            description: "desc".to_string(),
+            content: content.to_string(),
+            language: None,
+            executable: true,
+            tags: vec![],
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+            revisions: vec![],
+        }
+    }
+
+    fn backup_reader() -> MockFileReader {
+        MockFileReader {
+            store: SnippetStore {
+                snippets: vec![snippet("a", "echo old"), snippet("b", "echo b")],
+                trash: vec![],
+            },
+        }
+    }
+
+    fn ui(selected_index: Option<usize>) -> MockSelectionUI {
+        MockSelectionUI {
+            selected_index,
+            multi_selection: vec![],
+        }
     }
 
     #[test]
     fn test_restore_success() {
         let path = PathBuf::from("backup1.yml");
+        let storage = MockStorage::new(vec![path.clone()]);
+
+        assert!(
+            restore_command(
+                &storage,
+                &ui(Some(0)),
+                &MockConfirmPrompt { result: true },
+                &backup_reader(),
+                false,
+            )
+            .is_ok()
+        );
 
-        let storage = MockStorage {
-            backups: vec![path.clone()],
-            restore_called_with: RefCell::new(None),
-            fail_get: false,
-            fail_restore: false,
-        };
+        assert_eq!(*storage.restore_called_with.borrow(), Some(path));
+    }
 
-        let ui = MockSelectionUI {
-            selected_index: Some(0),
-        };
+    #[test]
+    fn test_restore_declined() {
+        let storage = MockStorage::new(vec![PathBuf::from("backup1.yml")]);
+
+        assert_eq!(
+            restore_command(
+                &storage,
+                &ui(Some(0)),
+                &MockConfirmPrompt { result: false },
+                &backup_reader(),
+                false,
+            ),
+            Err(CommandError::Cancelled)
+        );
 
-        restore_command(&storage, &ui);
+        assert!(storage.restore_called_with.borrow().is_none());
+    }
 
-        assert_eq!(*storage.restore_called_with.borrow(), Some(path));
+    #[test]
+    fn test_restore_without_changes() {
+        let mut storage = MockStorage::new(vec![PathBuf::from("backup1.yml")]);
+        storage.current = backup_reader().store;
+
+        assert!(
+            restore_command(
+                &storage,
+                &ui(Some(0)),
+                &MockConfirmPrompt { result: true },
+                &backup_reader(),
+                false,
+            )
+            .is_ok()
+        );
+
+        assert!(storage.restore_called_with.borrow().is_none());
     }
 
     #[test]
-    fn test_no_backups_found() {
-        let storage = MockStorage {
-            backups: vec![],
-            restore_called_with: RefCell::new(None),
-            fail_get: false,
-            fail_restore: false,
+    fn test_restore_selected_snippets() {
+        let mut storage = MockStorage::new(vec![PathBuf::from("backup1.yml")]);
+        storage.current = SnippetStore {
+            snippets: vec![snippet("a", "echo new"), snippet("c", "echo c")],
+            trash: vec![],
         };
 
-        let ui = MockSelectionUI {
+        // Changes are: change a, remove c, add b. Restore only a and b.
+        let selection_ui = MockSelectionUI {
             selected_index: Some(0),
+            multi_selection: vec![0, 2],
         };
 
-        restore_command(&storage, &ui);
+        assert!(
+            restore_command(
+                &storage,
+                &selection_ui,
+                &MockConfirmPrompt { result: true },
+                &backup_reader(),
+                true,
+            )
+            .is_ok()
+        );
 
         assert!(storage.restore_called_with.borrow().is_none());
+        let saved = storage.saved.borrow().clone().unwrap();
+        let summary: Vec<(String, String)> = saved
+            .snippets
+            .iter()
+            .map(|s| (s.name.clone(), s.content.clone()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("a".to_string(), "echo old".to_string()),
+                ("c".to_string(), "echo c".to_string()),
+                ("b".to_string(), "echo b".to_string()),
+            ]
+        );
     }
 
     #[test]
-    fn test_user_cancels_selection() {
-        let storage = MockStorage {
-            backups: vec![PathBuf::from("backup.yml")],
-            restore_called_with: RefCell::new(None),
-            fail_get: false,
-            fail_restore: false,
+    fn test_restore_selected_snippets_from_backup_without_ids() {
+        let mut storage = MockStorage::new(vec![PathBuf::from("backup1.yml")]);
+        storage.current = SnippetStore {
+            snippets: vec![snippet("a", "echo new")],
+            trash: vec![],
         };
-
-        let ui = MockSelectionUI {
-            selected_index: None,
+        let mut reader = backup_reader();
+        for snippet in &mut reader.store.snippets {
+            snippet.id.clear();
+        }
+        let selection_ui = MockSelectionUI {
+            selected_index: Some(0),
+            multi_selection: vec![0, 1],
         };
 
-        restore_command(&storage, &ui);
+        assert!(
+            restore_command(
+                &storage,
+                &selection_ui,
+                &MockConfirmPrompt { result: true },
+                &reader,
+                true,
+            )
+            .is_ok()
+        );
+
+        let saved = storage.saved.borrow().clone().unwrap();
+        assert_eq!(saved.snippets[0].id, "id-a");
+        assert_eq!(saved.snippets[0].content, "echo old");
+        assert_eq!(saved.snippets[1].name, "b");
+        assert!(!saved.snippets[1].id.is_empty());
+    }
 
-        assert!(storage.restore_called_with.borrow().is_none());
+    #[test]
+    fn test_restore_over_corrupt_store() {
+        let path = PathBuf::from("backup1.yml");
+        let mut storage = MockStorage::new(vec![path.clone()]);
+        storage.corrupt = true;
+
+        assert!(
+            restore_command(
+                &storage,
+                &ui(Some(0)),
+                &MockConfirmPrompt { result: true },
+                &backup_reader(),
+                false,
+            )
+            .is_ok()
+        );
+
+        assert_eq!(*storage.restore_called_with.borrow(), Some(path));
     }
 
     #[test]
-    fn test_get_backups_fails() {
-        let storage = MockStorage {
-            backups: vec![],
-            restore_called_with: RefCell::new(None),
-            fail_get: true,
-            fail_restore: false,
-        };
+    fn test_select_is_rejected_over_corrupt_store() {
+        let mut storage = MockStorage::new(vec![PathBuf::from("backup1.yml")]);
+        storage.corrupt = true;
+
+        assert_eq!(
+            restore_command(
+                &storage,
+                &ui(Some(0)),
+                &MockConfirmPrompt { result: true },
+                &backup_reader(),
+                true,
+            ),
+            Err(CommandError::Storage)
+        );
 
-        let ui = MockSelectionUI {
-            selected_index: Some(0),
-        };
+        assert!(storage.restore_called_with.borrow().is_none());
+        assert!(storage.saved.borrow().is_none());
+    }
 
-        restore_command(&storage, &ui);
+    #[test]
+    fn test_no_backups_found() {
+        let storage = MockStorage::new(vec![]);
+
+        assert_eq!(
+            restore_command(
+                &storage,
+                &ui(Some(0)),
+                &MockConfirmPrompt { result: true },
+                &backup_reader(),
+                false,
+            ),
+            Err(CommandError::NotFound)
+        );
 
         assert!(storage.restore_called_with.borrow().is_none());
     }
 
     #[test]
-    fn test_restore_fails() {
-        let path = PathBuf::from("broken_backup.yml");
+    fn test_user_cancels_selection() {
+        let storage = MockStorage::new(vec![PathBuf::from("backup.yml")]);
+
+        assert_eq!(
+            restore_command(
+                &storage,
+                &ui(None),
+                &MockConfirmPrompt { result: true },
+                &backup_reader(),
+                false,
+            ),
+            Err(CommandError::Cancelled)
+        );
 
-        let storage = MockStorage {
-            backups: vec![path.clone()],
-            restore_called_with: RefCell::new(None),
-            fail_get: false,
-            fail_restore: true,
-        };
+        assert!(storage.restore_called_with.borrow().is_none());
+    }
 
-        let ui = MockSelectionUI {
-            selected_index: Some(0),
-        };
+    #[test]
+    fn test_get_backups_fails() {
+        let mut storage = MockStorage::new(vec![]);
+        storage.fail_get = true;
+
+        assert_eq!(
+            restore_command(
+                &storage,
+                &ui(Some(0)),
+                &MockConfirmPrompt { result: true },
+                &backup_reader(),
+                false,
+            ),
+            Err(CommandError::NotFound)
+        );
+
+        assert!(storage.restore_called_with.borrow().is_none());
+    }
 
-        restore_command(&storage, &ui);
+    #[test]
+    fn test_restore_fails() {
+        let path = PathBuf::from("broken_backup.yml");
+        let mut storage = MockStorage::new(vec![path.clone()]);
+        storage.fail_restore = true;
+
+        assert_eq!(
+            restore_command(
+                &storage,
+                &ui(Some(0)),
+                &MockConfirmPrompt { result: true },
+                &backup_reader(),
+                false,
+            ),
+            Err(CommandError::Storage)
+        );
 
         // Called but failed internally
         assert_eq!(*storage.restore_called_with.borrow(), Some(path));
@@ -0,0 +1,282 @@
+use chrono::Local;
+
+use crate::{
+    commands::{
+        error::{CommandError, CommandResult},
+        helper::{get_snippet, load_store, lock_storage},
+    },
+    diff::{changed_fields, describe_modification},
+    models::Snippet,
+    storage::Storage,
+    ui::SelectionUI,
+};
+
+/// Lists the kept revisions of a snippet, newest first, with the fields each
+/// one changed.
+pub fn log_command(
+    storage: &dyn Storage,
+    selection_ui: &dyn SelectionUI,
+    name: String,
+) -> CommandResult {
+    let store = load_store(storage)?;
+    let snippet = get_snippet(storage, &store, selection_ui, name)?;
+
+    let current = snippet.revision_number();
+    let oldest = snippet.revisions.first().map_or(current, |r| r.number);
+
+    println!("📜 Revisions of '{}':", snippet.name);
+    for number in (oldest..=current).rev() {
+        let Some(version) = snippet.at_revision(number) else {
+            continue;
+        };
+        let summary = match number.checked_sub(1).and_then(|n| snippet.at_revision(n)) {
+            Some(before) => changed_fields(&before, &version).join(", "),
+            None if number == 1 => "created".to_string(),
+            None => String::new(),
+        };
+        let marker = if number == current { " (current)" } else { "" };
+        println!(
+            "  #{:<3} {}  {}{}",
+            number,
+            version
+                .updated_at
+                .with_timezone(&Local)
+                .format("%Y-%m-%d %H:%M:%S"),
+            summary,
+            marker
+        );
+    }
+
+    if snippet.revisions.is_empty() {
+        println!("💡 Revisions are kept each time the snippet is edited.");
+    }
+    Ok(())
+}
+
+/// Shows how revision `to` differs from revision `from`. Without revisions
+/// the current version is compared with the one before it; with only `from`
+/// it is compared with the current version.
+pub fn diff_command(
+    storage: &dyn Storage,
+    selection_ui: &dyn SelectionUI,
+    name: String,
+    from: Option<u32>,
+    to: Option<u32>,
+) -> CommandResult {
+    let store = load_store(storage)?;
+    let snippet = get_snippet(storage, &store, selection_ui, name)?;
+
+    let current = snippet.revision_number();
+    let to = to.unwrap_or(current);
+    let from = match from {
+        Some(from) => from,
+        None if current > 1 => current - 1,
+        None => {
+            println!("📭 '{}' has no earlier revisions.", snippet.name);
+            return Ok(());
+        }
+    };
+
+    let old = revision(&snippet, from)?;
+    let new = revision(&snippet, to)?;
+
+    println!("--- #{}", from);
+    println!("+++ #{}", to);
+    let lines = describe_modification(&old, &new);
+    if lines.is_empty() {
+        println!("No differences.");
+    }
+    for line in lines {
+        println!("{}", line);
+    }
+    Ok(())
+}
+
+/// Brings back revision `number` as a new revision, so the revert itself can
+/// be reverted.
+pub fn revert_command(
+    storage: &dyn Storage,
+    selection_ui: &dyn SelectionUI,
+    name: String,
+    number: u32,
+) -> CommandResult {
+    let store = load_store(storage)?;
+    let chosen = get_snippet(storage, &store, selection_ui, name)?;
+
+    let _lock = lock_storage(storage)?;
+
+    let mut store = load_store(storage)?;
+
+    let Some(snippet) = store.snippets.iter_mut().find(|s| s.id == chosen.id) else {
+        eprintln!("⛔ Snippet '{}' was removed in the meantime.", chosen.name);
+        return Err(CommandError::NotFound);
+    };
+
+    if number == snippet.revision_number() {
+        println!("✅ '{}' is already at revision #{}.", snippet.name, number);
+        return Ok(());
+    }
+
+    let target = revision(snippet, number)?;
+    let previous = snippet.clone();
+    snippet.description = target.description;
+    snippet.content = target.content;
+    snippet.language = target.language;
+    snippet.executable = target.executable;
+    snippet.tags = target.tags;
+    snippet.updated_at = chrono::Utc::now();
+    snippet.keep_revision(&previous);
+    let current = snippet.revision_number();
+
+    if let Err(e) = storage.save_all(&store) {
+        eprintln!("⛔ Failed to update snippet: {}", e);
+        return Err(CommandError::Storage);
+    }
+
+    println!(
+        "⏪ Snippet '{}' reverted to revision #{} (saved as #{}).",
+        chosen.name, number, current
+    );
+    Ok(())
+}
+
+fn revision(snippet: &Snippet, number: u32) -> Result<Snippet, CommandError> {
+    snippet.at_revision(number).ok_or_else(|| {
+        eprintln!(
+            "⛔ '{}' has no revision #{}. See `markit log {}`.",
+            snippet.name, number, snippet.name
+        );
+        CommandError::NotFound
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        models::SnippetStore, storage::StorageError, ui::preselected::PreselectedSelection,
+    };
+    use chrono::Utc;
+    use std::cell::RefCell;
+
+    struct MockStorage {
+        store: RefCell<SnippetStore>,
+    }
+
+    impl Storage for MockStorage {
+        fn load(&self) -> Result<SnippetStore, StorageError> {
+            Ok(self.store.borrow().clone())
+        }
+
+        fn save(&self, _: Snippet) -> Result<(), StorageError> {
+            Ok(())
+        }
+
+        fn save_all(&self, store: &SnippetStore) -> Result<(), StorageError> {
+            self.store.replace(store.clone());
+            Ok(())
+        }
+
+        fn get_backups(&self) -> Result<Vec<std::path::PathBuf>, StorageError> {
+            Ok(vec![])
+        }
+
+        fn restore_backup(&self, _: &std::path::Path) -> Result<(), StorageError> {
+            Ok(())
+        }
+    }
+
+    /// A snippet edited twice: "v1" → "v2" → "v3".
+    fn storage() -> MockStorage {
+        let mut snippet = Snippet {
+            id: Snippet::new_id(),
+            name: "deploy".to_string(),
+            description: "desc".to_string(),
+            content: "v1".to_string(),
+            language: None,
+            executable: true,
+            tags: vec![],
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            revisions: vec![],
+        };
+        for content in ["v2", "v3"] {
+            let previous = snippet.clone();
+            snippet.content = content.to_string();
+            snippet.keep_revision(&previous);
+        }
+        MockStorage {
+            store: RefCell::new(SnippetStore {
+                snippets: vec![snippet],
+                trash: vec![],
+            }),
+        }
+    }
+
+    fn current(storage: &MockStorage) -> Snippet {
+        storage.store.borrow().snippets[0].clone()
+    }
+
+    #[test]
+    fn test_revisions_are_numbered() {
+        let snippet = current(&storage());
+
+        assert_eq!(snippet.revision_number(), 3);
+        assert_eq!(snippet.at_revision(1).unwrap().content, "v1");
+        assert_eq!(snippet.at_revision(3).unwrap().content, "v3");
+        assert!(snippet.at_revision(4).is_none());
+    }
+
+    #[test]
+    fn test_log_and_diff() {
+        let storage = storage();
+        let selection = PreselectedSelection::new("deploy");
+
+        assert!(log_command(&storage, &selection, "deploy".to_string()).is_ok());
+        assert!(diff_command(&storage, &selection, "deploy".to_string(), None, None).is_ok());
+        assert!(diff_command(&storage, &selection, "deploy".to_string(), Some(1), Some(2)).is_ok());
+        assert_eq!(
+            diff_command(&storage, &selection, "deploy".to_string(), Some(7), None),
+            Err(CommandError::NotFound)
+        );
+    }
+
+    #[test]
+    fn test_revert_keeps_the_replaced_version() {
+        let storage = storage();
+        let selection = PreselectedSelection::new("deploy");
+
+        assert!(revert_command(&storage, &selection, "deploy".to_string(), 1).is_ok());
+
+        let snippet = current(&storage);
+        assert_eq!(snippet.content, "v1");
+        assert_eq!(snippet.revision_number(), 4);
+        assert_eq!(snippet.at_revision(3).unwrap().content, "v3");
+    }
+
+    #[test]
+    fn test_revert_unknown_revision() {
+        let storage = storage();
+        let selection = PreselectedSelection::new("deploy");
+
+        assert_eq!(
+            revert_command(&storage, &selection, "deploy".to_string(), 9),
+            Err(CommandError::NotFound)
+        );
+        assert_eq!(current(&storage).content, "v3");
+    }
+
+    #[test]
+    fn test_only_the_latest_revisions_are_kept() {
+        let mut snippet = current(&storage());
+        for i in 0..30 {
+            let previous = snippet.clone();
+            snippet.content = format!("edit {}", i);
+            snippet.keep_revision(&previous);
+        }
+
+        assert_eq!(snippet.revisions.len(), crate::models::MAX_REVISIONS);
+        assert_eq!(snippet.revision_number(), 33);
+        assert_eq!(snippet.revisions[0].number, 13);
+    }
+}
@@ -1,39 +1,178 @@
+use std::{collections::BTreeMap, path::PathBuf, time::Instant};
+
+use chrono::Utc;
+
+use serde::Serialize;
+
 use crate::{
-    command_runner::CommandRunner, commands::helper::get_snippet, storage::Storage, ui::SelectionUI,
+    command_runner::{CommandRunner, ExecutionPlan, RunRequest},
+    commands::{
+        error::{CommandError, CommandResult},
+        helper::{get_snippet, load_store, record_usage, resolve_variables},
+    },
+    input::VariableInput,
+    placeholder,
+    storage::{HistoryLog, Storage, history::HistoryEntry, usage::UsageKind},
+    ui::{PlanFormat, SelectionUI},
 };
 
+#[derive(Default)]
+pub struct RunOptions {
+    /// Placeholder values given with `--var`.
+    pub vars: Vec<(String, String)>,
+    pub dry_run: bool,
+    pub format: PlanFormat,
+    /// Passed to the snippet as `$1`, `$2`, ...
+    pub args: Vec<String>,
+    pub env: Vec<(String, String)>,
+    pub cwd: Option<PathBuf>,
+}
+
+#[derive(Serialize)]
+struct DryRun<'a> {
+    snippet: &'a str,
+    command: &'a str,
+    variables: &'a BTreeMap<String, String>,
+    #[serde(flatten)]
+    plan: &'a ExecutionPlan,
+}
+
 pub fn run_command(
     storage: &dyn Storage,
     selection_ui: &dyn SelectionUI,
+    variable_input: &dyn VariableInput,
     runner: &dyn CommandRunner,
+    history: &dyn HistoryLog,
     name: String,
-) {
-    let store = match storage.load() {
-        Ok(s) => s,
-        Err(_) => {
-            println!("📭 No snippets saved yet.");
-            return;
-        }
-    };
+    options: &RunOptions,
+) -> CommandResult {
+    let store = load_store(storage)?;
 
-    let snippet = match get_snippet(&store, selection_ui, name) {
-        Some(s) => s,
-        None => return,
-    };
+    let snippet = get_snippet(storage, &store, selection_ui, name)?;
 
     if !snippet.executable {
         println!("⛔ Snippet '{}' not executable.", snippet.name);
-        return;
+        return Err(CommandError::Validation);
+    }
+
+    let variables = resolve_variables(&snippet.content, variable_input, &options.vars)?;
+    let content =
+        placeholder::substitute(&snippet.content, &variables.clone().into_iter().collect());
+    let request = RunRequest {
+        name: snippet.name.clone(),
+        command: content.clone(),
+        language: snippet.language.clone(),
+        args: options.args.clone(),
+        env: options.env.iter().cloned().collect(),
+        cwd: options.cwd.clone(),
+    };
+    let plan = match runner.plan(&request) {
+        Ok(plan) => plan,
+        Err(e) => {
+            eprintln!("⛔ {}", e);
+            return Err(CommandError::Validation);
+        }
+    };
+
+    if options.dry_run {
+        return print_dry_run(
+            &DryRun {
+                snippet: &snippet.name,
+                command: &content,
+                variables: &variables,
+                plan: &plan,
+            },
+            options.format,
+        );
     }
 
     println!("🚀 Running: {}", snippet.name);
-    println!("📋 {}", snippet.content);
+    println!("📋 {}", content);
+    record_usage(storage, &snippet.id, UsageKind::Run);
+
+    let entry = HistoryEntry {
+        snippet: snippet.name,
+        snippet_id: Some(snippet.id),
+        command: content,
+        language: snippet.language,
+        variables,
+        args: request.args,
+        env_keys: request.env.into_keys().collect(),
+        cwd: plan.cwd.clone(),
+        started_at: Utc::now(),
+        duration_ms: 0,
+        exit_code: None,
+    };
+    execute_and_record(runner, history, &plan, entry)
+}
 
-    match runner.run(&snippet.content) {
-        Ok(code) if code.success() => println!("✅ Command ran successfully."),
-        Ok(code) => println!("⚠️ Command exited with status: {}", code),
-        Err(err) => println!("⛔ Failed to run command: {}", err),
+/// Executes `plan`, reports how it went and appends the run to the history.
+pub fn execute_and_record(
+    runner: &dyn CommandRunner,
+    history: &dyn HistoryLog,
+    plan: &ExecutionPlan,
+    mut entry: HistoryEntry,
+) -> CommandResult {
+    let started = Instant::now();
+    entry.started_at = Utc::now();
+    let result = runner.execute(plan);
+    entry.duration_ms = started.elapsed().as_millis() as u64;
+    entry.exit_code = result
+        .as_ref()
+        .ok()
+        .map(|status| CommandError::from_status(*status).exit_code());
+
+    if let Err(e) = history.append(&entry) {
+        eprintln!("⚠️ Failed to record run history: {}", e);
     }
+
+    match result {
+        Ok(code) if code.success() => {
+            println!("✅ Command ran successfully.");
+            Ok(())
+        }
+        Ok(code) => {
+            println!("⚠️ Command exited with status: {}", code);
+            Err(CommandError::from_status(code))
+        }
+        Err(err) => {
+            println!("⛔ Failed to run command: {}", err);
+            Err(CommandError::Failed)
+        }
+    }
+}
+
+fn print_dry_run(dry_run: &DryRun, format: PlanFormat) -> CommandResult {
+    if format == PlanFormat::Json {
+        return match serde_json::to_string_pretty(dry_run) {
+            Ok(json) => {
+                println!("{}", json);
+                Ok(())
+            }
+            Err(e) => {
+                eprintln!("⛔ Failed to format plan: {}", e);
+                Err(CommandError::Failed)
+            }
+        };
+    }
+
+    let join = |map: &BTreeMap<String, String>| {
+        if map.is_empty() {
+            return "(none)".to_string();
+        }
+        map.iter()
+            .map(|(k, v)| format!("{}={}", k, v))
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+
+    println!("🔍 Dry run: {}", dry_run.snippet);
+    println!("📋 Command:     {}", dry_run.command);
+    println!("🐚 Would run:   {}", dry_run.plan.command_line());
+    println!("📂 Directory:   {}", dry_run.plan.cwd.display());
+    println!("🌱 Environment: {}", join(&dry_run.plan.env));
+    println!("🔧 Variables:   {}", join(dry_run.variables));
+    Ok(())
 }
 
 #[cfg(test)]
@@ -45,6 +184,7 @@ mod tests {
         ui::SelectionUI,
     };
     use chrono::Utc;
+    use std::cell::RefCell;
     use std::process::ExitStatus;
 
     struct MockStorage {
@@ -59,6 +199,7 @@ mod tests {
             } else {
                 Ok(crate::models::SnippetStore {
                     snippets: self.snippet.clone().into_iter().collect(),
+                    trash: vec![],
                 })
             }
         }
@@ -92,14 +233,72 @@ mod tests {
         fn with_backup_list(&self, _backups: &[String]) -> Option<usize> {
             Some(0)
         }
+
+        fn with_multi_select(&self, _: &str, _: &[String]) -> Vec<usize> {
+            vec![]
+        }
+    }
+
+    struct NonInteractiveSelectionUI;
+
+    impl SelectionUI for NonInteractiveSelectionUI {
+        fn with_snippet_list(&self, snippets: Vec<Snippet>) -> Option<Snippet> {
+            snippets.into_iter().next()
+        }
+
+        fn with_backup_list(&self, _backups: &[String]) -> Option<usize> {
+            None
+        }
+
+        fn with_multi_select(&self, _: &str, _: &[String]) -> Vec<usize> {
+            vec![]
+        }
+
+        fn is_interactive(&self) -> bool {
+            false
+        }
+    }
+
+    struct MockVariableInput {
+        value: Option<String>,
+    }
+
+    impl VariableInput for MockVariableInput {
+        fn get_value(&self, _name: &str, default: Option<&str>) -> Option<String> {
+            self.value.clone().or(default.map(str::to_string))
+        }
     }
 
     struct MockCommandRunner {
         result: Result<ExitStatus, std::io::Error>,
+        last_command: RefCell<Option<String>>,
+        last_request: RefCell<Option<RunRequest>>,
+    }
+
+    impl MockCommandRunner {
+        fn new(result: Result<ExitStatus, std::io::Error>) -> Self {
+            Self {
+                result,
+                last_command: RefCell::new(None),
+                last_request: RefCell::new(None),
+            }
+        }
     }
 
     impl CommandRunner for MockCommandRunner {
-        fn run(&self, _command: &str) -> Result<ExitStatus, std::io::Error> {
+        fn plan(&self, request: &RunRequest) -> Result<ExecutionPlan, String> {
+            self.last_request.replace(Some(request.clone()));
+            Ok(ExecutionPlan {
+                program: "sh".to_string(),
+                args: vec!["-c".to_string(), request.command.clone()],
+                cwd: request.cwd.clone().unwrap_or_else(|| PathBuf::from(".")),
+                env: request.env.clone(),
+                script: None,
+            })
+        }
+
+        fn execute(&self, plan: &ExecutionPlan) -> Result<ExitStatus, std::io::Error> {
+            self.last_command.replace(plan.args.last().cloned());
             match &self.result {
                 Ok(status) => Ok(*status),
                 Err(e) => Err(std::io::Error::new(e.kind(), e.to_string())),
@@ -107,15 +306,34 @@ mod tests {
         }
     }
 
+    #[derive(Default)]
+    struct MockHistory {
+        entries: RefCell<Vec<HistoryEntry>>,
+    }
+
+    impl HistoryLog for MockHistory {
+        fn append(&self, entry: &HistoryEntry) -> Result<(), StorageError> {
+            self.entries.borrow_mut().push(entry.clone());
+            Ok(())
+        }
+
+        fn entries(&self) -> Result<Vec<HistoryEntry>, StorageError> {
+            Ok(self.entries.borrow().clone())
+        }
+    }
+
     fn test_snippet(name: &str, executable: bool) -> Snippet {
         Snippet {
+            id: Snippet::new_id(),
             name: name.to_string(),
             description: "desc".to_string(),
             content: "echo test".to_string(),
+            language: None,
             executable,
             tags: vec![],
             created_at: Utc::now(),
             updated_at: Utc::now(),
+            revisions: vec![],
         }
     }
 
@@ -140,11 +358,22 @@ mod tests {
             snippet: Some(snippet),
         };
 
-        let runner = MockCommandRunner {
-            result: Ok(fake_exit_status(true)),
-        };
-
-        run_command(&storage, &ui, &runner, "test".to_string());
+        let runner = MockCommandRunner::new(Ok(fake_exit_status(true)));
+
+        let history = MockHistory::default();
+
+        assert!(
+            run_command(
+                &storage,
+                &ui,
+                &MockVariableInput { value: None },
+                &runner,
+                &history,
+                "test".to_string(),
+                &RunOptions::default()
+            )
+            .is_ok()
+        );
     }
 
     #[test]
@@ -160,11 +389,22 @@ mod tests {
             snippet: Some(snippet),
         };
 
-        let runner = MockCommandRunner {
-            result: Ok(fake_exit_status(false)),
-        };
-
-        run_command(&storage, &ui, &runner, "test".to_string());
+        let runner = MockCommandRunner::new(Ok(fake_exit_status(false)));
+
+        let history = MockHistory::default();
+
+        assert_eq!(
+            run_command(
+                &storage,
+                &ui,
+                &MockVariableInput { value: None },
+                &runner,
+                &history,
+                "test".to_string(),
+                &RunOptions::default()
+            ),
+            Err(CommandError::Exited(1))
+        );
     }
 
     #[test]
@@ -180,11 +420,22 @@ mod tests {
             snippet: Some(snippet),
         };
 
-        let runner = MockCommandRunner {
-            result: Err(std::io::Error::other("Mock error")),
-        };
-
-        run_command(&storage, &ui, &runner, "test".to_string());
+        let runner = MockCommandRunner::new(Err(std::io::Error::other("Mock error")));
+
+        let history = MockHistory::default();
+
+        assert_eq!(
+            run_command(
+                &storage,
+                &ui,
+                &MockVariableInput { value: None },
+                &runner,
+                &history,
+                "test".to_string(),
+                &RunOptions::default()
+            ),
+            Err(CommandError::Failed)
+        );
     }
 
     #[test]
@@ -200,11 +451,22 @@ mod tests {
             snippet: Some(snippet),
         };
 
-        let runner = MockCommandRunner {
-            result: Ok(fake_exit_status(true)),
-        };
-
-        run_command(&storage, &ui, &runner, "test".to_string());
+        let runner = MockCommandRunner::new(Ok(fake_exit_status(true)));
+
+        let history = MockHistory::default();
+
+        assert_eq!(
+            run_command(
+                &storage,
+                &ui,
+                &MockVariableInput { value: None },
+                &runner,
+                &history,
+                "test".to_string(),
+                &RunOptions::default()
+            ),
+            Err(CommandError::Validation)
+        );
     }
 
     #[test]
@@ -216,11 +478,22 @@ mod tests {
 
         let ui = MockSelectionUI { snippet: None };
 
-        let runner = MockCommandRunner {
-            result: Ok(fake_exit_status(true)),
-        };
-
-        run_command(&storage, &ui, &runner, "test".to_string());
+        let runner = MockCommandRunner::new(Ok(fake_exit_status(true)));
+
+        let history = MockHistory::default();
+
+        assert_eq!(
+            run_command(
+                &storage,
+                &ui,
+                &MockVariableInput { value: None },
+                &runner,
+                &history,
+                "test".to_string(),
+                &RunOptions::default()
+            ),
+            Err(CommandError::Storage)
+        );
     }
 
     #[test]
@@ -232,10 +505,226 @@ mod tests {
 
         let ui = MockSelectionUI { snippet: None };
 
-        let runner = MockCommandRunner {
-            result: Ok(fake_exit_status(true)),
+        let runner = MockCommandRunner::new(Ok(fake_exit_status(true)));
+
+        let history = MockHistory::default();
+
+        assert_eq!(
+            run_command(
+                &storage,
+                &ui,
+                &MockVariableInput { value: None },
+                &runner,
+                &history,
+                "test".to_string(),
+                &RunOptions::default()
+            ),
+            Err(CommandError::NotFound)
+        );
+    }
+
+    #[test]
+    fn test_run_substitutes_variables() {
+        let mut snippet = test_snippet("test", true);
+        snippet.content = "kubectl -n {{namespace}} get pods --port {{port:8080}}".to_string();
+
+        let storage = MockStorage {
+            snippet: Some(snippet.clone()),
+            fail_load: false,
+        };
+
+        let ui = MockSelectionUI {
+            snippet: Some(snippet),
+        };
+
+        let runner = MockCommandRunner::new(Ok(fake_exit_status(true)));
+
+        let history = MockHistory::default();
+
+        assert!(
+            run_command(
+                &storage,
+                &ui,
+                &MockVariableInput { value: None },
+                &runner,
+                &history,
+                "test".to_string(),
+                &RunOptions {
+                    vars: vec![("namespace".to_string(), "prod".to_string())],
+                    ..Default::default()
+                }
+            )
+            .is_ok()
+        );
+
+        assert_eq!(
+            runner.last_command.borrow().as_deref(),
+            Some("kubectl -n prod get pods --port 8080")
+        );
+    }
+
+    #[test]
+    fn test_run_single_fuzzy_match_is_not_auto_run() {
+        let storage = MockStorage {
+            snippet: Some(test_snippet("deploy-prod", true)),
+            fail_load: false,
+        };
+        let runner = MockCommandRunner::new(Ok(fake_exit_status(true)));
+        let history = MockHistory::default();
+
+        // Without a terminal there is no one to confirm the guess.
+        assert_eq!(
+            run_command(
+                &storage,
+                &NonInteractiveSelectionUI,
+                &MockVariableInput { value: None },
+                &runner,
+                &history,
+                "dprod".to_string(),
+                &RunOptions::default()
+            ),
+            Err(CommandError::NotFound)
+        );
+
+        // With one, the match is offered and declining it runs nothing.
+        assert_eq!(
+            run_command(
+                &storage,
+                &MockSelectionUI { snippet: None },
+                &MockVariableInput { value: None },
+                &runner,
+                &history,
+                "dprod".to_string(),
+                &RunOptions::default()
+            ),
+            Err(CommandError::Cancelled)
+        );
+
+        assert!(runner.last_command.borrow().is_none());
+    }
+
+    #[test]
+    fn test_run_missing_variable_does_not_execute() {
+        let mut snippet = test_snippet("test", true);
+        snippet.content = "ssh {{host}}".to_string();
+
+        let storage = MockStorage {
+            snippet: Some(snippet.clone()),
+            fail_load: false,
+        };
+
+        let ui = MockSelectionUI {
+            snippet: Some(snippet),
+        };
+
+        let runner = MockCommandRunner::new(Ok(fake_exit_status(true)));
+
+        let history = MockHistory::default();
+
+        assert_eq!(
+            run_command(
+                &storage,
+                &ui,
+                &MockVariableInput { value: None },
+                &runner,
+                &history,
+                "test".to_string(),
+                &RunOptions::default()
+            ),
+            Err(CommandError::Validation)
+        );
+
+        assert!(runner.last_command.borrow().is_none());
+    }
+
+    #[test]
+    fn test_run_dry_run_does_not_execute() {
+        let mut snippet = test_snippet("test", true);
+        snippet.content = "ssh {{host:localhost}}".to_string();
+
+        let storage = MockStorage {
+            snippet: Some(snippet.clone()),
+            fail_load: false,
+        };
+
+        let ui = MockSelectionUI {
+            snippet: Some(snippet),
+        };
+
+        let runner = MockCommandRunner::new(Ok(fake_exit_status(true)));
+
+        let history = MockHistory::default();
+
+        for format in [PlanFormat::Text, PlanFormat::Json] {
+            assert!(
+                run_command(
+                    &storage,
+                    &ui,
+                    &MockVariableInput { value: None },
+                    &runner,
+                    &history,
+                    "test".to_string(),
+                    &RunOptions {
+                        dry_run: true,
+                        format,
+                        ..Default::default()
+                    }
+                )
+                .is_ok()
+            );
+        }
+
+        assert!(runner.last_command.borrow().is_none());
+        assert!(history.entries.borrow().is_empty());
+    }
+
+    #[test]
+    fn test_run_passes_args_env_and_cwd() {
+        let snippet = test_snippet("deploy", true);
+
+        let storage = MockStorage {
+            snippet: Some(snippet.clone()),
+            fail_load: false,
+        };
+
+        let ui = MockSelectionUI {
+            snippet: Some(snippet),
         };
 
-        run_command(&storage, &ui, &runner, "test".to_string());
+        let runner = MockCommandRunner::new(Ok(fake_exit_status(true)));
+
+        let history = MockHistory::default();
+
+        assert!(
+            run_command(
+                &storage,
+                &ui,
+                &MockVariableInput { value: None },
+                &runner,
+                &history,
+                "deploy".to_string(),
+                &RunOptions {
+                    args: vec!["staging".to_string(), "--force".to_string()],
+                    env: vec![("STAGE".to_string(), "staging".to_string())],
+                    cwd: Some(PathBuf::from("/srv")),
+                    ..Default::default()
+                }
+            )
+            .is_ok()
+        );
+
+        let request = runner.last_request.borrow().clone().unwrap();
+        assert_eq!(request.name, "deploy");
+        assert_eq!(request.command, "echo test");
+        assert_eq!(request.args, vec!["staging", "--force"]);
+        assert_eq!(request.env["STAGE"], "staging");
+        assert_eq!(request.cwd, Some(PathBuf::from("/srv")));
+
+        let recorded = history.entries.borrow();
+        assert_eq!(recorded.len(), 1);
+        assert_eq!(recorded[0].snippet, "deploy");
+        assert_eq!(recorded[0].args, vec!["staging", "--force"]);
+        assert_eq!(recorded[0].cwd, PathBuf::from("/srv"));
+        assert_eq!(recorded[0].exit_code, Some(0));
     }
 }
@@ -1,36 +1,73 @@
 use chrono::Utc;
 
-use crate::{input::SaveInput, models::Snippet, storage::Storage};
-
-pub fn save_command(storage: &dyn Storage, input: &dyn SaveInput, name: String) {
-    if let Ok(store) = storage.load() {
-        if store
-            .snippets
-            .iter()
-            .any(|s| s.name.eq_ignore_ascii_case(&name))
-        {
-            eprintln!("⛔ A snippet with the name '{}' already exists.", name);
-            return;
-        }
+use crate::{
+    commands::{
+        error::{CommandError, CommandResult},
+        helper::{load_store, lock_storage},
+    },
+    input::SaveInput,
+    models::{Snippet, SnippetStore},
+    storage::Storage,
+};
+
+pub fn save_command(storage: &dyn Storage, input: &dyn SaveInput, name: String) -> CommandResult {
+    let store = load_store(storage)?;
+
+    if name_taken(&store, &name) {
+        eprintln!("⛔ A snippet with the name '{}' already exists.", name);
+        return Err(CommandError::Validation);
     }
 
     let now = Utc::now();
     let entry = Snippet {
+        id: Snippet::new_id(),
         name,
         description: input.get_description(),
         content: input.get_content(),
+        language: input.get_language(),
         executable: input.get_executable(),
         tags: input.get_tags(),
         created_at: now,
         updated_at: now,
+        revisions: vec![],
     };
 
+    if entry.content.trim().is_empty() {
+        eprintln!("⛔ The snippet content is empty; nothing was saved.");
+        return Err(CommandError::Validation);
+    }
+
+    let _lock = lock_storage(storage)?;
+
+    let store = load_store(storage)?;
+
+    if name_taken(&store, &entry.name) {
+        eprintln!(
+            "⛔ A snippet with the name '{}' already exists.",
+            entry.name
+        );
+        return Err(CommandError::Validation);
+    }
+
     match storage.save(entry) {
-        Ok(_) => println!("✅ Snippet saved successfully."),
-        Err(e) => eprintln!("⛔ Failed to save snippet: {}", e),
+        Ok(_) => {
+            println!("✅ Snippet saved successfully.");
+            Ok(())
+        }
+        Err(e) => {
+            eprintln!("⛔ Failed to save snippet: {}", e);
+            Err(CommandError::Storage)
+        }
     }
 }
 
+fn name_taken(store: &SnippetStore, name: &str) -> bool {
+    store
+        .snippets
+        .iter()
+        .any(|s| s.name.eq_ignore_ascii_case(name))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -81,6 +118,26 @@ mod tests {
         }
     }
 
+    struct MockInputBlankContent;
+
+    impl SaveInput for MockInputBlankContent {
+        fn get_description(&self) -> String {
+            "Piped nothing".to_string()
+        }
+
+        fn get_executable(&self) -> bool {
+            true
+        }
+
+        fn get_content(&self) -> String {
+            " \n\t\n".to_string()
+        }
+
+        fn get_tags(&self) -> Vec<String> {
+            vec![]
+        }
+    }
+
     // Mock storage
     struct MockStorage {
         saved_snippets: RefCell<Vec<Snippet>>,
@@ -114,6 +171,7 @@ mod tests {
         fn load(&self) -> Result<SnippetStore, StorageError> {
             Ok(SnippetStore {
                 snippets: self.saved_snippets.borrow().clone(),
+                trash: vec![],
             })
         }
 
@@ -144,7 +202,7 @@ mod tests {
         let input = MockInput;
         let name = "test_snippet".to_string();
 
-        save_command(&storage, &input, name.clone());
+        assert!(save_command(&storage, &input, name.clone()).is_ok());
 
         let snippets = storage.saved_snippets.borrow();
         assert_eq!(snippets.len(), 1);
@@ -160,19 +218,25 @@ mod tests {
     #[test]
     fn test_save_command_rejects_duplicate() {
         let existing_snippet = Snippet {
+            id: Snippet::new_id(),
             name: "duplicate".to_string(),
             description: "Existing".to_string(),
             content: "echo test".to_string(),
+            language: None,
             executable: false,
             tags: vec![],
             created_at: Utc::now(),
             updated_at: Utc::now(),
+            revisions: vec![],
         };
 
         let storage = MockStorage::with_existing(existing_snippet);
         let input = MockInput;
 
-        save_command(&storage, &input, "duplicate".to_string());
+        assert_eq!(
+            save_command(&storage, &input, "duplicate".to_string()),
+            Err(CommandError::Validation)
+        );
 
         // Should not save another
         let snippets = storage.saved_snippets.borrow();
@@ -186,7 +250,10 @@ mod tests {
         let input = MockInput;
 
         // Should not panic
-        save_command(&storage, &input, "fail_test".to_string());
+        assert_eq!(
+            save_command(&storage, &input, "fail_test".to_string()),
+            Err(CommandError::Storage)
+        );
 
         let snippets = storage.saved_snippets.borrow();
         assert!(snippets.is_empty());
@@ -197,10 +264,21 @@ mod tests {
         let storage = MockStorage::new();
         let input = MockInputNoTags;
 
-        save_command(&storage, &input, "no_tags".to_string());
+        assert!(save_command(&storage, &input, "no_tags".to_string()).is_ok());
 
         let snippets = storage.saved_snippets.borrow();
         assert_eq!(snippets.len(), 1);
         assert!(snippets[0].tags.is_empty());
     }
+
+    #[test]
+    fn test_save_command_rejects_blank_content() {
+        let storage = MockStorage::new();
+
+        assert_eq!(
+            save_command(&storage, &MockInputBlankContent, "blank".to_string()),
+            Err(CommandError::Validation)
+        );
+        assert!(storage.saved_snippets.borrow().is_empty());
+    }
 }
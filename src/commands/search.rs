@@ -0,0 +1,143 @@
+use crate::{
+    commands::{error::CommandResult, helper::load_store},
+    storage::{Storage, filter::fuzzy_rank},
+    ui::TableUI,
+};
+
+pub fn search_command(
+    storage: &dyn Storage,
+    table_ui: &mut dyn TableUI,
+    query: String,
+) -> CommandResult {
+    let store = load_store(storage)?;
+
+    let results = fuzzy_rank(&store, &query);
+
+    if results.is_empty() {
+        println!("🔍 No snippets match '{}'.", query);
+    } else {
+        let table = table_ui.with_search_results(results);
+        println!("{table}");
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+
+    use crate::{
+        commands::search::search_command,
+        models::{Snippet, SnippetStore},
+        storage::{
+            Storage, StorageError, filter::FuzzyMatch, history::HistoryEntry,
+            retention::BackupInfo, usage::UsageStats,
+        },
+        ui::TableUI,
+    };
+
+    struct MockStorage {
+        store: SnippetStore,
+    }
+
+    impl Storage for MockStorage {
+        fn load(&self) -> Result<SnippetStore, StorageError> {
+            Ok(self.store.clone())
+        }
+
+        fn save(&self, _: Snippet) -> Result<(), StorageError> {
+            Ok(())
+        }
+
+        fn save_all(&self, _: &SnippetStore) -> Result<(), StorageError> {
+            Ok(())
+        }
+
+        fn get_backups(&self) -> Result<Vec<std::path::PathBuf>, StorageError> {
+            Ok(vec![])
+        }
+
+        fn restore_backup(&self, _: &std::path::Path) -> Result<(), StorageError> {
+            Ok(())
+        }
+    }
+
+    struct MockTableUI {
+        results: RefCell<Option<Vec<String>>>,
+    }
+
+    impl TableUI for MockTableUI {
+        fn with_snippet_list(&mut self, _: Vec<Snippet>, _: &UsageStats) -> comfy_table::Table {
+            comfy_table::Table::new()
+        }
+
+        fn with_backup_list(&mut self, _: Vec<BackupInfo>) -> comfy_table::Table {
+            comfy_table::Table::new()
+        }
+
+        fn with_search_results(&mut self, results: Vec<FuzzyMatch>) -> comfy_table::Table {
+            self.results
+                .replace(Some(results.into_iter().map(|r| r.snippet.name).collect()));
+            comfy_table::Table::new()
+        }
+
+        fn with_history(&mut self, _: Vec<(usize, HistoryEntry)>) -> comfy_table::Table {
+            comfy_table::Table::new()
+        }
+    }
+
+    fn snippet(name: &str, content: &str) -> Snippet {
+        Snippet {
+            id: Snippet::new_id(),
+            name: name.to_string(),
+            description: "desc".to_string(),
+            content: content.to_string(),
+            language: None,
+            executable: true,
+            tags: vec![],
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+            revisions: vec![],
+        }
+    }
+
+    #[test]
+    fn test_search_command_ranks_results() {
+        let storage = MockStorage {
+            store: SnippetStore {
+                snippets: vec![
+                    snippet("list-pods", "kubectl get pods"),
+                    snippet("pods", "kubectl get pods -A"),
+                ],
+                trash: vec![],
+            },
+        };
+
+        let mut table_ui = MockTableUI {
+            results: RefCell::new(None),
+        };
+
+        assert!(search_command(&storage, &mut table_ui, "pods".to_string()).is_ok());
+
+        let results = table_ui.results.borrow().clone().unwrap();
+        assert_eq!(results, vec!["pods", "list-pods"]);
+    }
+
+    #[test]
+    fn test_search_command_no_matches() {
+        let storage = MockStorage {
+            store: SnippetStore {
+                snippets: vec![snippet("list-pods", "kubectl get pods")],
+                trash: vec![],
+            },
+        };
+
+        let mut table_ui = MockTableUI {
+            results: RefCell::new(None),
+        };
+
+        assert!(search_command(&storage, &mut table_ui, "zzz".to_string()).is_ok());
+
+        assert!(table_ui.results.borrow().is_none());
+    }
+}
@@ -1,25 +1,51 @@
-use crate::{commands::helper::get_snippet, storage::Storage, ui::SelectionUI};
-
-pub fn show_command(storage: &dyn Storage, selection_ui: &dyn SelectionUI, name: String) {
-    let store = match storage.load() {
-        Ok(s) => s,
-        Err(_) => {
-            println!("📭 No snippets saved yet.");
-            return;
-        }
-    };
+use crate::{
+    commands::{
+        error::{CommandError, CommandResult},
+        helper::{get_snippet, load_store, record_usage},
+    },
+    storage::{Storage, usage::UsageKind},
+    ui::{OutputFormatter, SelectionUI},
+};
+
+pub fn show_command(
+    storage: &dyn Storage,
+    selection_ui: &dyn SelectionUI,
+    formatter: Option<&dyn OutputFormatter>,
+    name: String,
+) -> CommandResult {
+    let store = load_store(storage)?;
+
+    let snippet = get_snippet(storage, &store, selection_ui, name)?;
+    record_usage(storage, &snippet.id, UsageKind::Show);
 
-    let Some(snippet) = get_snippet(&store, selection_ui, name) else {
-        return;
-    };
+    if let Some(formatter) = formatter {
+        return match formatter.format_snippet(&snippet) {
+            Ok(output) => {
+                println!("{output}");
+                Ok(())
+            }
+            Err(e) => {
+                eprintln!("⛔ Failed to format snippet: {}", e);
+                Err(CommandError::Failed)
+            }
+        };
+    }
 
     println!("🔎 Snippet: {}", snippet.name);
+    println!("🆔 Id: {}", snippet.id);
     println!("📄 Description: {}", snippet.description);
+    if let Some(language) = &snippet.language {
+        println!("🗣️ Language: {}", language);
+    }
     println!("🚀 Executable: {}", snippet.executable);
     println!("🕒 Created at: {}", snippet.created_at);
     println!("🕒 Updated at: {}", snippet.updated_at);
+    if !snippet.revisions.is_empty() {
+        println!("🗂️ Revision: #{}", snippet.revision_number());
+    }
     println!("📋 Content:\n{}", snippet.content);
     println!("🏷️ Tags: {}", snippet.tags.join(", "));
+    Ok(())
 }
 
 #[cfg(test)]
@@ -27,7 +53,7 @@ mod tests {
     use super::*;
     use crate::models::{Snippet, SnippetStore};
     use crate::storage::{Storage, StorageError};
-    use crate::ui::SelectionUI;
+    use crate::ui::{OutputFormatter, SelectionUI};
     use std::cell::RefCell;
 
     struct MockStorage {
@@ -42,6 +68,7 @@ mod tests {
             } else {
                 Ok(SnippetStore {
                     snippets: self.snippets.clone(),
+                    trash: vec![],
                 })
             }
         }
@@ -75,18 +102,75 @@ mod tests {
         fn with_backup_list(&self, _: &[String]) -> Option<usize> {
             Some(0)
         }
+
+        fn with_multi_select(&self, _: &str, _: &[String]) -> Vec<usize> {
+            vec![]
+        }
+    }
+
+    struct NonInteractiveSelectionUI;
+
+    impl SelectionUI for NonInteractiveSelectionUI {
+        fn with_snippet_list(&self, _: Vec<Snippet>) -> Option<Snippet> {
+            panic!("must not prompt without a terminal")
+        }
+
+        fn with_backup_list(&self, _: &[String]) -> Option<usize> {
+            None
+        }
+
+        fn with_multi_select(&self, _: &str, _: &[String]) -> Vec<usize> {
+            vec![]
+        }
+
+        fn is_interactive(&self) -> bool {
+            false
+        }
+    }
+
+    fn named(name: &str) -> Snippet {
+        Snippet {
+            id: Snippet::new_id(),
+            name: name.to_string(),
+            description: "desc".to_string(),
+            content: "echo hello".to_string(),
+            language: None,
+            executable: true,
+            tags: vec![],
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+            revisions: vec![],
+        }
+    }
+
+    struct MockFormatter {
+        formatted: RefCell<Option<String>>,
+    }
+
+    impl OutputFormatter for MockFormatter {
+        fn format_snippets(&self, _: &[Snippet]) -> Result<String, String> {
+            Ok(String::new())
+        }
+
+        fn format_snippet(&self, snippet: &Snippet) -> Result<String, String> {
+            self.formatted.replace(Some(snippet.name.clone()));
+            Ok(String::new())
+        }
     }
 
     #[test]
     fn test_show_command_success() {
         let snippet = Snippet {
+            id: Snippet::new_id(),
             name: "test".to_string(),
             description: "desc".to_string(),
             content: "echo hello".to_string(),
+            language: None,
             executable: true,
             tags: vec!["tag1".to_string()],
             created_at: chrono::Utc::now(),
             updated_at: chrono::Utc::now(),
+            revisions: vec![],
         };
 
         let storage = MockStorage {
@@ -98,7 +182,7 @@ mod tests {
             selection: RefCell::new(Some(snippet)),
         };
 
-        show_command(&storage, &selection_ui, "test".to_string());
+        assert!(show_command(&storage, &selection_ui, None, "test".to_string()).is_ok());
     }
 
     #[test]
@@ -112,19 +196,25 @@ mod tests {
             selection: RefCell::new(None),
         };
 
-        show_command(&storage, &selection_ui, "test".to_string());
+        assert_eq!(
+            show_command(&storage, &selection_ui, None, "test".to_string()),
+            Err(CommandError::Storage)
+        );
     }
 
     #[test]
     fn test_show_command_not_found() {
         let snippet = Snippet {
+            id: Snippet::new_id(),
             name: "test".to_string(),
             description: "desc".to_string(),
             content: "echo hello".to_string(),
+            language: None,
             executable: true,
             tags: vec!["tag1".to_string()],
             created_at: chrono::Utc::now(),
             updated_at: chrono::Utc::now(),
+            revisions: vec![],
         };
 
         let storage = MockStorage {
@@ -136,6 +226,90 @@ mod tests {
             selection: RefCell::new(None),
         };
 
-        show_command(&storage, &selection_ui, "test".to_string());
+        assert_eq!(
+            show_command(&storage, &selection_ui, None, "missing".to_string()),
+            Err(CommandError::NotFound)
+        );
+    }
+
+    #[test]
+    fn test_show_command_uses_formatter() {
+        let snippet = Snippet {
+            id: Snippet::new_id(),
+            name: "test".to_string(),
+            description: "desc".to_string(),
+            content: "echo hello".to_string(),
+            language: None,
+            executable: true,
+            tags: vec!["tag1".to_string()],
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+            revisions: vec![],
+        };
+
+        let storage = MockStorage {
+            snippets: vec![snippet.clone()],
+            should_fail: false,
+        };
+
+        let selection_ui = MockSelectionUI {
+            selection: RefCell::new(Some(snippet)),
+        };
+
+        let formatter = MockFormatter {
+            formatted: RefCell::new(None),
+        };
+
+        assert!(
+            show_command(
+                &storage,
+                &selection_ui,
+                Some(&formatter),
+                "test".to_string(),
+            )
+            .is_ok()
+        );
+
+        assert_eq!(formatter.formatted.borrow().as_deref(), Some("test"));
+    }
+
+    #[test]
+    fn test_show_command_exact_name_wins() {
+        let storage = MockStorage {
+            snippets: vec![named("deploy"), named("deploy-prod")],
+            should_fail: false,
+        };
+        let formatter = MockFormatter {
+            formatted: RefCell::new(None),
+        };
+
+        assert!(
+            show_command(
+                &storage,
+                &NonInteractiveSelectionUI,
+                Some(&formatter),
+                "Deploy".to_string()
+            )
+            .is_ok()
+        );
+        assert_eq!(formatter.formatted.borrow().as_deref(), Some("deploy"));
+    }
+
+    #[test]
+    fn test_show_command_ambiguous_without_terminal() {
+        let storage = MockStorage {
+            snippets: vec![named("deploy-dev"), named("deploy-prod")],
+            should_fail: false,
+        };
+
+        assert_eq!(
+            show_command(
+                &storage,
+                &NonInteractiveSelectionUI,
+                None,
+                "deploy".to_string()
+            ),
+            Err(CommandError::Ambiguous)
+        );
     }
 }
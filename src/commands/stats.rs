@@ -0,0 +1,178 @@
+use std::cmp::Reverse;
+
+use chrono::{DateTime, Duration, Local, Utc};
+
+use crate::{
+    commands::{
+        error::CommandResult,
+        helper::{load_store, load_usage},
+    },
+    models::Snippet,
+    storage::{
+        Storage,
+        usage::{Usage, UsageStats},
+    },
+};
+
+struct StatsReport {
+    most_used: Vec<(String, Usage)>,
+    never_used: Vec<String>,
+    stale: Vec<(String, Usage)>,
+}
+
+fn build_report(
+    snippets: &[Snippet],
+    usage: &UsageStats,
+    limit: usize,
+    stale_after: Duration,
+    now: DateTime<Utc>,
+) -> StatsReport {
+    let mut used: Vec<(String, Usage)> = vec![];
+    let mut never_used = vec![];
+    for snippet in snippets {
+        let usage = usage.get(&snippet.id);
+        if usage.total() == 0 {
+            never_used.push(snippet.name.clone());
+        } else {
+            used.push((snippet.name.clone(), usage));
+        }
+    }
+
+    let mut stale: Vec<(String, Usage)> = used
+        .iter()
+        .filter(|(_, usage)| usage.last_used.is_some_and(|t| now - t > stale_after))
+        .cloned()
+        .collect();
+    stale.sort_by_key(|(_, usage)| usage.last_used);
+
+    used.sort_by_key(|(_, usage)| Reverse(usage.total()));
+    used.truncate(limit);
+
+    StatsReport {
+        most_used: used,
+        never_used,
+        stale,
+    }
+}
+
+fn last_used(usage: &Usage) -> String {
+    match usage.last_used {
+        Some(t) => t.with_timezone(&Local).format("%Y-%m-%d").to_string(),
+        None => "never".to_string(),
+    }
+}
+
+pub fn stats_command(storage: &dyn Storage, limit: usize, stale_days: i64) -> CommandResult {
+    let store = load_store(storage)?;
+
+    if store.snippets.is_empty() {
+        println!("📭 No snippets found.");
+        return Ok(());
+    }
+
+    let usage = load_usage(storage);
+    let report = build_report(
+        &store.snippets,
+        &usage,
+        limit,
+        Duration::days(stale_days),
+        Utc::now(),
+    );
+
+    let width = store
+        .snippets
+        .iter()
+        .map(|s| s.name.len())
+        .max()
+        .unwrap_or(0);
+
+    if report.most_used.is_empty() {
+        println!("📭 No snippet has been used yet.");
+    } else {
+        println!("🔥 Most used:");
+        for (name, usage) in &report.most_used {
+            println!(
+                "  {:<width$}  {:>4} runs  {:>4} copies  {:>4} shows  last used {}",
+                name,
+                usage.runs,
+                usage.copies,
+                usage.shows,
+                last_used(usage),
+                width = width
+            );
+        }
+    }
+
+    if !report.never_used.is_empty() {
+        println!();
+        println!("💤 Never used ({}):", report.never_used.len());
+        for name in &report.never_used {
+            println!("  {}", name);
+        }
+    }
+
+    if !report.stale.is_empty() {
+        println!();
+        println!("🕸️ Not used in the last {} days:", stale_days);
+        for (name, usage) in &report.stale {
+            println!(
+                "  {:<width$}  last used {}",
+                name,
+                last_used(usage),
+                width = width
+            );
+        }
+    }
+
+    if !report.never_used.is_empty() || !report.stale.is_empty() {
+        println!();
+        println!("💡 Remove snippets you no longer need with `markit delete <name>`.");
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::usage::UsageKind;
+
+    fn snippet(name: &str) -> Snippet {
+        Snippet {
+            id: name.to_string(),
+            name: name.to_string(),
+            description: String::new(),
+            content: "ls".to_string(),
+            language: None,
+            executable: true,
+            tags: vec![],
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            revisions: vec![],
+        }
+    }
+
+    #[test]
+    fn test_report_groups_snippets() {
+        let now = Utc::now();
+        let mut usage = UsageStats::default();
+        usage.record("deploy", UsageKind::Run, now);
+        usage.record("deploy", UsageKind::Copy, now);
+        usage.record("backup", UsageKind::Show, now - Duration::days(60));
+        usage.record("logs", UsageKind::Run, now - Duration::days(40));
+        usage.record("deleted", UsageKind::Run, now);
+
+        let snippets = vec![
+            snippet("backup"),
+            snippet("deploy"),
+            snippet("logs"),
+            snippet("unused"),
+        ];
+        let report = build_report(&snippets, &usage, 2, Duration::days(30), now);
+
+        let most_used: Vec<&str> = report.most_used.iter().map(|(n, _)| n.as_str()).collect();
+        let stale: Vec<&str> = report.stale.iter().map(|(n, _)| n.as_str()).collect();
+        assert_eq!(most_used, vec!["deploy", "backup"]);
+        assert_eq!(report.never_used, vec!["unused"]);
+        assert_eq!(stale, vec!["backup", "logs"]);
+    }
+}
@@ -0,0 +1,165 @@
+use crate::{
+    commands::error::{CommandError, CommandResult},
+    storage::StoreRegistry,
+};
+
+pub fn list_stores_command(registry: &dyn StoreRegistry) -> CommandResult {
+    let stores = match registry.list_stores() {
+        Ok(s) => s,
+        Err(e) => {
+            eprintln!("⛔ Failed to list stores: {}", e);
+            return Err(CommandError::Storage);
+        }
+    };
+
+    let width = stores.iter().map(|s| s.name.len()).max().unwrap_or(0);
+
+    println!("📚 Stores:");
+    for store in &stores {
+        let marker = if store.is_default { "*" } else { " " };
+        let count = match store.snippet_count {
+            Some(n) => format!("{} snippets", n),
+            None => "unreadable".to_string(),
+        };
+        println!(
+            "  {} {:<width$}  {:>12}  {}",
+            marker,
+            store.name,
+            count,
+            store.path.display(),
+            width = width
+        );
+    }
+    Ok(())
+}
+
+pub fn create_store_command(registry: &dyn StoreRegistry, name: String) -> CommandResult {
+    match registry.create_store(&name) {
+        Ok(path) => {
+            println!("✅ Created store '{}' at {}", name, path.display());
+            println!(
+                "💡 Use it with `markit --store {} <command>` or `markit stores switch {}`.",
+                name, name
+            );
+            Ok(())
+        }
+        Err(e) => {
+            eprintln!("⛔ Failed to create store: {}", e);
+            Err(CommandError::Validation)
+        }
+    }
+}
+
+pub fn switch_store_command(registry: &dyn StoreRegistry, name: String) -> CommandResult {
+    let stores = match registry.list_stores() {
+        Ok(s) => s,
+        Err(e) => {
+            eprintln!("⛔ Failed to list stores: {}", e);
+            return Err(CommandError::Storage);
+        }
+    };
+
+    if !stores.iter().any(|s| s.name == name) {
+        eprintln!(
+            "⛔ Store '{}' does not exist. Create it with `markit stores create {}`.",
+            name, name
+        );
+        return Err(CommandError::NotFound);
+    }
+
+    match registry.set_default_store(&name) {
+        Ok(_) => {
+            println!("✅ Default store is now '{}'.", name);
+            Ok(())
+        }
+        Err(e) => {
+            eprintln!("⛔ Failed to switch store: {}", e);
+            Err(CommandError::Failed)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::{StorageError, stores::StoreInfo};
+    use std::{cell::RefCell, path::PathBuf};
+
+    struct MockRegistry {
+        stores: Vec<&'static str>,
+        created: RefCell<Option<String>>,
+        default_store: RefCell<Option<String>>,
+    }
+
+    impl MockRegistry {
+        fn new(stores: Vec<&'static str>) -> Self {
+            Self {
+                stores,
+                created: RefCell::new(None),
+                default_store: RefCell::new(None),
+            }
+        }
+    }
+
+    impl StoreRegistry for MockRegistry {
+        fn list_stores(&self) -> Result<Vec<StoreInfo>, StorageError> {
+            Ok(self
+                .stores
+                .iter()
+                .map(|name| StoreInfo {
+                    name: name.to_string(),
+                    path: PathBuf::from(name),
+                    snippet_count: Some(0),
+                    is_default: *name == "default",
+                })
+                .collect())
+        }
+
+        fn create_store(&self, name: &str) -> Result<PathBuf, StorageError> {
+            self.created.replace(Some(name.to_string()));
+            Ok(PathBuf::from(name))
+        }
+
+        fn set_default_store(&self, name: &str) -> Result<(), StorageError> {
+            self.default_store.replace(Some(name.to_string()));
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_list_stores() {
+        let registry = MockRegistry::new(vec!["default", "work"]);
+
+        assert!(list_stores_command(&registry).is_ok());
+    }
+
+    #[test]
+    fn test_create_store() {
+        let registry = MockRegistry::new(vec!["default"]);
+
+        assert!(create_store_command(&registry, "work".to_string()).is_ok());
+
+        assert_eq!(registry.created.borrow().as_deref(), Some("work"));
+    }
+
+    #[test]
+    fn test_switch_store() {
+        let registry = MockRegistry::new(vec!["default", "work"]);
+
+        assert!(switch_store_command(&registry, "work".to_string()).is_ok());
+
+        assert_eq!(registry.default_store.borrow().as_deref(), Some("work"));
+    }
+
+    #[test]
+    fn test_switch_to_missing_store() {
+        let registry = MockRegistry::new(vec!["default"]);
+
+        assert_eq!(
+            switch_store_command(&registry, "work".to_string()),
+            Err(CommandError::NotFound)
+        );
+
+        assert!(registry.default_store.borrow().is_none());
+    }
+}
@@ -0,0 +1,269 @@
+use chrono::{Duration, Local};
+
+use crate::{
+    commands::{
+        error::{CommandError, CommandResult},
+        helper::{load_store, lock_storage},
+    },
+    storage::Storage,
+    ui::ConfirmPrompt,
+};
+
+pub fn list_trash_command(storage: &dyn Storage, purge_after_days: u32) -> CommandResult {
+    let store = load_store(storage)?;
+
+    if store.trash.is_empty() {
+        println!("📭 The trash is empty.");
+        return Ok(());
+    }
+
+    let width = store
+        .trash
+        .iter()
+        .map(|t| t.snippet.name.len())
+        .max()
+        .unwrap_or(0);
+
+    println!("🗑️ Trash:");
+    for trashed in store.trash.iter().rev() {
+        let purge = if purge_after_days > 0 {
+            let purge_at = trashed.deleted_at + Duration::days(purge_after_days.into());
+            format!(
+                "  (purged after {})",
+                purge_at.with_timezone(&Local).format("%Y-%m-%d")
+            )
+        } else {
+            String::new()
+        };
+        println!(
+            "  {:<width$}  deleted {}{}",
+            trashed.snippet.name,
+            trashed
+                .deleted_at
+                .with_timezone(&Local)
+                .format("%Y-%m-%d %H:%M:%S"),
+            purge,
+            width = width
+        );
+    }
+    println!("💡 Bring one back with `markit undelete <name>`.");
+    Ok(())
+}
+
+/// Moves the most recently deleted snippet called `name` back into the store.
+pub fn undelete_command(storage: &dyn Storage, name: String) -> CommandResult {
+    let _lock = lock_storage(storage)?;
+
+    let mut store = load_store(storage)?;
+
+    if store
+        .snippets
+        .iter()
+        .any(|s| s.name.eq_ignore_ascii_case(&name))
+    {
+        eprintln!(
+            "⛔ A snippet with the name '{}' already exists. Rename it before restoring the deleted one.",
+            name
+        );
+        return Err(CommandError::Validation);
+    }
+
+    let Some(trashed) = store.take_from_trash(&name) else {
+        eprintln!(
+            "⛔ No snippet named '{}' in the trash. See `markit trash list`.",
+            name
+        );
+        return Err(CommandError::NotFound);
+    };
+
+    let restored = trashed.snippet.name.clone();
+    store.snippets.push(trashed.snippet);
+
+    if let Err(e) = storage.save_all(&store) {
+        eprintln!("⛔ Failed to restore snippet: {}", e);
+        return Err(CommandError::Storage);
+    }
+
+    println!("♻️ Snippet '{}' restored from the trash.", restored);
+    Ok(())
+}
+
+pub fn empty_trash_command(
+    storage: &dyn Storage,
+    confirm: &dyn ConfirmPrompt,
+    force: bool,
+) -> CommandResult {
+    let count = load_store(storage)?.trash.len();
+
+    if count == 0 {
+        println!("📭 The trash is empty.");
+        return Ok(());
+    }
+
+    if !force {
+        let prompt = format!(
+            "❗ Permanently delete {} snippet(s) in the trash? This cannot be undone.",
+            count
+        );
+        if !confirm.confirm(&prompt) {
+            println!("🚫 Emptying the trash cancelled.");
+            return Err(CommandError::Cancelled);
+        }
+    }
+
+    let _lock = lock_storage(storage)?;
+
+    let mut store = load_store(storage)?;
+    let removed = std::mem::take(&mut store.trash);
+
+    if let Err(e) = storage.save_all(&store) {
+        eprintln!("⛔ Failed to empty the trash: {}", e);
+        return Err(CommandError::Storage);
+    }
+
+    if let Err(e) = storage.update_usage(&mut |usage| {
+        for trashed in &removed {
+            usage.remove(&trashed.snippet.id);
+        }
+    }) {
+        eprintln!("⚠️ Failed to clear usage statistics: {}", e);
+    }
+
+    println!("🗑️ Permanently deleted {} snippet(s).", removed.len());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        models::{Snippet, SnippetStore},
+        storage::StorageError,
+    };
+    use chrono::Utc;
+    use std::cell::RefCell;
+
+    struct MockStorage {
+        store: RefCell<SnippetStore>,
+    }
+
+    impl Storage for MockStorage {
+        fn load(&self) -> Result<SnippetStore, StorageError> {
+            Ok(self.store.borrow().clone())
+        }
+
+        fn save(&self, _: Snippet) -> Result<(), StorageError> {
+            Ok(())
+        }
+
+        fn save_all(&self, store: &SnippetStore) -> Result<(), StorageError> {
+            self.store.replace(store.clone());
+            Ok(())
+        }
+
+        fn get_backups(&self) -> Result<Vec<std::path::PathBuf>, StorageError> {
+            Ok(vec![])
+        }
+
+        fn restore_backup(&self, _: &std::path::Path) -> Result<(), StorageError> {
+            Ok(())
+        }
+    }
+
+    struct MockConfirmPrompt {
+        answer: bool,
+    }
+
+    impl ConfirmPrompt for MockConfirmPrompt {
+        fn confirm(&self, _: &str) -> bool {
+            self.answer
+        }
+    }
+
+    fn snippet(name: &str, content: &str) -> Snippet {
+        Snippet {
+            id: Snippet::new_id(),
+            name: name.to_string(),
+            description: String::new(),
+            content: content.to_string(),
+            language: None,
+            executable: true,
+            tags: vec![],
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            revisions: vec![],
+        }
+    }
+
+    /// "deploy" was deleted twice; "backup" is live.
+    fn storage() -> MockStorage {
+        let mut store = SnippetStore {
+            snippets: vec![
+                snippet("deploy", "first"),
+                snippet("deploy", "second"),
+                snippet("backup", "tar"),
+            ],
+            trash: vec![],
+        };
+        for _ in 0..2 {
+            let id = store.snippets[0].id.clone();
+            store.move_to_trash(&id, Utc::now());
+        }
+        MockStorage {
+            store: RefCell::new(store),
+        }
+    }
+
+    #[test]
+    fn test_list_trash() {
+        assert!(list_trash_command(&storage(), 30).is_ok());
+    }
+
+    #[test]
+    fn test_undelete_restores_latest_deletion() {
+        let storage = storage();
+
+        assert!(undelete_command(&storage, "Deploy".to_string()).is_ok());
+
+        let store = storage.store.borrow();
+        let restored = store.snippets.iter().find(|s| s.name == "deploy").unwrap();
+        assert_eq!(restored.content, "second");
+        assert_eq!(store.trash.len(), 1);
+    }
+
+    #[test]
+    fn test_undelete_refuses_to_shadow_a_live_snippet() {
+        let storage = storage();
+        let id = storage.store.borrow().snippets[0].id.clone();
+        storage.store.borrow_mut().move_to_trash(&id, Utc::now());
+        storage
+            .store
+            .borrow_mut()
+            .snippets
+            .push(snippet("backup", "rsync"));
+
+        assert_eq!(
+            undelete_command(&storage, "backup".to_string()),
+            Err(CommandError::Validation)
+        );
+        assert_eq!(
+            undelete_command(&storage, "missing".to_string()),
+            Err(CommandError::NotFound)
+        );
+    }
+
+    #[test]
+    fn test_empty_trash() {
+        let storage = storage();
+
+        assert_eq!(
+            empty_trash_command(&storage, &MockConfirmPrompt { answer: false }, false),
+            Err(CommandError::Cancelled)
+        );
+        assert_eq!(storage.store.borrow().trash.len(), 2);
+
+        assert!(empty_trash_command(&storage, &MockConfirmPrompt { answer: false }, true).is_ok());
+        assert!(storage.store.borrow().trash.is_empty());
+        assert_eq!(storage.store.borrow().snippets.len(), 1);
+    }
+}
@@ -0,0 +1,271 @@
+use std::io::IsTerminal;
+
+use crate::{
+    clipboard_provider::ClipboardProvider,
+    command_runner::CommandRunner,
+    commands::{
+        copy, delete, edit,
+        error::{CommandError, CommandResult},
+        helper::{load_store, load_usage},
+        run,
+    },
+    file::EditorLauncher,
+    input::VariableInput,
+    models::Snippet,
+    storage::{HistoryLog, Storage},
+    tui::{self, ActionHandler, app::Action},
+    ui::{ConfirmPrompt, preselected::PreselectedSelection},
+};
+
+pub fn tui_command(
+    storage: &dyn Storage,
+    variable_input: &dyn VariableInput,
+    runner: &dyn CommandRunner,
+    history: &dyn HistoryLog,
+    clipboard: &mut dyn ClipboardProvider,
+    editor: &dyn EditorLauncher,
+    confirm: &dyn ConfirmPrompt,
+) -> CommandResult {
+    if !std::io::stdout().is_terminal() {
+        eprintln!("⛔ `markit ui` needs an interactive terminal.");
+        return Err(CommandError::Failed);
+    }
+
+    let snippets = by_frecency(storage, load_store(storage)?.snippets);
+
+    let mut handler = CommandActions {
+        storage,
+        variable_input,
+        runner,
+        history,
+        clipboard,
+        editor,
+        confirm,
+    };
+
+    if let Err(e) = tui::run(snippets, &mut handler) {
+        eprintln!("⛔ Terminal UI failed: {}", e);
+        return Err(CommandError::Failed);
+    }
+    Ok(())
+}
+
+fn by_frecency(storage: &dyn Storage, mut snippets: Vec<Snippet>) -> Vec<Snippet> {
+    load_usage(storage).sort_by_frecency(&mut snippets, |s| &s.id);
+    snippets
+}
+
+/// Runs TUI actions through the same command functions as the CLI.
+struct CommandActions<'a> {
+    storage: &'a dyn Storage,
+    variable_input: &'a dyn VariableInput,
+    runner: &'a dyn CommandRunner,
+    history: &'a dyn HistoryLog,
+    clipboard: &'a mut dyn ClipboardProvider,
+    editor: &'a dyn EditorLauncher,
+    confirm: &'a dyn ConfirmPrompt,
+}
+
+impl ActionHandler for CommandActions<'_> {
+    fn handle(&mut self, action: &Action) {
+        // Failures are already reported on screen; the UI keeps going.
+        let _ = match action {
+            Action::Run(name) => run::run_command(
+                self.storage,
+                &PreselectedSelection::new(name),
+                self.variable_input,
+                self.runner,
+                self.history,
+                name.clone(),
+                &run::RunOptions::default(),
+            ),
+            Action::Copy(name) => copy::copy_command(
+                self.storage,
+                &PreselectedSelection::new(name),
+                self.variable_input,
+                self.clipboard,
+                name.clone(),
+                &[],
+            ),
+            Action::Edit(name) => edit::edit_command(
+                self.storage,
+                &PreselectedSelection::new(name),
+                self.editor,
+                name.clone(),
+            ),
+            Action::Delete(name) => delete::delete_command(
+                self.storage,
+                &PreselectedSelection::new(name),
+                self.confirm,
+                name.clone(),
+                false,
+            ),
+            Action::Quit => Ok(()),
+        };
+    }
+
+    fn reload(&self) -> Option<Vec<Snippet>> {
+        load_store(self.storage)
+            .ok()
+            .map(|store| by_frecency(self.storage, store.snippets))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        command_runner::{ExecutionPlan, RunRequest},
+        models::{PartialSnippet, SnippetStore},
+        storage::{StorageError, history::HistoryEntry},
+    };
+    use chrono::Utc;
+    use std::{cell::RefCell, process::ExitStatus};
+
+    struct MockStorage {
+        snippets: RefCell<Vec<Snippet>>,
+    }
+
+    impl Storage for MockStorage {
+        fn load(&self) -> Result<SnippetStore, StorageError> {
+            Ok(SnippetStore {
+                snippets: self.snippets.borrow().clone(),
+                trash: vec![],
+            })
+        }
+
+        fn save(&self, _: Snippet) -> Result<(), StorageError> {
+            Ok(())
+        }
+
+        fn save_all(&self, store: &SnippetStore) -> Result<(), StorageError> {
+            self.snippets.replace(store.snippets.clone());
+            Ok(())
+        }
+
+        fn get_backups(&self) -> Result<Vec<std::path::PathBuf>, StorageError> {
+            Ok(vec![])
+        }
+
+        fn restore_backup(&self, _: &std::path::Path) -> Result<(), StorageError> {
+            Ok(())
+        }
+    }
+
+    struct MockVariableInput;
+
+    impl VariableInput for MockVariableInput {
+        fn get_value(&self, _: &str, default: Option<&str>) -> Option<String> {
+            default.map(str::to_string)
+        }
+    }
+
+    struct MockCommandRunner {
+        last_command: RefCell<Option<String>>,
+    }
+
+    impl CommandRunner for MockCommandRunner {
+        fn plan(&self, request: &RunRequest) -> Result<ExecutionPlan, String> {
+            Ok(ExecutionPlan {
+                program: "sh".to_string(),
+                args: vec!["-c".to_string(), request.command.clone()],
+                cwd: std::path::PathBuf::from("."),
+                env: Default::default(),
+                script: None,
+            })
+        }
+
+        fn execute(&self, plan: &ExecutionPlan) -> Result<ExitStatus, std::io::Error> {
+            self.last_command.replace(plan.args.last().cloned());
+            std::process::Command::new("true").status()
+        }
+    }
+
+    struct MockHistory;
+
+    impl HistoryLog for MockHistory {
+        fn append(&self, _: &HistoryEntry) -> Result<(), StorageError> {
+            Ok(())
+        }
+
+        fn entries(&self) -> Result<Vec<HistoryEntry>, StorageError> {
+            Ok(vec![])
+        }
+    }
+
+    struct MockClipboard {
+        text: Option<String>,
+    }
+
+    impl ClipboardProvider for MockClipboard {
+        fn set_text(&mut self, text: &str) -> Result<(), String> {
+            self.text = Some(text.to_string());
+            Ok(())
+        }
+    }
+
+    struct MockEditor;
+
+    impl EditorLauncher for MockEditor {
+        fn open_editor(&self, snippet: &PartialSnippet) -> Result<PartialSnippet, String> {
+            let mut edited = snippet.clone();
+            edited.description = "edited".to_string();
+            Ok(edited)
+        }
+    }
+
+    struct MockConfirmPrompt;
+
+    impl ConfirmPrompt for MockConfirmPrompt {
+        fn confirm(&self, _: &str) -> bool {
+            true
+        }
+    }
+
+    fn snippet(name: &str) -> Snippet {
+        Snippet {
+            id: Snippet::new_id(),
+            name: name.to_string(),
+            description: "desc".to_string(),
+            content: format!("echo {}", name),
+            language: None,
+            executable: true,
+            tags: vec![],
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            revisions: vec![],
+        }
+    }
+
+    #[test]
+    fn test_actions_use_the_chosen_snippet() {
+        let storage = MockStorage {
+            snippets: RefCell::new(vec![snippet("deploy"), snippet("deploy-prod")]),
+        };
+        let runner = MockCommandRunner {
+            last_command: RefCell::new(None),
+        };
+        let mut clipboard = MockClipboard { text: None };
+
+        let mut handler = CommandActions {
+            storage: &storage,
+            variable_input: &MockVariableInput,
+            runner: &runner,
+            history: &MockHistory,
+            clipboard: &mut clipboard,
+            editor: &MockEditor,
+            confirm: &MockConfirmPrompt,
+        };
+
+        handler.handle(&Action::Run("deploy".to_string()));
+        handler.handle(&Action::Copy("deploy-prod".to_string()));
+        handler.handle(&Action::Edit("deploy".to_string()));
+        handler.handle(&Action::Delete("deploy-prod".to_string()));
+        let reloaded = handler.reload().unwrap();
+
+        assert_eq!(runner.last_command.borrow().as_deref(), Some("echo deploy"));
+        assert_eq!(clipboard.text.as_deref(), Some("echo deploy-prod"));
+        assert_eq!(reloaded.len(), 1);
+        assert_eq!(reloaded[0].description, "edited");
+    }
+}
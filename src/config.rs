@@ -0,0 +1,520 @@
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_yaml::{Mapping, Value};
+
+use crate::storage::retention::RetentionPolicy;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub shell: ShellConfig,
+    pub interpreters: Interpreters,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub editor: Option<String>,
+    pub clipboard: ClipboardConfig,
+    pub table: TableConfig,
+    pub confirm: ConfirmConfig,
+    pub backups: RetentionPolicy,
+    pub trash: TrashConfig,
+    pub history: HistoryConfig,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default_store: Option<String>,
+}
+
+/// Shell used by `run` for snippets without a language. Defaults to `/bin/sh`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct ShellConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub program: Option<String>,
+    pub args: Vec<String>,
+}
+
+impl Default for ShellConfig {
+    fn default() -> Self {
+        Self {
+            program: None,
+            args: vec!["-c".to_string()],
+        }
+    }
+}
+
+/// Interpreter command per snippet language, e.g. `python: python3`.
+/// Configured entries are merged over the built-in ones; `null` drops an
+/// override.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(transparent)]
+pub struct Interpreters(BTreeMap<String, String>);
+
+impl Interpreters {
+    /// The interpreter for `language` split into program and arguments.
+    pub fn command(&self, language: &str) -> Option<Vec<String>> {
+        let command = self.0.get(&language.to_lowercase())?;
+        let parts: Vec<String> = command.split_whitespace().map(str::to_string).collect();
+        (!parts.is_empty()).then_some(parts)
+    }
+}
+
+impl Default for Interpreters {
+    fn default() -> Self {
+        Self(
+            [
+                ("sh", "sh"),
+                ("bash", "bash"),
+                ("zsh", "zsh"),
+                ("python", "python3"),
+                ("node", "node"),
+            ]
+            .into_iter()
+            .map(|(language, command)| (language.to_string(), command.to_string()))
+            .collect(),
+        )
+    }
+}
+
+impl<'de> Deserialize<'de> for Interpreters {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let configured = BTreeMap::<String, Option<String>>::deserialize(deserializer)?;
+        let mut interpreters = Self::default();
+        for (language, command) in configured {
+            if let Some(command) = command {
+                interpreters.0.insert(language.to_lowercase(), command);
+            }
+        }
+        Ok(interpreters)
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub enum ClipboardBackend {
+    Native,
+    WlCopy,
+    Xclip,
+    Xsel,
+    Pbcopy,
+}
+
+/// Clipboard providers to try, in order.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct ClipboardConfig {
+    pub providers: Vec<ClipboardBackend>,
+}
+
+impl Default for ClipboardConfig {
+    fn default() -> Self {
+        Self {
+            providers: vec![
+                ClipboardBackend::Native,
+                ClipboardBackend::WlCopy,
+                ClipboardBackend::Xclip,
+                ClipboardBackend::Xsel,
+            ],
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum TableColumn {
+    Name,
+    Description,
+    Executable,
+    CreatedAt,
+    UpdatedAt,
+    Tags,
+    Runs,
+    Copies,
+    Shows,
+    LastUsed,
+}
+
+/// Columns and colours of the `list` table. Colours are names such as
+/// `cyan` or hex values such as `#64ffff`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct TableConfig {
+    pub columns: Vec<TableColumn>,
+    pub header_color: String,
+    pub text_color: String,
+}
+
+impl Default for TableConfig {
+    fn default() -> Self {
+        Self {
+            columns: vec![
+                TableColumn::Name,
+                TableColumn::Description,
+                TableColumn::Executable,
+                TableColumn::CreatedAt,
+                TableColumn::UpdatedAt,
+                TableColumn::Tags,
+            ],
+            header_color: "#64ffff".to_string(),
+            text_color: "white".to_string(),
+        }
+    }
+}
+
+/// Answer preselected in confirmation prompts.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct ConfirmConfig {
+    pub default: bool,
+}
+
+/// How long deleted snippets stay in the trash.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct TrashConfig {
+    /// `0` keeps them until `markit trash empty`.
+    pub purge_after_days: u32,
+}
+
+impl Default for TrashConfig {
+    fn default() -> Self {
+        Self {
+            purge_after_days: 30,
+        }
+    }
+}
+
+/// How many runs `history.jsonl` keeps.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct HistoryConfig {
+    /// `0` keeps every run.
+    pub max_entries: usize,
+}
+
+impl Default for HistoryConfig {
+    fn default() -> Self {
+        Self { max_entries: 1000 }
+    }
+}
+
+pub trait ConfigStore {
+    fn path(&self) -> &Path;
+    fn load(&self) -> Result<Config, String>;
+    fn save(&self, config: &Config) -> Result<(), String>;
+}
+
+pub struct FileConfigStore {
+    path: PathBuf,
+}
+
+impl FileConfigStore {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+}
+
+impl ConfigStore for FileConfigStore {
+    fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn load(&self) -> Result<Config, String> {
+        Config::load(&self.path)
+    }
+
+    fn save(&self, config: &Config) -> Result<(), String> {
+        config.save(&self.path)
+    }
+}
+
+impl Config {
+    /// Loads the config at `path`, falling back to defaults when it does not exist.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let contents = fs::read_to_string(path)
+            .map_err(|e| format!("Could not read '{}': {}", path.display(), e))?;
+        let config: Self = serde_yaml::from_str(&contents)
+            .map_err(|e| format!("Invalid config '{}': {}", path.display(), e))?;
+        config
+            .validate()
+            .map_err(|e| format!("Invalid config '{}': {}", path.display(), e))?;
+        Ok(config)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("Could not create '{}': {}", parent.display(), e))?;
+        }
+
+        let contents = serde_yaml::to_string(self).map_err(|e| e.to_string())?;
+        fs::write(path, contents)
+            .map_err(|e| format!("Could not write '{}': {}", path.display(), e))
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        for color in [&self.table.header_color, &self.table.text_color] {
+            if crate::ui::cli_table::parse_color(color).is_none() {
+                return Err(format!("unknown colour '{}'", color));
+            }
+        }
+        Ok(())
+    }
+
+    /// Every set key as a dotted path with its value, e.g. `shell.args = -c`.
+    pub fn entries(&self) -> Vec<(String, String)> {
+        let mut entries = Vec::new();
+        flatten("", &self.to_value(), &mut entries);
+        entries
+    }
+
+    /// Value of a dotted key such as `backups.keep_last`, or `None` when the
+    /// key is valid but unset.
+    pub fn get(&self, key: &str) -> Result<Option<String>, String> {
+        match lookup(&self.to_value(), key) {
+            Some(value) => Ok(Some(display_value(value))),
+            None if Self::is_known_key(key) => Ok(None),
+            None => Err(format!("Unknown config key '{}'", key)),
+        }
+    }
+
+    /// Returns a copy of the config with `key` set to `raw`. Lists accept
+    /// comma-separated values and `null` resets a key to its default.
+    pub fn set(&self, key: &str, raw: &str) -> Result<Self, String> {
+        let mut root = self.to_value();
+        let current = lookup(&root, key);
+        if current.is_none() && !Self::is_known_key(key) {
+            return Err(format!("Unknown config key '{}'", key));
+        }
+
+        let value = match current {
+            _ if raw == "null" => lookup(&Self::default().to_value(), key)
+                .cloned()
+                .unwrap_or(Value::Null),
+            Some(Value::Sequence(_)) if !raw.trim_start().starts_with('[') => Value::Sequence(
+                raw.split(',')
+                    .map(|item| parse_scalar(item.trim()))
+                    .collect(),
+            ),
+            Some(Value::String(_)) => Value::String(raw.to_string()),
+            _ => parse_scalar(raw),
+        };
+
+        if !insert(&mut root, key, value) {
+            return Err(format!("Cannot set '{}'", key));
+        }
+
+        let config: Self = serde_yaml::from_value(root)
+            .map_err(|e| format!("Invalid value for '{}': {}", key, e))?;
+        config
+            .validate()
+            .map_err(|e| format!("Invalid value for '{}': {}", key, e))?;
+        Ok(config)
+    }
+
+    /// Whether `key` names a setting, including new entries in open maps
+    /// such as `interpreters.<language>`.
+    fn is_known_key(key: &str) -> bool {
+        [Value::Null, Value::String(String::new())]
+            .into_iter()
+            .any(|probe| {
+                let mut root = Self::default().to_value();
+                insert(&mut root, key, probe) && serde_yaml::from_value::<Self>(root).is_ok()
+            })
+    }
+
+    fn to_value(&self) -> Value {
+        serde_yaml::to_value(self).unwrap_or(Value::Null)
+    }
+}
+
+fn parse_scalar(raw: &str) -> Value {
+    serde_yaml::from_str(raw).unwrap_or_else(|_| Value::String(raw.to_string()))
+}
+
+fn lookup<'a>(root: &'a Value, key: &str) -> Option<&'a Value> {
+    key.split('.')
+        .try_fold(root, |value, part| value.as_mapping()?.get(part))
+}
+
+fn insert(root: &mut Value, key: &str, value: Value) -> bool {
+    let parts: Vec<&str> = key.split('.').collect();
+    let Some((last, parents)) = parts.split_last() else {
+        return false;
+    };
+
+    let mut current = root;
+    for part in parents {
+        let Some(mapping) = current.as_mapping_mut() else {
+            return false;
+        };
+        current = mapping
+            .entry(Value::String(part.to_string()))
+            .or_insert_with(|| Value::Mapping(Mapping::new()));
+    }
+
+    match current.as_mapping_mut() {
+        Some(mapping) if !last.is_empty() => {
+            mapping.insert(Value::String(last.to_string()), value);
+            true
+        }
+        _ => false,
+    }
+}
+
+fn flatten(prefix: &str, value: &Value, entries: &mut Vec<(String, String)>) {
+    match value {
+        Value::Mapping(mapping) => {
+            for (key, child) in mapping {
+                let key = key.as_str().unwrap_or_default();
+                let path = if prefix.is_empty() {
+                    key.to_string()
+                } else {
+                    format!("{}.{}", prefix, key)
+                };
+                flatten(&path, child, entries);
+            }
+        }
+        other => entries.push((prefix.to_string(), display_value(other))),
+    }
+}
+
+fn display_value(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::Bool(b) => b.to_string(),
+        Value::Number(n) => n.to_string(),
+        Value::String(s) => s.clone(),
+        Value::Sequence(items) => items
+            .iter()
+            .map(display_value)
+            .collect::<Vec<_>>()
+            .join(","),
+        other => serde_yaml::to_string(other)
+            .unwrap_or_default()
+            .trim_end()
+            .to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_values() {
+        let config = Config::default();
+
+        assert_eq!(
+            config.get("backups.keep_last").unwrap().as_deref(),
+            Some("20")
+        );
+        assert_eq!(config.get("shell.args").unwrap().as_deref(), Some("-c"));
+        assert_eq!(config.get("editor").unwrap(), None);
+        assert!(config.get("nope").is_err());
+        assert!(config.get("backups.nope").is_err());
+    }
+
+    #[test]
+    fn test_set_values() {
+        let config = Config::default()
+            .set("shell.program", "/bin/zsh")
+            .unwrap()
+            .set("table.columns", "name, tags")
+            .unwrap()
+            .set("confirm.default", "true")
+            .unwrap()
+            .set("clipboard.providers", "xclip,native")
+            .unwrap();
+
+        assert_eq!(config.shell.program.as_deref(), Some("/bin/zsh"));
+        assert_eq!(
+            config.table.columns,
+            vec![TableColumn::Name, TableColumn::Tags]
+        );
+        assert!(config.confirm.default);
+        assert_eq!(
+            config.clipboard.providers,
+            vec![ClipboardBackend::Xclip, ClipboardBackend::Native]
+        );
+
+        let reset = config
+            .set("shell.program", "null")
+            .unwrap()
+            .set("table.columns", "null")
+            .unwrap();
+        assert_eq!(reset.shell.program, None);
+        assert_eq!(reset.table.columns, TableConfig::default().columns);
+    }
+
+    #[test]
+    fn test_set_rejects_invalid_values() {
+        let config = Config::default();
+
+        assert!(config.set("backups.keep_last", "many").is_err());
+        assert!(config.set("table.columns", "name,colour").is_err());
+        assert!(config.set("table.header_color", "sparkly").is_err());
+        assert!(config.set("shell.nope", "x").is_err());
+        assert!(config.set("backups.keep_last.x", "1").is_err());
+    }
+
+    #[test]
+    fn test_load_rejects_unknown_keys() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.yml");
+        fs::write(&path, "edtior: vim\n").unwrap();
+
+        assert!(Config::load(&path).is_err());
+    }
+
+    #[test]
+    fn test_entries_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.yml");
+        let config = Config::default().set("editor", "code --wait").unwrap();
+
+        config.save(&path).unwrap();
+        let loaded = Config::load(&path).unwrap();
+
+        assert_eq!(loaded, config);
+        assert!(
+            loaded
+                .entries()
+                .contains(&("editor".to_string(), "code --wait".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_interpreters_merge_with_defaults() {
+        let config = Config::default()
+            .set("interpreters.ruby", "ruby -w")
+            .unwrap()
+            .set("interpreters.python", "python3.12")
+            .unwrap();
+
+        assert_eq!(
+            config.interpreters.command("Ruby"),
+            Some(vec!["ruby".to_string(), "-w".to_string()])
+        );
+        assert_eq!(
+            config.interpreters.command("python"),
+            Some(vec!["python3.12".to_string()])
+        );
+        assert_eq!(
+            config.interpreters.command("node"),
+            Some(vec!["node".to_string()])
+        );
+        assert_eq!(config.interpreters.command("cobol"), None);
+
+        let reset = config.set("interpreters.python", "null").unwrap();
+        assert_eq!(
+            reset.interpreters.command("python"),
+            Some(vec!["python3".to_string()])
+        );
+    }
+}
@@ -0,0 +1,345 @@
+use chrono::Utc;
+use similar::{ChangeTag, TextDiff};
+
+use crate::models::{Snippet, SnippetStore};
+
+/// A difference between the current store and a target store, described from
+/// the point of view of moving to the target.
+#[derive(Debug, Clone)]
+pub enum SnippetChange {
+    Added(Snippet),
+    Removed(Snippet),
+    Modified { current: Snippet, target: Snippet },
+}
+
+impl SnippetChange {
+    pub fn name(&self) -> &str {
+        match self {
+            SnippetChange::Added(s) | SnippetChange::Removed(s) => &s.name,
+            SnippetChange::Modified { current, .. } => &current.name,
+        }
+    }
+
+    pub fn label(&self) -> String {
+        match self {
+            SnippetChange::Added(s) => format!("+ {} (will be added)", s.name),
+            SnippetChange::Removed(s) => format!("- {} (will be removed)", s.name),
+            SnippetChange::Modified { current, target } if current.name != target.name => {
+                format!(
+                    "~ {} (will be changed and renamed to {})",
+                    current.name, target.name
+                )
+            }
+            SnippetChange::Modified { current, .. } => {
+                format!("~ {} (will be changed)", current.name)
+            }
+        }
+    }
+}
+
+pub fn diff_stores(current: &SnippetStore, target: &SnippetStore) -> Vec<SnippetChange> {
+    let mut changes = Vec::new();
+
+    for snippet in &current.snippets {
+        match target.snippets.iter().find(|t| same_identity(snippet, t)) {
+            None => changes.push(SnippetChange::Removed(snippet.clone())),
+            Some(t) if !same_snippet(snippet, t) || snippet.name != t.name => {
+                changes.push(SnippetChange::Modified {
+                    current: snippet.clone(),
+                    target: t.clone(),
+                })
+            }
+            Some(_) => {}
+        }
+    }
+
+    for snippet in &target.snippets {
+        if !current.snippets.iter().any(|c| same_identity(c, snippet)) {
+            changes.push(SnippetChange::Added(snippet.clone()));
+        }
+    }
+
+    changes
+}
+
+/// Applies `changes` to `store`, moving those snippets to their target state.
+/// Changed snippets keep their current id and revisions, and the version they
+/// replace is kept as a revision. Added ones get a new id if theirs is
+/// missing, as in backups taken before ids existed, or already in use.
+pub fn apply_changes(store: &mut SnippetStore, changes: &[SnippetChange]) {
+    for change in changes {
+        match change {
+            SnippetChange::Added(snippet) => {
+                let mut snippet = snippet.clone();
+                let trashed = store.trash.iter().map(|t| &t.snippet);
+                if snippet.id.is_empty()
+                    || store
+                        .snippets
+                        .iter()
+                        .chain(trashed)
+                        .any(|s| s.id == snippet.id)
+                {
+                    snippet.id = Snippet::new_id();
+                }
+                store.snippets.push(snippet);
+            }
+            SnippetChange::Removed(snippet) => {
+                store.snippets.retain(|s| !same_identity(s, snippet))
+            }
+            SnippetChange::Modified { current, target } => {
+                if let Some(existing) = store
+                    .snippets
+                    .iter_mut()
+                    .find(|s| same_identity(s, current))
+                {
+                    let previous = existing.clone();
+                    existing.name = target.name.clone();
+                    existing.description = target.description.clone();
+                    existing.content = target.content.clone();
+                    existing.language = target.language.clone();
+                    existing.executable = target.executable;
+                    existing.tags = target.tags.clone();
+                    existing.updated_at = Utc::now();
+                    existing.keep_revision(&previous);
+                }
+            }
+        }
+    }
+}
+
+/// Line-by-line description of how `current` differs from `target`.
+pub fn describe_modification(current: &Snippet, target: &Snippet) -> Vec<String> {
+    let mut lines = Vec::new();
+
+    if current.name != target.name {
+        lines.push(format!("name: '{}' → '{}'", current.name, target.name));
+    }
+    if current.description != target.description {
+        lines.push(format!(
+            "description: '{}' → '{}'",
+            current.description, target.description
+        ));
+    }
+    if current.language != target.language {
+        let show = |language: &Option<String>| language.clone().unwrap_or_else(|| "shell".into());
+        lines.push(format!(
+            "language: {} → {}",
+            show(&current.language),
+            show(&target.language)
+        ));
+    }
+    if current.executable != target.executable {
+        lines.push(format!(
+            "executable: {} → {}",
+            current.executable, target.executable
+        ));
+    }
+    if current.tags != target.tags {
+        lines.push(format!(
+            "tags: [{}] → [{}]",
+            current.tags.join(", "),
+            target.tags.join(", ")
+        ));
+    }
+    if current.content != target.content {
+        lines.push("content:".to_string());
+        lines.extend(content_diff(&current.content, &target.content));
+    }
+
+    lines
+}
+
+pub fn content_diff(old: &str, new: &str) -> Vec<String> {
+    TextDiff::from_lines(old, new)
+        .iter_all_changes()
+        .map(|change| {
+            let sign = match change.tag() {
+                ChangeTag::Delete => "-",
+                ChangeTag::Insert => "+",
+                ChangeTag::Equal => " ",
+            };
+            format!("{}{}", sign, change.value().trim_end_matches('\n'))
+        })
+        .collect()
+}
+
+/// Names of the fields that differ between `a` and `b`, ignoring the name
+/// and timestamps.
+pub fn changed_fields(a: &Snippet, b: &Snippet) -> Vec<&'static str> {
+    [
+        ("description", a.description != b.description),
+        ("content", a.content != b.content),
+        ("language", a.language != b.language),
+        ("executable", a.executable != b.executable),
+        ("tags", a.tags != b.tags),
+    ]
+    .into_iter()
+    .filter(|(_, changed)| *changed)
+    .map(|(field, _)| field)
+    .collect()
+}
+
+/// Snippets are matched by id. Files written before snippets had ids, such
+/// as old backups, fall back to the name.
+pub fn same_identity(a: &Snippet, b: &Snippet) -> bool {
+    if a.id.is_empty() || b.id.is_empty() {
+        a.name == b.name
+    } else {
+        a.id == b.id
+    }
+}
+
+pub fn same_snippet(a: &Snippet, b: &Snippet) -> bool {
+    changed_fields(a, b).is_empty()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn snippet(name: &str, content: &str) -> Snippet {
+        Snippet {
+            id: format!("id-{}", name),
+            name: name.to_string(),
+            description: "desc".to_string(),
+            content: content.to_string(),
+            language: None,
+            executable: true,
+            tags: vec![],
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            revisions: vec![],
+        }
+    }
+
+    #[test]
+    fn test_diff_stores() {
+        let current = SnippetStore {
+            snippets: vec![snippet("a", "echo a"), snippet("b", "echo b")],
+            trash: vec![],
+        };
+        let target = SnippetStore {
+            snippets: vec![snippet("b", "echo B"), snippet("c", "echo c")],
+            trash: vec![],
+        };
+
+        let changes = diff_stores(&current, &target);
+        let labels: Vec<String> = changes.iter().map(|c| c.label()).collect();
+
+        assert_eq!(
+            labels,
+            vec![
+                "- a (will be removed)",
+                "~ b (will be changed)",
+                "+ c (will be added)"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_apply_changes_reaches_target() {
+        let mut current = SnippetStore {
+            snippets: vec![snippet("a", "echo a"), snippet("b", "echo b")],
+            trash: vec![],
+        };
+        let target = SnippetStore {
+            snippets: vec![snippet("b", "echo B"), snippet("c", "echo c")],
+            trash: vec![],
+        };
+
+        let changes = diff_stores(&current, &target);
+        apply_changes(&mut current, &changes);
+
+        assert!(diff_stores(&current, &target).is_empty());
+    }
+
+    #[test]
+    fn test_renamed_snippet_is_a_modification() {
+        let current = SnippetStore {
+            snippets: vec![snippet("a", "echo a")],
+            trash: vec![],
+        };
+        let mut renamed = snippet("a", "echo a");
+        renamed.name = "b".to_string();
+        let target = SnippetStore {
+            snippets: vec![renamed],
+            trash: vec![],
+        };
+
+        let changes = diff_stores(&current, &target);
+
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].label(), "~ a (will be changed and renamed to b)");
+    }
+
+    #[test]
+    fn test_apply_changes_from_backup_without_ids_keeps_ids() {
+        let mut current = SnippetStore {
+            snippets: vec![snippet("a", "echo a")],
+            trash: vec![],
+        };
+        let mut old_a = snippet("a", "echo old");
+        let mut old_b = snippet("b", "echo b");
+        old_a.id.clear();
+        old_b.id.clear();
+        let backup = SnippetStore {
+            snippets: vec![old_a, old_b],
+            trash: vec![],
+        };
+
+        let changes = diff_stores(&current, &backup);
+        apply_changes(&mut current, &changes);
+
+        assert_eq!(current.snippets[0].id, "id-a");
+        assert_eq!(current.snippets[0].content, "echo old");
+        assert_eq!(current.snippets[1].name, "b");
+        assert!(!current.snippets[1].id.is_empty());
+    }
+
+    #[test]
+    fn test_apply_changes_keeps_replaced_version_as_revision() {
+        let mut edited = snippet("a", "echo v2");
+        edited.keep_revision(&snippet("a", "echo v1"));
+        let mut current = SnippetStore {
+            snippets: vec![edited],
+            trash: vec![],
+        };
+        let backup = SnippetStore {
+            snippets: vec![snippet("a", "echo v1")],
+            trash: vec![],
+        };
+
+        let changes = diff_stores(&current, &backup);
+        apply_changes(&mut current, &changes);
+
+        let restored = &current.snippets[0];
+        assert_eq!(restored.content, "echo v1");
+        let kept: Vec<(u32, &str)> = restored
+            .revisions
+            .iter()
+            .map(|r| (r.number, r.content.as_str()))
+            .collect();
+        assert_eq!(kept, vec![(1, "echo v1"), (2, "echo v2")]);
+    }
+
+    #[test]
+    fn test_added_snippet_with_an_id_in_use_gets_a_new_one() {
+        let mut current = SnippetStore::default();
+        current.snippets.push(snippet("a", "echo a"));
+        current.move_to_trash("id-a", Utc::now());
+
+        let changes = vec![SnippetChange::Added(snippet("a", "echo a"))];
+        apply_changes(&mut current, &changes);
+
+        assert_ne!(current.snippets[0].id, "id-a");
+    }
+
+    #[test]
+    fn test_content_diff() {
+        assert_eq!(
+            content_diff("one\ntwo\n", "one\nthree\n"),
+            vec![" one", "-two", "+three"]
+        );
+    }
+}
@@ -0,0 +1,162 @@
+use std::collections::HashSet;
+
+use chrono::Utc;
+use clap::ValueEnum;
+
+use crate::{
+    models::{Snippet, SnippetStore},
+    placeholder::is_valid_name,
+};
+
+pub mod navi;
+pub mod pet;
+
+/// Another snippet tool whose files `import --from` converts.
+#[derive(Clone, Copy, Debug, PartialEq, ValueEnum)]
+pub enum ImportSource {
+    /// navi `.cheat` files, or a directory of them.
+    Navi,
+    /// pet's `snippet.toml`.
+    Pet,
+}
+
+/// Snippets converted from another tool, and what could not be mapped.
+#[derive(Debug, Default)]
+pub struct Conversion {
+    pub store: SnippetStore,
+    pub unmapped: Vec<String>,
+}
+
+impl Conversion {
+    /// Adds an executable snippet named after its description, or its command
+    /// if there is none. The id is left empty for the import to assign.
+    fn push(&mut self, description: &str, command: &str, tags: Vec<String>) {
+        let base = slug(description)
+            .or_else(|| slug(command.lines().next().unwrap_or_default()))
+            .unwrap_or_else(|| "snippet".to_string());
+        let taken: HashSet<&str> = self
+            .store
+            .snippets
+            .iter()
+            .map(|s| s.name.as_str())
+            .collect();
+        let name = std::iter::once(base.clone())
+            .chain((2..).map(|n| format!("{}-{}", base, n)))
+            .find(|candidate| !taken.contains(candidate.as_str()))
+            .unwrap_or(base);
+
+        let now = Utc::now();
+        self.store.snippets.push(Snippet {
+            id: String::new(),
+            name,
+            description: description.to_string(),
+            content: command.to_string(),
+            language: None,
+            executable: true,
+            tags,
+            created_at: now,
+            updated_at: now,
+            revisions: vec![],
+        });
+    }
+}
+
+const MAX_NAME_LEN: usize = 40;
+
+/// Lowercase words joined by dashes, e.g. "Change branch" → "change-branch".
+fn slug(text: &str) -> Option<String> {
+    let words: Vec<String> = text
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .map(str::to_lowercase)
+        .collect();
+
+    let mut slug = String::new();
+    for word in words {
+        if !slug.is_empty() && slug.len() + word.len() + 1 > MAX_NAME_LEN {
+            break;
+        }
+        if !slug.is_empty() {
+            slug.push('-');
+        }
+        slug.push_str(&word);
+    }
+    (!slug.is_empty()).then_some(slug)
+}
+
+/// Turns `<name>` and `<name=default>` into `{{name}}` and
+/// `{{name:default}}`. Anything else between angle brackets, such as a
+/// redirect, is left alone.
+fn angle_placeholders(command: &str) -> String {
+    let mut result = String::with_capacity(command.len());
+    let mut rest = command;
+
+    while let Some(open) = rest.find('<') {
+        result.push_str(&rest[..open]);
+        let after = &rest[open + 1..];
+        let inner = after.find('>').map(|close| &after[..close]);
+
+        let placeholder = inner.and_then(|inner| {
+            let (name, default) = match inner.split_once('=') {
+                Some((name, default)) => (name, Some(default)),
+                None => (inner, None),
+            };
+            is_valid_name(name).then(|| match default {
+                Some(default) => format!("{{{{{}:{}}}}}", name, default),
+                None => format!("{{{{{}}}}}", name),
+            })
+        });
+
+        match (placeholder, inner) {
+            (Some(placeholder), Some(inner)) => {
+                result.push_str(&placeholder);
+                rest = &after[inner.len() + 1..];
+            }
+            _ => {
+                result.push('<');
+                rest = after;
+            }
+        }
+    }
+
+    result.push_str(rest);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_angle_placeholders() {
+        assert_eq!(
+            angle_placeholders("git checkout <branch> && ping <host=8.8.8.8>"),
+            "git checkout {{branch}} && ping {{host:8.8.8.8}}"
+        );
+        assert_eq!(
+            angle_placeholders("sort < in.txt > out.txt; diff <(ls a) <(ls b)"),
+            "sort < in.txt > out.txt; diff <(ls a) <(ls b)"
+        );
+    }
+
+    #[test]
+    fn test_names_come_from_descriptions() {
+        let mut conversion = Conversion::default();
+        conversion.push("Change branch!", "git checkout x", vec![]);
+        conversion.push("change branch", "git switch x", vec![]);
+        conversion.push("", "docker ps -a", vec![]);
+
+        let names: Vec<&str> = conversion
+            .store
+            .snippets
+            .iter()
+            .map(|s| s.name.as_str())
+            .collect();
+        assert_eq!(
+            names,
+            vec!["change-branch", "change-branch-2", "docker-ps-a"]
+        );
+        assert!(conversion.store.snippets[0].executable);
+        assert!(conversion.store.snippets[0].id.is_empty());
+    }
+}
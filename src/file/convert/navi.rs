@@ -0,0 +1,123 @@
+use crate::file::convert::{Conversion, angle_placeholders};
+
+/// Adds the commands of one navi cheat sheet. `%` lines set the tags of the
+/// commands after them, a `#` line describes the next command and `<var>`
+/// becomes a `{{var}}` placeholder. `$ var: ...` generators and `@` extends
+/// have no markit equivalent and are reported instead.
+pub fn convert(conversion: &mut Conversion, file: &str, text: &str) {
+    let mut tags: Vec<String> = vec![];
+    let mut description = String::new();
+    let mut command: Vec<&str> = vec![];
+
+    for (number, line) in text.lines().enumerate() {
+        let line = line.trim_end();
+        let trimmed = line.trim_start();
+
+        if trimmed.is_empty() {
+            finish(conversion, &mut description, &mut command, &tags);
+        } else if let Some(rest) = trimmed.strip_prefix('%') {
+            finish(conversion, &mut description, &mut command, &tags);
+            tags = rest
+                .split(',')
+                .map(|t| t.trim().to_string())
+                .filter(|t| !t.is_empty())
+                .collect();
+        } else if let Some(rest) = trimmed.strip_prefix('#') {
+            finish(conversion, &mut description, &mut command, &tags);
+            description = rest.trim().to_string();
+        } else if trimmed.starts_with(';') {
+            continue;
+        } else if let Some(rest) = trimmed.strip_prefix('$') {
+            finish(conversion, &mut description, &mut command, &tags);
+            let (variable, generator) = rest.split_once(':').unwrap_or((rest, ""));
+            conversion.unmapped.push(format!(
+                "{}:{}: values of '{}' come from `{}`; it is asked for instead",
+                file,
+                number + 1,
+                variable.trim(),
+                generator.trim()
+            ));
+        } else if trimmed.starts_with('@') {
+            finish(conversion, &mut description, &mut command, &tags);
+            conversion.unmapped.push(format!(
+                "{}:{}: `{}` extends another cheat sheet, which is not supported",
+                file,
+                number + 1,
+                trimmed
+            ));
+        } else {
+            command.push(line);
+        }
+    }
+    finish(conversion, &mut description, &mut command, &tags);
+}
+
+/// Adds the command collected so far, if any.
+fn finish(
+    conversion: &mut Conversion,
+    description: &mut String,
+    command: &mut Vec<&str>,
+    tags: &[String],
+) {
+    if !command.is_empty() {
+        let content = angle_placeholders(&command.join("\n"));
+        conversion.push(description, &content, tags.to_vec());
+        command.clear();
+        description.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CHEAT: &str = "\
+% git, code
+
+# Change branch
+git checkout <branch>
+
+$ branch: git branch | awk '{print $NF}'
+
+; a comment
+# Show the log
+git log \\
+  --oneline
+
+% docker
+@ git
+
+# Remove stopped containers
+docker container prune -f
+";
+
+    #[test]
+    fn test_convert_cheat_sheet() {
+        let mut conversion = Conversion::default();
+        convert(&mut conversion, "git.cheat", CHEAT);
+
+        let snippets = &conversion.store.snippets;
+        let summary: Vec<(&str, &str, &str)> = snippets
+            .iter()
+            .map(|s| (s.name.as_str(), s.description.as_str(), s.content.as_str()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("change-branch", "Change branch", "git checkout {{branch}}"),
+                ("show-the-log", "Show the log", "git log \\\n  --oneline"),
+                (
+                    "remove-stopped-containers",
+                    "Remove stopped containers",
+                    "docker container prune -f"
+                ),
+            ]
+        );
+        assert_eq!(snippets[0].tags, vec!["git", "code"]);
+        assert_eq!(snippets[2].tags, vec!["docker"]);
+
+        assert_eq!(conversion.unmapped.len(), 2);
+        assert!(conversion.unmapped[0].starts_with("git.cheat:6: values of 'branch'"));
+        assert!(conversion.unmapped[1].starts_with("git.cheat:14: `@ git`"));
+    }
+}
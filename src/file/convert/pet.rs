@@ -0,0 +1,146 @@
+use serde::Deserialize;
+
+use crate::file::convert::{Conversion, angle_placeholders};
+
+#[derive(Deserialize)]
+struct PetFile {
+    #[serde(default)]
+    snippets: Vec<PetSnippet>,
+}
+
+#[derive(Deserialize)]
+struct PetSnippet {
+    #[serde(default)]
+    description: String,
+    #[serde(default)]
+    command: String,
+    #[serde(default)]
+    tag: Vec<String>,
+    #[serde(default)]
+    output: String,
+}
+
+/// Converts the `[[snippets]]` of a pet `snippet.toml`. `<param>` and
+/// `<param=default>` become placeholders; of a `|_a_||_b_|` choice only the
+/// first value is kept as the default. Sample output has nowhere to go and
+/// is reported.
+pub fn convert(file: &str, text: &str) -> Result<Conversion, toml::de::Error> {
+    let pet: PetFile = toml::from_str(text)?;
+    let mut conversion = Conversion::default();
+
+    for (number, snippet) in pet.snippets.into_iter().enumerate() {
+        let label = if snippet.description.is_empty() {
+            format!("{}: snippet #{}", file, number + 1)
+        } else {
+            format!("{}: '{}'", file, snippet.description)
+        };
+
+        if snippet.command.trim().is_empty() {
+            conversion
+                .unmapped
+                .push(format!("{} has no command and was left out", label));
+            continue;
+        }
+
+        let (command, choices) = first_choices(&snippet.command);
+        if choices {
+            conversion.unmapped.push(format!(
+                "{} offers a choice of values; only the first is kept as the default",
+                label
+            ));
+        }
+        if !snippet.output.trim().is_empty() {
+            conversion
+                .unmapped
+                .push(format!("{} has sample output, which is not kept", label));
+        }
+
+        conversion.push(
+            &snippet.description,
+            &angle_placeholders(&command),
+            snippet.tag,
+        );
+    }
+    Ok(conversion)
+}
+
+/// Replaces every `|_a_||_b_|` choice list with its first value, and tells
+/// whether there was one.
+fn first_choices(command: &str) -> (String, bool) {
+    let mut result = String::with_capacity(command.len());
+    let mut rest = command;
+    let mut found = false;
+
+    while let Some(start) = rest.find("|_") {
+        let Some(end) = rest[start..].find("_|") else {
+            break;
+        };
+        result.push_str(&rest[..start]);
+        result.push_str(&rest[start + 2..start + end]);
+        found = true;
+
+        rest = &rest[start + end + 2..];
+        while let Some(next) = rest.strip_prefix("|_") {
+            match next.find("_|") {
+                Some(close) => rest = &next[close + 2..],
+                None => break,
+            }
+        }
+    }
+
+    result.push_str(rest);
+    (result, found)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SNIPPETS: &str = r#"
+[[snippets]]
+  description = "Ping a host"
+  command = "ping -c <count=3> <host>"
+  tag = ["network"]
+  output = ""
+
+[[snippets]]
+  description = "Pick a log level"
+  command = "export LOG=<level=|_info_||_debug_|>"
+  tag = []
+  output = "(nothing)"
+
+[[snippets]]
+  description = "Empty"
+  command = ""
+"#;
+
+    #[test]
+    fn test_convert_snippet_toml() {
+        let conversion = convert("snippet.toml", SNIPPETS).unwrap();
+
+        let snippets = &conversion.store.snippets;
+        assert_eq!(snippets.len(), 2);
+        assert_eq!(snippets[0].name, "ping-a-host");
+        assert_eq!(snippets[0].content, "ping -c {{count:3}} {{host}}");
+        assert_eq!(snippets[0].tags, vec!["network"]);
+        assert_eq!(snippets[1].content, "export LOG={{level:info}}");
+
+        assert_eq!(
+            conversion.unmapped,
+            vec![
+                "snippet.toml: 'Pick a log level' offers a choice of values; only the first is kept as the default",
+                "snippet.toml: 'Pick a log level' has sample output, which is not kept",
+                "snippet.toml: 'Empty' has no command and was left out",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_first_choices() {
+        assert_eq!(
+            first_choices("a <x=|_1_||_2_||_3_|> b"),
+            ("a <x=1> b".to_string(), true)
+        );
+        assert_eq!(first_choices("echo a|b"), ("echo a|b".to_string(), false));
+    }
+}
@@ -1,7 +1,20 @@
 use crate::{file::EditorLauncher, models::PartialSnippet};
 use std::io::Write;
 
-pub struct Editor;
+pub struct Editor {
+    command: String,
+}
+
+impl Editor {
+    /// Uses `command` if given, then `$EDITOR`, then `vim`. The command may
+    /// include arguments, e.g. `code --wait`.
+    pub fn new(command: Option<String>) -> Self {
+        let command = command
+            .or_else(|| std::env::var("EDITOR").ok())
+            .unwrap_or_else(|| "vim".to_string());
+        Self { command }
+    }
+}
 
 impl EditorLauncher for Editor {
     fn open_editor(&self, snippet: &PartialSnippet) -> Result<PartialSnippet, String> {
@@ -15,9 +28,11 @@ impl EditorLauncher for Editor {
             .write_all(yaml.as_bytes())
             .map_err(|e| format!("Could not write to temp file: {}", e))?;
 
-        let editor = std::env::var("EDITOR").unwrap_or_else(|_| "vim".to_string());
+        let mut parts = self.command.split_whitespace();
+        let program = parts.next().unwrap_or("vim");
 
-        let status = std::process::Command::new(editor)
+        let status = std::process::Command::new(program)
+            .args(parts)
             .arg(tmpfile.path())
             .status()
             .map_err(|e| format!("Failed to launch editor: {}", e))?;
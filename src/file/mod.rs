@@ -1,18 +1,78 @@
+use std::path::Path;
+
+use clap::ValueEnum;
+
+use crate::file::convert::{Conversion, ImportSource};
 use crate::models::{PartialSnippet, SnippetStore};
 use crate::storage::StorageError;
 
+pub mod convert;
 pub mod editor;
 pub mod reader;
 pub mod writer;
 
+/// Path that reads from stdin or writes to stdout instead of a file.
+pub const STDIO_PATH: &str = "-";
+
+/// Format of an import or export file.
+#[derive(Clone, Copy, Debug, Default, PartialEq, ValueEnum)]
+pub enum FileFormat {
+    #[default]
+    Yaml,
+    Json,
+    Toml,
+}
+
+impl FileFormat {
+    /// Picks the format from the file extension. Anything unknown, including
+    /// stdin and stdout, is YAML.
+    pub fn detect(path: &str) -> Self {
+        let extension = Path::new(path)
+            .extension()
+            .map(|e| e.to_string_lossy().to_lowercase());
+        match extension.as_deref() {
+            Some("json") => FileFormat::Json,
+            Some("toml") => FileFormat::Toml,
+            _ => FileFormat::Yaml,
+        }
+    }
+}
+
 pub trait EditorLauncher {
     fn open_editor(&self, snippet: &PartialSnippet) -> Result<PartialSnippet, String>;
 }
 
 pub trait FileWriter {
-    fn write_yaml(&self, path: &str, store: &SnippetStore) -> Result<(), String>;
+    fn write(
+        &self,
+        path: &str,
+        format: FileFormat,
+        document: &serde_yaml::Value,
+    ) -> Result<(), String>;
 }
 
 pub trait FileReader {
-    fn read_yaml(&self, path: &str) -> Result<SnippetStore, StorageError>;
+    fn read(&self, path: &str, format: FileFormat) -> Result<SnippetStore, StorageError>;
+
+    /// Reads another tool's snippets from `path`, a file or, for navi, a
+    /// directory of cheat sheets.
+    fn convert(&self, path: &str, source: ImportSource) -> Result<Conversion, StorageError>;
+
+    fn read_yaml(&self, path: &str) -> Result<SnippetStore, StorageError> {
+        self.read(path, FileFormat::Yaml)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_format() {
+        assert_eq!(FileFormat::detect("snippets.json"), FileFormat::Json);
+        assert_eq!(FileFormat::detect("team/Snippets.TOML"), FileFormat::Toml);
+        assert_eq!(FileFormat::detect("snippets.yml"), FileFormat::Yaml);
+        assert_eq!(FileFormat::detect("snippets"), FileFormat::Yaml);
+        assert_eq!(FileFormat::detect(STDIO_PATH), FileFormat::Yaml);
+    }
 }
@@ -1,12 +1,167 @@
-use crate::{file::FileReader, models::SnippetStore, storage::StorageError};
-use std::{fs::File, path::Path};
+use crate::{
+    file::{
+        FileFormat, FileReader, STDIO_PATH,
+        convert::{Conversion, ImportSource, navi, pet},
+    },
+    models::SnippetStore,
+    storage::StorageError,
+};
+use std::{
+    fs,
+    io::{self, Read},
+    path::{Path, PathBuf},
+};
 
 pub struct Reader;
 
 impl FileReader for Reader {
-    fn read_yaml(&self, path: &str) -> Result<SnippetStore, StorageError> {
-        let file = File::open(Path::new(path)).map_err(StorageError::Io)?;
-        let store = serde_yaml::from_reader(file).map_err(StorageError::Serde)?;
-        Ok(store)
+    fn read(&self, path: &str, format: FileFormat) -> Result<SnippetStore, StorageError> {
+        let path = Path::new(path);
+        let text = read_text(path)?;
+
+        match format {
+            FileFormat::Yaml => {
+                serde_yaml::from_str(&text).map_err(|e| StorageError::corrupt(path, e))
+            }
+            FileFormat::Json => serde_json::from_str(&text).map_err(|e| {
+                let message = e.to_string();
+                let message = message
+                    .strip_suffix(&format!(" at line {} column {}", e.line(), e.column()))
+                    .unwrap_or(&message);
+                corrupt_at(path, Some((e.line(), e.column())), message)
+            }),
+            FileFormat::Toml => toml::from_str(&text).map_err(|e| toml_error(path, &text, e)),
+        }
+    }
+
+    fn convert(&self, path: &str, source: ImportSource) -> Result<Conversion, StorageError> {
+        let path = Path::new(path);
+        match source {
+            ImportSource::Navi => {
+                let mut conversion = Conversion::default();
+                for file in cheat_files(path)? {
+                    let text = read_text(&file)?;
+                    navi::convert(&mut conversion, &file.display().to_string(), &text);
+                }
+                Ok(conversion)
+            }
+            ImportSource::Pet => {
+                let text = read_text(path)?;
+                pet::convert(&path.display().to_string(), &text)
+                    .map_err(|e| toml_error(path, &text, e))
+            }
+        }
+    }
+}
+
+fn read_text(path: &Path) -> Result<String, StorageError> {
+    if path == Path::new(STDIO_PATH) {
+        let mut text = String::new();
+        io::stdin().lock().read_to_string(&mut text)?;
+        return Ok(text);
+    }
+    Ok(fs::read_to_string(path)?)
+}
+
+/// `path` itself, or every `.cheat` file below it, in name order. Symlinked
+/// directories are not followed, so a link back up the tree cannot loop.
+fn cheat_files(path: &Path) -> Result<Vec<PathBuf>, StorageError> {
+    if !path.is_dir() {
+        return Ok(vec![path.to_path_buf()]);
+    }
+
+    let mut files = vec![];
+    let mut dirs = vec![path.to_path_buf()];
+    while let Some(dir) = dirs.pop() {
+        for entry in fs::read_dir(&dir)? {
+            let entry = entry?;
+            let file_type = entry.file_type()?;
+            let path = entry.path();
+            if file_type.is_dir() {
+                dirs.push(path);
+            } else if path.extension().is_some_and(|e| e == "cheat") && path.is_file() {
+                files.push(path);
+            }
+        }
+    }
+
+    if files.is_empty() {
+        return Err(StorageError::Io(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("no .cheat files in '{}'", path.display()),
+        )));
+    }
+    files.sort();
+    Ok(files)
+}
+
+fn toml_error(path: &Path, text: &str, error: toml::de::Error) -> StorageError {
+    let location = error.span().map(|span| line_and_column(text, span.start));
+    corrupt_at(path, location, error.message())
+}
+
+fn corrupt_at(path: &Path, location: Option<(usize, usize)>, message: &str) -> StorageError {
+    StorageError::Corrupt {
+        path: path.to_path_buf(),
+        line: location.map(|(line, _)| line),
+        column: location.map(|(_, column)| column),
+        message: message.to_string(),
+    }
+}
+
+/// 1-based line and column of the byte `offset` in `text`.
+fn line_and_column(text: &str, offset: usize) -> (usize, usize) {
+    let before = &text[..offset.min(text.len())];
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+    (
+        before.matches('\n').count() + 1,
+        before[line_start..].chars().count() + 1,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_errors_point_at_the_line() {
+        let dir = tempfile::tempdir().unwrap();
+        for (file, format, text) in [
+            (
+                "s.json",
+                FileFormat::Json,
+                "{\n  \"snippets\": [\n    oops\n  ]\n}",
+            ),
+            (
+                "s.toml",
+                FileFormat::Toml,
+                "[[snippets]]\nname = \"a\"\n= 1\n",
+            ),
+        ] {
+            let path = dir.path().join(file);
+            fs::write(&path, text).unwrap();
+
+            match Reader.read(path.to_str().unwrap(), format) {
+                Err(StorageError::Corrupt { line, .. }) => assert_eq!(line, Some(3), "{file}"),
+                other => panic!("expected a corrupt {file}, got {other:?}"),
+            }
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_cheat_files_skip_symlinked_directories() {
+        let dir = tempfile::tempdir().unwrap();
+        let nested = dir.path().join("git");
+        fs::create_dir(&nested).unwrap();
+        fs::write(nested.join("git.cheat"), "git status\n").unwrap();
+        std::os::unix::fs::symlink(dir.path(), nested.join("loop")).unwrap();
+        std::os::unix::fs::symlink(nested.join("git.cheat"), dir.path().join("link.cheat"))
+            .unwrap();
+
+        assert_eq!(
+            cheat_files(dir.path()).unwrap(),
+            vec![nested.join("git.cheat"), dir.path().join("link.cheat")]
+        );
     }
 }
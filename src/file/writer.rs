@@ -1,13 +1,93 @@
-use crate::{file::FileWriter, models::SnippetStore};
-use std::{fs::File, path::Path};
+use crate::file::{FileFormat, FileWriter, STDIO_PATH};
+use std::{
+    fs,
+    io::{self, Write},
+};
 
 pub struct Writer;
 
 impl FileWriter for Writer {
-    fn write_yaml(&self, path: &str, store: &SnippetStore) -> Result<(), String> {
-        match File::create(Path::new(path)) {
-            Ok(file) => serde_yaml::to_writer(file, store).map_err(|e| e.to_string()),
-            Err(err) => Err(err.to_string()),
+    fn write(
+        &self,
+        path: &str,
+        format: FileFormat,
+        document: &serde_yaml::Value,
+    ) -> Result<(), String> {
+        let text = match format {
+            FileFormat::Yaml => serde_yaml::to_string(document).map_err(|e| e.to_string())?,
+            FileFormat::Json => serde_json::to_string_pretty(document)
+                .map(|json| json + "\n")
+                .map_err(|e| e.to_string())?,
+            FileFormat::Toml => toml::to_string_pretty(document).map_err(|e| e.to_string())?,
+        };
+
+        if path == STDIO_PATH {
+            return io::stdout()
+                .lock()
+                .write_all(text.as_bytes())
+                .map_err(|e| e.to_string());
+        }
+        fs::write(path, text).map_err(|e| e.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        file::{FileReader, reader::Reader},
+        models::{Snippet, SnippetStore},
+    };
+    use chrono::Utc;
+
+    fn store() -> SnippetStore {
+        let mut snippet = Snippet {
+            id: Snippet::new_id(),
+            name: "deploy".to_string(),
+            description: "Deploy the app".to_string(),
+            content: "echo \"deploy\"\n./deploy.sh".to_string(),
+            language: Some("bash".to_string()),
+            executable: true,
+            tags: vec!["ops".to_string()],
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            revisions: vec![],
+        };
+        let previous = snippet.clone();
+        snippet.content = "./deploy.sh --prod".to_string();
+        snippet.language = None;
+        snippet.keep_revision(&previous);
+
+        SnippetStore {
+            snippets: vec![snippet],
+            trash: vec![],
+        }
+    }
+
+    #[test]
+    fn test_every_format_round_trips() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = store();
+        let document = serde_yaml::to_value(&store).unwrap();
+
+        for (file, format) in [
+            ("s.yml", FileFormat::Yaml),
+            ("s.json", FileFormat::Json),
+            ("s.toml", FileFormat::Toml),
+        ] {
+            let path = dir.path().join(file);
+            let path = path.to_str().unwrap();
+
+            Writer.write(path, format, &document).unwrap();
+            let read = Reader.read(path, format).unwrap();
+
+            assert_eq!(read.snippets.len(), 1, "{file}");
+            let (read, original) = (&read.snippets[0], &store.snippets[0]);
+            assert_eq!(read.id, original.id, "{file}");
+            assert_eq!(read.content, original.content, "{file}");
+            assert_eq!(read.language, original.language, "{file}");
+            assert_eq!(read.updated_at, original.updated_at, "{file}");
+            assert_eq!(read.revisions, original.revisions, "{file}");
         }
     }
 }
@@ -0,0 +1,23 @@
+use crate::input::{HistoryInput, SaveInput, cli_save::CliSaveInput};
+use std::io::{self, Write};
+
+pub struct CliHistoryInput;
+
+impl HistoryInput for CliHistoryInput {
+    fn get_name(&self) -> String {
+        print!("🏷️  Enter name (empty to skip): ");
+        io::stdout().flush().unwrap();
+
+        let mut name = String::new();
+        io::stdin().read_line(&mut name).unwrap();
+        name.trim().to_string()
+    }
+
+    fn get_description(&self) -> String {
+        CliSaveInput.get_description()
+    }
+
+    fn get_tags(&self) -> Vec<String> {
+        CliSaveInput.get_tags()
+    }
+}
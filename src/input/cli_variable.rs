@@ -0,0 +1,29 @@
+use crate::input::VariableInput;
+use std::io::{self, IsTerminal, Write};
+
+pub struct CliVariableInput;
+
+impl VariableInput for CliVariableInput {
+    fn get_value(&self, name: &str, default: Option<&str>) -> Option<String> {
+        if !io::stdin().is_terminal() {
+            return default.map(str::to_string);
+        }
+
+        match default {
+            Some(default) => print!("🧩 Enter value for '{}' [{}]: ", name, default),
+            None => print!("🧩 Enter value for '{}': ", name),
+        }
+        io::stdout().flush().ok()?;
+
+        let mut value = String::new();
+        if io::stdin().read_line(&mut value).ok()? == 0 {
+            return None;
+        }
+
+        let value = value.trim_end_matches(['\r', '\n']);
+        match (value.is_empty(), default) {
+            (true, Some(default)) => Some(default.to_string()),
+            _ => Some(value.to_string()),
+        }
+    }
+}
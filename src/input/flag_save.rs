@@ -0,0 +1,152 @@
+use crate::input::{SaveInput, cli_save::CliSaveInput};
+use std::io::{self, IsTerminal, Read};
+
+/// Save input driven by command-line flags, a file or piped stdin. Fields
+/// that were not supplied are prompted for when attached to a terminal and
+/// left empty otherwise.
+pub struct FlagSaveInput {
+    description: Option<String>,
+    tags: Option<Vec<String>>,
+    executable: Option<bool>,
+    content: Option<String>,
+    language: Option<String>,
+    interactive: bool,
+}
+
+impl FlagSaveInput {
+    pub fn new(
+        description: Option<String>,
+        tags: Vec<String>,
+        executable: Option<bool>,
+        content: Option<String>,
+        from_file: Option<String>,
+        language: Option<String>,
+    ) -> Result<Self, String> {
+        let interactive = io::stdin().is_terminal();
+
+        let content = match (content, from_file) {
+            (Some(content), _) => Some(content),
+            (None, Some(path)) => Some(
+                std::fs::read_to_string(&path)
+                    .map_err(|e| format!("Could not read '{}': {}", path, e))?,
+            ),
+            (None, None) if !interactive => {
+                let mut buffer = String::new();
+                io::stdin()
+                    .read_to_string(&mut buffer)
+                    .map_err(|e| format!("Could not read stdin: {}", e))?;
+                Some(buffer)
+            }
+            (None, None) => None,
+        };
+
+        let tags: Vec<String> = tags
+            .into_iter()
+            .map(|t| t.trim().to_string())
+            .filter(|t| !t.is_empty())
+            .collect();
+
+        Ok(Self {
+            description,
+            tags: (!tags.is_empty()).then_some(tags),
+            executable,
+            content,
+            language: language.map(|l| l.trim().to_lowercase()),
+            interactive,
+        })
+    }
+}
+
+impl SaveInput for FlagSaveInput {
+    fn get_description(&self) -> String {
+        match &self.description {
+            Some(description) => description.clone(),
+            None if self.interactive => CliSaveInput.get_description(),
+            None => String::new(),
+        }
+    }
+
+    fn get_executable(&self) -> bool {
+        match self.executable {
+            Some(executable) => executable,
+            None if self.interactive => CliSaveInput.get_executable(),
+            None => false,
+        }
+    }
+
+    fn get_content(&self) -> String {
+        match &self.content {
+            Some(content) => content.clone(),
+            None if self.interactive => CliSaveInput.get_content(),
+            None => String::new(),
+        }
+    }
+
+    fn get_tags(&self) -> Vec<String> {
+        match &self.tags {
+            Some(tags) => tags.clone(),
+            None if self.interactive => CliSaveInput.get_tags(),
+            None => vec![],
+        }
+    }
+
+    fn get_language(&self) -> Option<String> {
+        self.language.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    #[test]
+    fn test_flag_save_input_uses_flags() {
+        let input = FlagSaveInput::new(
+            Some("List pods".to_string()),
+            vec!["k8s".to_string(), " ".to_string()],
+            Some(true),
+            Some("kubectl get pods".to_string()),
+            None,
+            None,
+        )
+        .unwrap();
+
+        assert_eq!(input.get_description(), "List pods");
+        assert_eq!(input.get_tags(), vec!["k8s"]);
+        assert!(input.get_executable());
+        assert_eq!(input.get_content(), "kubectl get pods");
+    }
+
+    #[test]
+    fn test_flag_save_input_reads_file() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(b"echo from file\n").unwrap();
+
+        let input = FlagSaveInput::new(
+            None,
+            vec![],
+            Some(false),
+            None,
+            Some(file.path().to_string_lossy().to_string()),
+            None,
+        )
+        .unwrap();
+
+        assert_eq!(input.get_content(), "echo from file\n");
+    }
+
+    #[test]
+    fn test_flag_save_input_missing_file() {
+        let result = FlagSaveInput::new(
+            None,
+            vec![],
+            None,
+            None,
+            Some("/nonexistent/markit/snippet.sh".to_string()),
+            None,
+        );
+
+        assert!(result.is_err());
+    }
+}
@@ -1,8 +1,26 @@
+pub mod cli_history;
 pub mod cli_save;
+pub mod cli_variable;
+pub mod flag_save;
 
 pub trait SaveInput {
     fn get_description(&self) -> String;
     fn get_executable(&self) -> bool;
     fn get_content(&self) -> String;
     fn get_tags(&self) -> Vec<String>;
+    fn get_language(&self) -> Option<String> {
+        None
+    }
+}
+
+/// Details for a snippet saved from a shell history command.
+pub trait HistoryInput {
+    /// An empty name skips the command.
+    fn get_name(&self) -> String;
+    fn get_description(&self) -> String;
+    fn get_tags(&self) -> Vec<String>;
+}
+
+pub trait VariableInput {
+    fn get_value(&self, name: &str, default: Option<&str>) -> Option<String>;
 }
@@ -2,73 +2,395 @@ mod cli;
 mod clipboard_provider;
 mod command_runner;
 mod commands;
+mod config;
+mod diff;
 mod file;
 mod input;
 mod models;
+mod paths;
+mod placeholder;
+mod shell_history;
 mod storage;
+mod tui;
 mod ui;
 
 use clap::Parser;
-use cli::{Cli, Commands};
+use cli::{BackupsAction, Cli, Commands, ConfigAction, StoresAction, TrashAction};
+use std::io::IsTerminal;
 
 use crate::{
     clipboard_provider::SmartClipboard,
     command_runner::ShellCommandRunner,
-    commands::{copy, delete, edit, export, import, list, restore, run, save, show},
-    file::{editor::Editor, reader::Reader, writer::Writer},
-    input::cli_save::CliSaveInput,
-    storage::file_storage::FileStorage,
-    ui::{cli_confirm::DialoguerConfirm, cli_selection::CliSelection, cli_table::CliTable},
+    commands::{
+        backups, copy, delete, doctor, edit,
+        error::{CommandError, CommandResult},
+        export, history, import, import_history, list, restore, revisions, run, save, search, show,
+        stats, stores, trash,
+    },
+    config::{Config, FileConfigStore},
+    file::{STDIO_PATH, editor::Editor, reader::Reader, writer::Writer},
+    input::{
+        cli_history::CliHistoryInput, cli_save::CliSaveInput, cli_variable::CliVariableInput,
+        flag_save::FlagSaveInput,
+    },
+    paths::{DEFAULT_STORE, Paths},
+    shell_history::HistoryFiles,
+    storage::{
+        file_storage::FileStorage, history::FileHistoryLog, retention::RetentionPolicy,
+        stores::FileStoreRegistry,
+    },
+    ui::{
+        OutputFormat, OutputFormatter, cli_confirm::DialoguerConfirm, cli_formatter::CliFormatter,
+        cli_selection::CliSelection, cli_table::CliTable,
+    },
 };
 
 fn main() {
+    if let Err(e) = run_cli() {
+        std::process::exit(e.exit_code());
+    }
+}
+
+fn run_cli() -> CommandResult {
     let args = Cli::parse();
-    let storage = FileStorage::new();
+    let paths = Paths::resolve();
+
+    if let Commands::Config { action } = &args.command {
+        let config_store = FileConfigStore::new(paths.config_file());
+        return match action {
+            ConfigAction::List => commands::config::config_list_command(&config_store),
+            ConfigAction::Get { key } => {
+                commands::config::config_get_command(&config_store, key.clone())
+            }
+            ConfigAction::Set { key, value } => {
+                commands::config::config_set_command(&config_store, key.clone(), value.clone())
+            }
+        };
+    }
+
+    let config = Config::load(&paths.config_file()).unwrap_or_else(|e| {
+        eprintln!("⚠️ {}, using defaults.", e);
+        Config::default()
+    });
+
+    if let Commands::Stores { action } = &args.command {
+        let registry = FileStoreRegistry::new(paths, config.default_store);
+        return match action {
+            StoresAction::List => stores::list_stores_command(&registry),
+            StoresAction::Create { name } => stores::create_store_command(&registry, name.clone()),
+            StoresAction::Switch { name } => stores::switch_store_command(&registry, name.clone()),
+        };
+    }
+
+    let store = args
+        .store
+        .as_deref()
+        .or(config.default_store.as_deref())
+        .unwrap_or(DEFAULT_STORE);
+    let store_dir = match paths.store_dir(store) {
+        Ok(dir) => dir,
+        Err(e) => {
+            eprintln!("⛔ {}", e);
+            return Err(CommandError::Validation);
+        }
+    };
+    let storage = FileStorage::with_base_path(store_dir)
+        .with_retention(config.backups.clone())
+        .with_trash_purge(config.trash.purge_after_days);
 
     match args.command {
-        Commands::Save { name } => {
-            let input = CliSaveInput;
-            save::save_command(&storage, &input, name);
+        Commands::Save {
+            name,
+            description,
+            tags,
+            executable,
+            content,
+            from_file,
+            language,
+            from_history,
+        } => {
+            if from_history {
+                let selection_ui = CliSelection::new();
+                let content =
+                    import_history::pick_from_history(&HistoryFiles::detect(), &selection_ui)?;
+                let executable = Some(executable.unwrap_or(true));
+                return match FlagSaveInput::new(
+                    description,
+                    tags,
+                    executable,
+                    Some(content),
+                    None,
+                    language,
+                ) {
+                    Ok(input) => save::save_command(&storage, &input, name),
+                    Err(e) => {
+                        eprintln!("⛔ {}", e);
+                        Err(CommandError::Validation)
+                    }
+                };
+            }
+
+            let scripted = description.is_some()
+                || !tags.is_empty()
+                || executable.is_some()
+                || content.is_some()
+                || from_file.is_some()
+                || language.is_some()
+                || !std::io::stdin().is_terminal();
+
+            if !scripted {
+                let input = CliSaveInput;
+                return save::save_command(&storage, &input, name);
+            }
+
+            match FlagSaveInput::new(description, tags, executable, content, from_file, language) {
+                Ok(input) => save::save_command(&storage, &input, name),
+                Err(e) => {
+                    eprintln!("⛔ {}", e);
+                    Err(CommandError::Validation)
+                }
+            }
         }
-        Commands::Run { name } => {
+        Commands::Run {
+            name,
+            vars,
+            dry_run,
+            format,
+            env,
+            cwd,
+            args,
+        } => {
             let selection_ui = CliSelection::new();
-            let runner = ShellCommandRunner;
-            run::run_command(&storage, &selection_ui, &runner, name);
+            let variable_input = CliVariableInput;
+            let runner = ShellCommandRunner::new(&config.shell, &config.interpreters);
+            let history = FileHistoryLog::new(paths.history_file())
+                .with_max_entries(config.history.max_entries);
+            run::run_command(
+                &storage,
+                &selection_ui,
+                &variable_input,
+                &runner,
+                &history,
+                name,
+                &run::RunOptions {
+                    vars,
+                    dry_run,
+                    format,
+                    args,
+                    env,
+                    cwd,
+                },
+            )
+        }
+        Commands::List { tag, sort, format } => {
+            let mut cli_table = CliTable::new(&config.table);
+            let formatter = formatter_for(format);
+            list::list_command(&storage, &mut cli_table, formatter.as_deref(), tag, sort)
         }
-        Commands::List { tag } => {
-            let mut cli_table = CliTable::new();
-            list::list_command(&storage, &mut cli_table, tag);
+        Commands::Search { query } => {
+            let mut cli_table = CliTable::new(&config.table);
+            search::search_command(&storage, &mut cli_table, query)
         }
-        Commands::Show { name } => {
+        Commands::Show { name, format } => {
             let selection_ui = CliSelection::new();
-            show::show_command(&storage, &selection_ui, name);
+            let formatter = formatter_for(format);
+            show::show_command(&storage, &selection_ui, formatter.as_deref(), name)
         }
-        Commands::Copy { name } => {
+        Commands::Copy { name, vars } => {
             let selection_ui = CliSelection::new();
-            let mut clipboard = SmartClipboard::new();
-            copy::copy_command(&storage, &selection_ui, &mut clipboard, name);
+            let variable_input = CliVariableInput;
+            let mut clipboard = SmartClipboard::new(&config.clipboard.providers);
+            copy::copy_command(
+                &storage,
+                &selection_ui,
+                &variable_input,
+                &mut clipboard,
+                name,
+                &vars,
+            )
         }
         Commands::Delete { name, force } => {
             let selection_ui = CliSelection::new();
-            let confirm_prompt = DialoguerConfirm;
-            delete::delete_command(&storage, &selection_ui, &confirm_prompt, name, force);
+            let confirm_prompt = DialoguerConfirm::new(&config.confirm);
+            delete::delete_command(&storage, &selection_ui, &confirm_prompt, name, force)
         }
+        Commands::Undelete { name } => trash::undelete_command(&storage, name),
+        Commands::Trash { action } => match action {
+            TrashAction::List => trash::list_trash_command(&storage, config.trash.purge_after_days),
+            TrashAction::Empty { force } => {
+                let confirm_prompt = DialoguerConfirm::new(&config.confirm);
+                trash::empty_trash_command(&storage, &confirm_prompt, force)
+            }
+        },
         Commands::Edit { name } => {
             let selection_ui = CliSelection::new();
-            let editor = Editor;
-            edit::edit_command(&storage, &selection_ui, &editor, name);
+            let editor = Editor::new(config.editor.clone());
+            edit::edit_command(&storage, &selection_ui, &editor, name)
+        }
+        Commands::Log { name } => {
+            let selection_ui = CliSelection::new();
+            revisions::log_command(&storage, &selection_ui, name)
         }
-        Commands::Export { path } => {
+        Commands::Diff { name, from, to } => {
+            let selection_ui = CliSelection::new();
+            revisions::diff_command(&storage, &selection_ui, name, from, to)
+        }
+        Commands::Revert { name, revision } => {
+            let selection_ui = CliSelection::new();
+            revisions::revert_command(&storage, &selection_ui, name, revision)
+        }
+        Commands::Export {
+            path,
+            names,
+            tags,
+            exclude_tags,
+            search,
+            strip_timestamps,
+            stdout: _,
+            format,
+        } => {
             let writer = Writer;
-            export::export_command(&storage, &writer, &path);
+            let path = path.unwrap_or_else(|| STDIO_PATH.to_string());
+            export::export_command(
+                &storage,
+                &writer,
+                &path,
+                &export::ExportOptions {
+                    names,
+                    tags,
+                    exclude_tags,
+                    search,
+                    strip_timestamps,
+                    format,
+                },
+            )
         }
-        Commands::Import { path } => {
+        Commands::Import {
+            path,
+            on_conflict,
+            dry_run,
+            format,
+            from,
+        } => {
             let reader = Reader;
-            import::import_command(&storage, &reader, &path);
+            let confirm_prompt = DialoguerConfirm::new(&config.confirm);
+            import::import_command(
+                &storage,
+                &reader,
+                &confirm_prompt,
+                &path,
+                &import::ImportOptions {
+                    on_conflict,
+                    dry_run,
+                    format,
+                    from,
+                },
+            )
         }
-        Commands::Restore => {
+        Commands::ImportHistory { query, limit } => {
             let selection_ui = CliSelection::new();
-            restore::restore_command(&storage, &selection_ui);
+            import_history::import_history_command(
+                &storage,
+                &HistoryFiles::detect(),
+                &selection_ui,
+                &CliHistoryInput,
+                query,
+                limit,
+            )
+        }
+        Commands::Restore { select } => {
+            let selection_ui = CliSelection::new();
+            let confirm_prompt = DialoguerConfirm::new(&config.confirm);
+            let reader = Reader;
+            restore::restore_command(&storage, &selection_ui, &confirm_prompt, &reader, select)
+        }
+        Commands::Ui => {
+            let variable_input = CliVariableInput;
+            let runner = ShellCommandRunner::new(&config.shell, &config.interpreters);
+            let history = FileHistoryLog::new(paths.history_file())
+                .with_max_entries(config.history.max_entries);
+            let mut clipboard = SmartClipboard::new(&config.clipboard.providers);
+            let editor = Editor::new(config.editor.clone());
+            let confirm_prompt = DialoguerConfirm::new(&config.confirm);
+            commands::tui::tui_command(
+                &storage,
+                &variable_input,
+                &runner,
+                &history,
+                &mut clipboard,
+                &editor,
+                &confirm_prompt,
+            )
         }
+        Commands::History {
+            snippet,
+            status,
+            since,
+            until,
+            limit,
+            format,
+        } => {
+            let history = FileHistoryLog::new(paths.history_file())
+                .with_max_entries(config.history.max_entries);
+            let mut cli_table = CliTable::new(&config.table);
+            let filter = history::HistoryFilter {
+                snippet,
+                status,
+                since,
+                until,
+                limit,
+            };
+            history::history_command(&storage, &history, &mut cli_table, &filter, format)
+        }
+        Commands::Rerun { number } => {
+            let history = FileHistoryLog::new(paths.history_file())
+                .with_max_entries(config.history.max_entries);
+            let runner = ShellCommandRunner::new(&config.shell, &config.interpreters);
+            history::rerun_command(&history, &runner, number)
+        }
+        Commands::Stats { limit, stale_days } => stats::stats_command(&storage, limit, stale_days),
+        Commands::Doctor { repair } => {
+            let confirm_prompt = DialoguerConfirm::new(&config.confirm);
+            let reader = Reader;
+            doctor::doctor_command(&storage, &reader, &confirm_prompt, repair)
+        }
+        Commands::Backups { action } => match action {
+            BackupsAction::List => {
+                let mut cli_table = CliTable::new(&config.table);
+                backups::list_backups_command(&storage, &mut cli_table)
+            }
+            BackupsAction::Prune {
+                keep_last,
+                keep_daily,
+                keep_weekly,
+                keep_monthly,
+                max_size,
+                dry_run,
+            } => {
+                let defaults = config.backups;
+                let policy = RetentionPolicy {
+                    keep_last: keep_last.unwrap_or(defaults.keep_last),
+                    keep_daily: keep_daily.unwrap_or(defaults.keep_daily),
+                    keep_weekly: keep_weekly.unwrap_or(defaults.keep_weekly),
+                    keep_monthly: keep_monthly.unwrap_or(defaults.keep_monthly),
+                    max_total_bytes: max_size.or(defaults.max_total_bytes),
+                };
+                backups::prune_backups_command(&storage, &policy, dry_run)
+            }
+            BackupsAction::Delete { names, force } => {
+                let confirm_prompt = DialoguerConfirm::new(&config.confirm);
+                backups::delete_backups_command(&storage, &confirm_prompt, names, force)
+            }
+        },
+        Commands::Config { .. } | Commands::Stores { .. } => {
+            unreachable!("handled before the store is opened")
+        }
+    }
+}
+
+fn formatter_for(format: OutputFormat) -> Option<Box<dyn OutputFormatter>> {
+    match format {
+        OutputFormat::Table => None,
+        format => Some(Box::new(CliFormatter::new(format))),
     }
 }
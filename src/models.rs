@@ -1,17 +1,98 @@
+use std::collections::HashMap;
+
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Snippet {
+    /// Stable identity that survives renames. Snippets saved before ids
+    /// existed get one derived from their name, so an old store or backup
+    /// always reads back with the same ids.
+    #[serde(default)]
+    pub id: String,
     pub name: String,
     pub description: String,
     pub content: String,
+    /// Interpreter to run the content with, e.g. `python`. `None` uses the
+    /// configured shell unless the content starts with a `#!` line.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
     pub executable: bool,
     pub tags: Vec<String>,
     #[serde(default = "default_now")]
     pub created_at: DateTime<Utc>,
     #[serde(default = "default_now")]
     pub updated_at: DateTime<Utc>,
+    /// Earlier versions, oldest first.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub revisions: Vec<Revision>,
+}
+
+/// How many earlier versions are kept per snippet.
+pub const MAX_REVISIONS: usize = 20;
+
+/// A snippet as it was before an edit or revert.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Revision {
+    pub number: u32,
+    pub description: String,
+    pub content: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+    pub executable: bool,
+    pub tags: Vec<String>,
+    /// When this version was saved.
+    #[serde(default = "default_now")]
+    pub saved_at: DateTime<Utc>,
+}
+
+impl Snippet {
+    pub fn new_id() -> String {
+        uuid::Uuid::new_v4().to_string()
+    }
+
+    /// Number of the current version. Revisions are numbered from 1 and the
+    /// current version comes after the last kept one.
+    pub fn revision_number(&self) -> u32 {
+        self.revisions.last().map_or(1, |r| r.number + 1)
+    }
+
+    /// Keeps `previous`, the version this snippet replaced, as a revision and
+    /// drops the oldest ones beyond [`MAX_REVISIONS`].
+    pub fn keep_revision(&mut self, previous: &Snippet) {
+        self.revisions.push(Revision {
+            number: previous.revision_number(),
+            description: previous.description.clone(),
+            content: previous.content.clone(),
+            language: previous.language.clone(),
+            executable: previous.executable,
+            tags: previous.tags.clone(),
+            saved_at: previous.updated_at,
+        });
+        let excess = self.revisions.len().saturating_sub(MAX_REVISIONS);
+        self.revisions.drain(..excess);
+    }
+
+    /// The snippet as it was at revision `number`, or `None` if that revision
+    /// is not kept.
+    pub fn at_revision(&self, number: u32) -> Option<Snippet> {
+        if number == self.revision_number() {
+            return Some(self.clone());
+        }
+        let revision = self.revisions.iter().find(|r| r.number == number)?;
+        Some(Snippet {
+            id: self.id.clone(),
+            name: self.name.clone(),
+            description: revision.description.clone(),
+            content: revision.content.clone(),
+            language: revision.language.clone(),
+            executable: revision.executable,
+            tags: revision.tags.clone(),
+            created_at: self.created_at,
+            updated_at: revision.saved_at,
+            revisions: vec![],
+        })
+    }
 }
 
 fn default_now() -> DateTime<Utc> {
@@ -21,6 +102,68 @@ fn default_now() -> DateTime<Utc> {
 #[derive(Serialize, Deserialize, Default, Debug, Clone)]
 pub struct SnippetStore {
     pub snippets: Vec<Snippet>,
+    /// Deleted snippets that can still be brought back, oldest first.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub trash: Vec<TrashedSnippet>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TrashedSnippet {
+    pub deleted_at: DateTime<Utc>,
+    pub snippet: Snippet,
+}
+
+impl SnippetStore {
+    /// Gives every snippet without an id, including trashed ones, one derived
+    /// from its name. Repeated names are told apart by how often the name
+    /// came before, so the same store always gets the same ids.
+    pub fn assign_missing_ids(&mut self) {
+        let mut seen: HashMap<String, usize> = HashMap::new();
+        let trashed = self.trash.iter_mut().map(|t| &mut t.snippet);
+        for snippet in self.snippets.iter_mut().chain(trashed) {
+            if snippet.id.is_empty() {
+                let count = seen.entry(snippet.name.clone()).or_default();
+                let key = match *count {
+                    0 => snippet.name.clone(),
+                    n => format!("{}#{}", snippet.name, n),
+                };
+                *count += 1;
+                snippet.id =
+                    uuid::Uuid::new_v5(&uuid::Uuid::NAMESPACE_OID, key.as_bytes()).to_string();
+            }
+        }
+    }
+
+    /// Moves the snippet with `id` to the trash.
+    pub fn move_to_trash(&mut self, id: &str, now: DateTime<Utc>) -> bool {
+        let Some(index) = self.snippets.iter().position(|s| s.id == id) else {
+            return false;
+        };
+        let snippet = self.snippets.remove(index);
+        self.trash.push(TrashedSnippet {
+            deleted_at: now,
+            snippet,
+        });
+        true
+    }
+
+    /// Takes the most recently deleted snippet called `name` out of the trash.
+    pub fn take_from_trash(&mut self, name: &str) -> Option<TrashedSnippet> {
+        let index = self
+            .trash
+            .iter()
+            .rposition(|t| t.snippet.name.eq_ignore_ascii_case(name))?;
+        Some(self.trash.remove(index))
+    }
+
+    /// Removes trashed snippets deleted before `cutoff` and returns them.
+    pub fn purge_trash(&mut self, cutoff: DateTime<Utc>) -> Vec<TrashedSnippet> {
+        let (expired, kept) = std::mem::take(&mut self.trash)
+            .into_iter()
+            .partition(|t| t.deleted_at < cutoff);
+        self.trash = kept;
+        expired
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone)]
@@ -28,6 +171,8 @@ pub struct PartialSnippet {
     pub name: String,
     pub description: String,
     pub content: String,
+    #[serde(default)]
+    pub language: Option<String>,
     pub executable: bool,
     pub tags: Vec<String>,
 }
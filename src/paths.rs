@@ -0,0 +1,263 @@
+use std::{
+    env, fs, io,
+    path::{Path, PathBuf},
+};
+
+pub const DEFAULT_STORE: &str = "default";
+const CONFIG_FILE: &str = "config.yml";
+const STORES_DIR: &str = "stores";
+const HISTORY_FILE: &str = "history.jsonl";
+
+/// Where markit keeps its data and config.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Paths {
+    pub data_dir: PathBuf,
+    pub config_dir: PathBuf,
+}
+
+impl Paths {
+    /// Resolves the directories from `MARKIT_HOME`, the XDG variables or the
+    /// platform defaults, moving a legacy `~/.markit` into place if needed.
+    pub fn resolve() -> Self {
+        Self::locate(
+            env_dir("MARKIT_HOME"),
+            env_dir("XDG_DATA_HOME"),
+            env_dir("XDG_CONFIG_HOME"),
+            dirs::home_dir().map(|home| home.join(".markit")),
+        )
+    }
+
+    fn locate(
+        markit_home: Option<PathBuf>,
+        xdg_data: Option<PathBuf>,
+        xdg_config: Option<PathBuf>,
+        legacy: Option<PathBuf>,
+    ) -> Self {
+        if let Some(dir) = markit_home {
+            return Self::single(dir);
+        }
+
+        let data_dir = xdg_data.or_else(dirs::data_dir).map(|d| d.join("markit"));
+        let config_dir = xdg_config
+            .or_else(dirs::config_dir)
+            .map(|d| d.join("markit"));
+
+        let (Some(data_dir), Some(config_dir)) = (data_dir, config_dir) else {
+            eprintln!("⚠️ Could not determine home directory, defaulting to current directory.");
+            return Self::single(PathBuf::from(".markit"));
+        };
+
+        let paths = Self {
+            data_dir,
+            config_dir,
+        };
+
+        match legacy {
+            Some(legacy) if legacy.is_dir() && !paths.data_dir.exists() => {
+                match paths.migrate_from(&legacy) {
+                    Ok(_) => {
+                        eprintln!(
+                            "📦 Moved {} to {}",
+                            legacy.display(),
+                            paths.data_dir.display()
+                        );
+                        paths
+                    }
+                    Err(e) => {
+                        eprintln!(
+                            "⚠️ Could not move {} to {}: {}. Using it in place.",
+                            legacy.display(),
+                            paths.data_dir.display(),
+                            e
+                        );
+                        Self::single(legacy)
+                    }
+                }
+            }
+            _ => paths,
+        }
+    }
+
+    fn single(dir: PathBuf) -> Self {
+        Self {
+            data_dir: dir.clone(),
+            config_dir: dir,
+        }
+    }
+
+    fn migrate_from(&self, legacy: &Path) -> io::Result<()> {
+        if let Some(parent) = self.data_dir.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::rename(legacy, &self.data_dir)?;
+
+        let moved_config = self.data_dir.join(CONFIG_FILE);
+        if self.config_dir != self.data_dir && moved_config.exists() && !self.config_file().exists()
+        {
+            fs::create_dir_all(&self.config_dir)?;
+            fs::rename(moved_config, self.config_file())?;
+        }
+
+        Ok(())
+    }
+
+    pub fn config_file(&self) -> PathBuf {
+        self.config_dir.join(CONFIG_FILE)
+    }
+
+    /// Log of executed snippets, shared by all stores.
+    pub fn history_file(&self) -> PathBuf {
+        self.data_dir.join(HISTORY_FILE)
+    }
+
+    pub fn stores_dir(&self) -> PathBuf {
+        self.data_dir.join(STORES_DIR)
+    }
+
+    /// Directory of the named store. The default store lives at the root of
+    /// the data directory so existing installs keep working.
+    pub fn named_store_dir(&self, name: &str) -> PathBuf {
+        if name == DEFAULT_STORE {
+            self.data_dir.clone()
+        } else {
+            self.stores_dir().join(name)
+        }
+    }
+
+    /// Resolves `--store`, which is either the name of an existing store or a
+    /// path to a store directory.
+    pub fn store_dir(&self, spec: &str) -> Result<PathBuf, String> {
+        if is_path(spec) {
+            return Ok(expand_home(spec));
+        }
+
+        if !is_valid_store_name(spec) {
+            return Err(format!("Invalid store name '{}'", spec));
+        }
+
+        let dir = self.named_store_dir(spec);
+        if spec != DEFAULT_STORE && !dir.is_dir() {
+            return Err(format!(
+                "Store '{}' does not exist. Create it with `markit stores create {}`.",
+                spec, spec
+            ));
+        }
+
+        Ok(dir)
+    }
+}
+
+pub fn is_valid_store_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+fn is_path(spec: &str) -> bool {
+    spec.contains('/') || spec.contains('\\') || spec.starts_with('.') || spec.starts_with('~')
+}
+
+fn expand_home(spec: &str) -> PathBuf {
+    match (spec.strip_prefix("~/"), dirs::home_dir()) {
+        (Some(rest), Some(home)) => home.join(rest),
+        _ => PathBuf::from(spec),
+    }
+}
+
+fn env_dir(key: &str) -> Option<PathBuf> {
+    env::var_os(key)
+        .filter(|v| !v.is_empty())
+        .map(PathBuf::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_markit_home_wins() {
+        let dir = tempfile::tempdir().unwrap();
+        let home = dir.path().join("home");
+
+        let paths = Paths::locate(
+            Some(home.clone()),
+            Some(dir.path().join("data")),
+            Some(dir.path().join("config")),
+            None,
+        );
+
+        assert_eq!(paths.data_dir, home);
+        assert_eq!(paths.config_file(), home.join("config.yml"));
+    }
+
+    #[test]
+    fn test_xdg_directories() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let paths = Paths::locate(
+            None,
+            Some(dir.path().join("data")),
+            Some(dir.path().join("config")),
+            Some(dir.path().join(".markit")),
+        );
+
+        assert_eq!(paths.data_dir, dir.path().join("data/markit"));
+        assert_eq!(paths.config_dir, dir.path().join("config/markit"));
+    }
+
+    #[test]
+    fn test_migrates_legacy_directory() {
+        let dir = tempfile::tempdir().unwrap();
+        let legacy = dir.path().join(".markit");
+        fs::create_dir_all(legacy.join("backups")).unwrap();
+        fs::write(legacy.join("bookmarks.yml"), "snippets: []\n").unwrap();
+        fs::write(legacy.join("config.yml"), "backups:\n  keep_last: 3\n").unwrap();
+
+        let paths = Paths::locate(
+            None,
+            Some(dir.path().join("data")),
+            Some(dir.path().join("config")),
+            Some(legacy.clone()),
+        );
+
+        assert!(!legacy.exists());
+        assert!(paths.data_dir.join("bookmarks.yml").exists());
+        assert!(paths.data_dir.join("backups").is_dir());
+        assert!(paths.config_file().exists());
+        assert!(!paths.data_dir.join("config.yml").exists());
+    }
+
+    #[test]
+    fn test_does_not_migrate_over_existing_data() {
+        let dir = tempfile::tempdir().unwrap();
+        let legacy = dir.path().join(".markit");
+        fs::create_dir_all(&legacy).unwrap();
+        fs::create_dir_all(dir.path().join("data/markit")).unwrap();
+
+        Paths::locate(
+            None,
+            Some(dir.path().join("data")),
+            Some(dir.path().join("config")),
+            Some(legacy.clone()),
+        );
+
+        assert!(legacy.exists());
+    }
+
+    #[test]
+    fn test_store_dir_resolution() {
+        let dir = tempfile::tempdir().unwrap();
+        let paths = Paths::locate(Some(dir.path().to_path_buf()), None, None, None);
+        fs::create_dir_all(paths.stores_dir().join("work")).unwrap();
+
+        assert_eq!(paths.store_dir("default").unwrap(), dir.path());
+        assert_eq!(
+            paths.store_dir("work").unwrap(),
+            dir.path().join("stores/work")
+        );
+        assert_eq!(paths.store_dir("./team").unwrap(), PathBuf::from("./team"));
+        assert!(paths.store_dir("personal").is_err());
+        assert!(paths.store_dir("bad name").is_err());
+    }
+}
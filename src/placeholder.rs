@@ -0,0 +1,164 @@
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Placeholder {
+    pub name: String,
+    pub default: Option<String>,
+}
+
+/// Finds every `{{name}}` / `{{name:default}}` placeholder in `content`,
+/// in order of first appearance and without duplicates.
+pub fn parse_placeholders(content: &str) -> Vec<Placeholder> {
+    let mut placeholders: Vec<Placeholder> = Vec::new();
+
+    for (_, _, placeholder) in scan(content) {
+        if let Some(existing) = placeholders.iter_mut().find(|p| p.name == placeholder.name) {
+            if existing.default.is_none() {
+                existing.default = placeholder.default;
+            }
+        } else {
+            placeholders.push(placeholder);
+        }
+    }
+
+    placeholders
+}
+
+/// Replaces each placeholder with its value from `values`, leaving unknown
+/// placeholders untouched.
+pub fn substitute(content: &str, values: &HashMap<String, String>) -> String {
+    let mut result = String::with_capacity(content.len());
+    let mut last = 0;
+
+    for (start, end, placeholder) in scan(content) {
+        if let Some(value) = values.get(&placeholder.name) {
+            result.push_str(&content[last..start]);
+            result.push_str(value);
+            last = end;
+        }
+    }
+
+    result.push_str(&content[last..]);
+    result
+}
+
+/// Parses a `key=value` pair as passed to `--var`.
+pub fn parse_var(input: &str) -> Result<(String, String), String> {
+    let (key, value) = input
+        .split_once('=')
+        .ok_or_else(|| format!("invalid variable '{}': expected key=value", input))?;
+
+    let key = key.trim();
+    if !is_valid_name(key) {
+        return Err(format!("invalid variable name '{}'", key));
+    }
+
+    Ok((key.to_string(), value.to_string()))
+}
+
+fn scan(content: &str) -> Vec<(usize, usize, Placeholder)> {
+    let mut found = Vec::new();
+    let mut offset = 0;
+
+    while let Some(open) = content[offset..].find("{{") {
+        let start = offset + open;
+        let Some(close) = content[start + 2..].find("}}") else {
+            break;
+        };
+        let end = start + 2 + close + 2;
+        let inner = &content[start + 2..end - 2];
+
+        let (name, default) = match inner.split_once(':') {
+            Some((name, default)) => (name.trim(), Some(default.to_string())),
+            None => (inner.trim(), None),
+        };
+
+        if is_valid_name(name) {
+            found.push((
+                start,
+                end,
+                Placeholder {
+                    name: name.to_string(),
+                    default,
+                },
+            ));
+            offset = end;
+        } else {
+            offset = start + 2;
+        }
+    }
+
+    found
+}
+
+pub fn is_valid_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_placeholders_with_defaults() {
+        let placeholders =
+            parse_placeholders("kubectl -n {{namespace}} port-forward svc/{{svc}} {{port:8080}}");
+
+        assert_eq!(
+            placeholders,
+            vec![
+                Placeholder {
+                    name: "namespace".into(),
+                    default: None
+                },
+                Placeholder {
+                    name: "svc".into(),
+                    default: None
+                },
+                Placeholder {
+                    name: "port".into(),
+                    default: Some("8080".into())
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_placeholders_deduplicates() {
+        let placeholders = parse_placeholders("echo {{host}} && ssh {{host:localhost}}");
+
+        assert_eq!(placeholders.len(), 1);
+        assert_eq!(placeholders[0].default.as_deref(), Some("localhost"));
+    }
+
+    #[test]
+    fn test_parse_placeholders_ignores_invalid_names() {
+        assert!(parse_placeholders("awk '{{ print $1 }}'").is_empty());
+        assert!(parse_placeholders("echo {{unterminated").is_empty());
+    }
+
+    #[test]
+    fn test_substitute_replaces_known_values() {
+        let values = HashMap::from([
+            ("namespace".to_string(), "prod".to_string()),
+            ("port".to_string(), "9090".to_string()),
+        ]);
+
+        let result = substitute("kubectl -n {{namespace}} {{port:8080}} {{other}}", &values);
+
+        assert_eq!(result, "kubectl -n prod 9090 {{other}}");
+    }
+
+    #[test]
+    fn test_parse_var() {
+        assert_eq!(
+            parse_var("ticket=ABC-1=2"),
+            Ok(("ticket".to_string(), "ABC-1=2".to_string()))
+        );
+        assert!(parse_var("novalue").is_err());
+        assert!(parse_var("bad name=1").is_err());
+    }
+}
@@ -0,0 +1,224 @@
+use std::{
+    collections::HashSet,
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+/// Commands from the user's shell history, oldest first.
+pub trait ShellHistory {
+    fn commands(&self) -> Result<Vec<String>, String>;
+}
+
+/// Bash and zsh history files on disk.
+pub struct HistoryFiles {
+    paths: Vec<PathBuf>,
+}
+
+impl HistoryFiles {
+    /// `HISTFILE` if it is exported, then the default bash and zsh files.
+    pub fn detect() -> Self {
+        let home = dirs::home_dir();
+        let zdotdir = env::var_os("ZDOTDIR")
+            .map(PathBuf::from)
+            .or_else(|| home.clone());
+
+        let candidates = [
+            env::var_os("HISTFILE")
+                .filter(|f| !f.is_empty())
+                .map(PathBuf::from),
+            home.map(|h| h.join(".bash_history")),
+            zdotdir.map(|z| z.join(".zsh_history")),
+        ];
+
+        let mut paths: Vec<PathBuf> = vec![];
+        for path in candidates.into_iter().flatten() {
+            if !paths.contains(&path) {
+                paths.push(path);
+            }
+        }
+        Self { paths }
+    }
+}
+
+impl ShellHistory for HistoryFiles {
+    fn commands(&self) -> Result<Vec<String>, String> {
+        let mut commands = vec![];
+        let mut found = false;
+
+        for path in &self.paths {
+            let Ok(bytes) = fs::read(path) else {
+                continue;
+            };
+            found = true;
+
+            let text = if is_zsh(path) {
+                String::from_utf8_lossy(&unmetafy(&bytes)).into_owned()
+            } else {
+                String::from_utf8_lossy(&bytes).into_owned()
+            };
+            commands.extend(parse(&text));
+        }
+
+        if !found {
+            let looked_in: Vec<String> =
+                self.paths.iter().map(|p| p.display().to_string()).collect();
+            return Err(format!(
+                "No shell history found (looked in {}).",
+                looked_in.join(", ")
+            ));
+        }
+        Ok(commands)
+    }
+}
+
+fn is_zsh(path: &Path) -> bool {
+    path.file_name()
+        .is_some_and(|name| name.to_string_lossy().contains("zsh"))
+}
+
+/// zsh stores some bytes as 0x83 followed by the byte XOR 32.
+fn unmetafy(bytes: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut iter = bytes.iter();
+    while let Some(&b) = iter.next() {
+        if b == 0x83 {
+            if let Some(&next) = iter.next() {
+                out.push(next ^ 32);
+            }
+        } else {
+            out.push(b);
+        }
+    }
+    out
+}
+
+/// Reads plain bash history, bash history with `#<timestamp>` lines and zsh
+/// extended history (`: <start>:<elapsed>;<command>`). Lines ending in a
+/// backslash continue on the next line, as zsh writes multi-line commands.
+pub fn parse(text: &str) -> Vec<String> {
+    let mut commands = vec![];
+    let mut lines = text.lines();
+
+    while let Some(line) = lines.next() {
+        if is_bash_timestamp(line) {
+            continue;
+        }
+
+        let mut command = strip_zsh_prefix(line).to_string();
+        while command.ends_with('\\') {
+            let Some(next) = lines.next() else {
+                break;
+            };
+            command.pop();
+            command.push('\n');
+            command.push_str(next);
+        }
+
+        let command = command.trim();
+        if !command.is_empty() {
+            commands.push(command.to_string());
+        }
+    }
+    commands
+}
+
+fn is_bash_timestamp(line: &str) -> bool {
+    line.strip_prefix('#')
+        .is_some_and(|ts| !ts.is_empty() && ts.chars().all(|c| c.is_ascii_digit()))
+}
+
+fn strip_zsh_prefix(line: &str) -> &str {
+    let Some(rest) = line.strip_prefix(": ") else {
+        return line;
+    };
+    let Some((meta, command)) = rest.split_once(';') else {
+        return line;
+    };
+    let is_meta = meta.split_once(':').is_some_and(|(start, elapsed)| {
+        [start, elapsed]
+            .iter()
+            .all(|n| !n.is_empty() && n.chars().all(|c| c.is_ascii_digit()))
+    });
+    if is_meta { command } else { line }
+}
+
+/// Drops repeated commands, keeping the latest run, newest first.
+pub fn unique_newest_first(commands: Vec<String>) -> Vec<String> {
+    let mut seen = HashSet::new();
+    commands
+        .into_iter()
+        .rev()
+        .filter(|command| seen.insert(command.clone()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_bash_history() {
+        let text = "ls -la\n#1700000000\ngit status\n\n  \ncargo test\n";
+
+        assert_eq!(parse(text), vec!["ls -la", "git status", "cargo test"]);
+    }
+
+    #[test]
+    fn test_parse_zsh_extended_history() {
+        let text = ": 1700000000:0;ls -la\n\
+                    : 1700000005:2;for f in *; do\\\n  echo $f\\\ndone\n\
+                    : 1700000009:0;echo a;b\n";
+
+        assert_eq!(
+            parse(text),
+            vec!["ls -la", "for f in *; do\n  echo $f\ndone", "echo a;b"]
+        );
+    }
+
+    #[test]
+    fn test_plain_lines_that_look_like_zsh_are_kept() {
+        assert_eq!(parse(": not;zsh\n"), vec![": not;zsh"]);
+    }
+
+    #[test]
+    fn test_unmetafy() {
+        // "é" is 0xC3 0xA9; zsh metafies neither byte, but 0x83 itself is
+        // stored as 0x83 0xA3.
+        assert_eq!(
+            unmetafy(&[b'a', 0x83, 0xA3, 0xC3, 0xA9]),
+            vec![b'a', 0x83, 0xC3, 0xA9]
+        );
+    }
+
+    #[test]
+    fn test_unique_newest_first() {
+        let commands = vec!["ls", "git status", "ls", "make"]
+            .into_iter()
+            .map(String::from)
+            .collect();
+
+        assert_eq!(
+            unique_newest_first(commands),
+            vec!["make", "ls", "git status"]
+        );
+    }
+
+    #[test]
+    fn test_history_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let bash = dir.path().join(".bash_history");
+        let zsh = dir.path().join(".zsh_history");
+        fs::write(&bash, "ls\n").unwrap();
+        fs::write(&zsh, ": 1700000000:0;make\n").unwrap();
+
+        let files = HistoryFiles {
+            paths: vec![bash, dir.path().join("missing"), zsh],
+        };
+        assert_eq!(files.commands().unwrap(), vec!["ls", "make"]);
+
+        let missing = HistoryFiles {
+            paths: vec![dir.path().join("missing")],
+        };
+        assert!(missing.commands().is_err());
+    }
+}
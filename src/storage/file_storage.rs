@@ -1,69 +1,243 @@
 use std::{
-    fs::{self, File},
+    cell::RefCell,
+    fs::{self, File, OpenOptions, TryLockError},
     io::Write,
     path::{Path, PathBuf},
+    rc::{Rc, Weak},
+    thread,
+    time::{Duration, Instant},
 };
 
-use chrono::Utc;
+use chrono::{DateTime, NaiveDateTime, Utc};
 
 use crate::{
     models::{Snippet, SnippetStore},
-    storage::{Storage, StorageError},
+    storage::{
+        BackupManager, Storage, StorageError, StoreLock,
+        retention::{BackupInfo, RetentionPolicy, select_prunable},
+        usage::UsageStats,
+    },
 };
 
+const LOCK_TIMEOUT: Duration = Duration::from_secs(5);
+const LOCK_RETRY_INTERVAL: Duration = Duration::from_millis(50);
+const BACKUP_TIMESTAMP_FORMAT: &str = "%Y-%m-%dT%H-%M-%SZ";
+
 pub struct FileStorage {
     base_path: PathBuf,
+    lock_timeout: Duration,
+    held_lock: RefCell<Weak<File>>,
+    retention: Option<RetentionPolicy>,
+    purge_trash_after: Option<chrono::Duration>,
 }
 
 impl FileStorage {
-    pub fn new() -> Self {
-        let base_path = dirs::home_dir()
-            .unwrap_or_else(|| {
-                eprintln!(
-                    "⚠️ Could not determine home directory, defaulting to current directory."
-                );
-                PathBuf::from(".")
-            })
-            .join(".markit");
-
+    pub fn with_base_path(base_path: PathBuf) -> Self {
         if let Err(e) = fs::create_dir_all(&base_path) {
             eprintln!("⛔ Failed to create base directory: {}", e);
         }
 
-        Self { base_path }
+        Self {
+            base_path,
+            lock_timeout: LOCK_TIMEOUT,
+            held_lock: RefCell::new(Weak::new()),
+            retention: None,
+            purge_trash_after: None,
+        }
+    }
+
+    /// Prunes backups with `policy` every time a new backup is written.
+    pub fn with_retention(mut self, policy: RetentionPolicy) -> Self {
+        self.retention = Some(policy);
+        self
+    }
+
+    /// Drops snippets deleted more than `days` ago from the trash whenever the
+    /// store is saved, after the backup is taken. `0` keeps them.
+    pub fn with_trash_purge(mut self, days: u32) -> Self {
+        self.purge_trash_after = (days > 0).then(|| chrono::Duration::days(days.into()));
+        self
     }
 
     fn storage_path(&self) -> PathBuf {
         self.base_path.join("bookmarks.yml")
     }
 
+    fn lock_path(&self) -> PathBuf {
+        self.base_path.join("bookmarks.lock")
+    }
+
+    fn usage_path(&self) -> PathBuf {
+        self.base_path.join("usage.yml")
+    }
+
     fn backup_dir(&self) -> PathBuf {
         self.base_path.join("backups")
     }
 
-    fn load_store(&self) -> Result<SnippetStore, StorageError> {
+    fn read_store(&self) -> Result<SnippetStore, StorageError> {
         let path = self.storage_path();
         if !path.exists() {
             return Ok(SnippetStore::default());
         }
 
         let file = File::open(&path)?;
-        let store = serde_yaml::from_reader(file)?;
+        serde_yaml::from_reader(file).map_err(|e| StorageError::corrupt(&path, e))
+    }
+
+    fn load_store(&self) -> Result<SnippetStore, StorageError> {
+        let mut store = self.read_store()?;
+
+        // Derived ids are stable, so they only reach the file with the next
+        // save and reading never writes.
+        store.assign_missing_ids();
         Ok(store)
     }
 
-    fn backup_current_store(&self, store: &SnippetStore) -> Result<(), StorageError> {
+    /// Writes `contents` to a temp file next to `path`, syncs it and renames it
+    /// into place so readers never observe a partially written file.
+    fn write_atomic(&self, path: &Path, contents: &[u8]) -> Result<(), StorageError> {
+        let dir = path.parent().unwrap_or(&self.base_path);
+        let mut tmp = tempfile::NamedTempFile::new_in(dir)?;
+        tmp.write_all(contents)?;
+        tmp.as_file().sync_all()?;
+        tmp.persist(path).map_err(|e| StorageError::Io(e.error))?;
+
+        #[cfg(unix)]
+        File::open(dir)?.sync_all()?;
+
+        Ok(())
+    }
+
+    fn write_store(&self, store: &SnippetStore) -> Result<(), StorageError> {
+        let yaml = serde_yaml::to_string(store)?;
+        self.write_atomic(&self.storage_path(), yaml.as_bytes())
+    }
+
+    /// Writes `store` without the snippets that have been in the trash longer
+    /// than configured, and forgets their usage. Callers take a backup first,
+    /// so purged snippets can still be restored from it.
+    fn write_store_purging_trash(&self, mut store: SnippetStore) -> Result<(), StorageError> {
+        let purged = match self.purge_trash_after {
+            Some(age) => store.purge_trash(Utc::now() - age),
+            None => vec![],
+        };
+        self.write_store(&store)?;
+
+        if !purged.is_empty() {
+            self.update_usage(&mut |usage| {
+                for trashed in &purged {
+                    usage.remove(&trashed.snippet.id);
+                }
+            })?;
+        }
+        Ok(())
+    }
+
+    /// Writes a new backup named after the current time. Backups taken in the
+    /// same second get a `_2`, `_3`, ... suffix, so none is ever overwritten.
+    fn write_backup(&self, contents: &[u8]) -> Result<PathBuf, StorageError> {
         let backup_dir = self.backup_dir();
         fs::create_dir_all(&backup_dir).map_err(StorageError::Io)?;
 
-        let timestamp = Utc::now().format("%Y-%m-%dT%H-%M-%SZ").to_string();
-        let backup_file = backup_dir.join(format!("{}.yml", timestamp));
-        let mut file = File::create(backup_file).map_err(StorageError::Io)?;
+        let mut tmp = tempfile::NamedTempFile::new_in(&backup_dir)?;
+        tmp.write_all(contents)?;
+        tmp.as_file().sync_all()?;
+
+        let timestamp = Utc::now().format(BACKUP_TIMESTAMP_FORMAT).to_string();
+        let mut number = 1;
+        loop {
+            let name = match number {
+                1 => format!("{}.yml", timestamp),
+                n => format!("{}_{}.yml", timestamp, n),
+            };
+            let path = backup_dir.join(name);
+            match tmp.persist_noclobber(&path) {
+                Ok(_) => {
+                    #[cfg(unix)]
+                    File::open(&backup_dir)?.sync_all()?;
+
+                    return Ok(path);
+                }
+                Err(e) if e.error.kind() == std::io::ErrorKind::AlreadyExists => {
+                    tmp = e.file;
+                    number += 1;
+                }
+                Err(e) => return Err(StorageError::Io(e.error)),
+            }
+        }
+    }
+
+    fn backup_current_store(&self, store: &SnippetStore) -> Result<(), StorageError> {
         let yaml = serde_yaml::to_string(store).map_err(StorageError::Serde)?;
-        file.write_all(yaml.as_bytes()).map_err(StorageError::Io)?;
+        self.write_backup(yaml.as_bytes())?;
+
+        if let Some(policy) = &self.retention
+            && let Err(e) = self.prune_backups(policy, false)
+        {
+            eprintln!("⚠️ Failed to prune old backups: {}", e);
+        }
 
         Ok(())
     }
+
+    fn backup_info(&self, path: &Path, with_count: bool) -> Result<BackupInfo, StorageError> {
+        let metadata = fs::metadata(path)?;
+
+        let timestamp = backup_name_order(path)
+            .map(|(naive, _)| naive.and_utc())
+            .or_else(|| metadata.modified().ok().map(DateTime::<Utc>::from))
+            .unwrap_or_else(Utc::now);
+
+        let snippet_count = if with_count {
+            File::open(path)
+                .ok()
+                .and_then(|file| serde_yaml::from_reader::<_, SnippetStore>(file).ok())
+                .map(|store| store.snippets.len())
+        } else {
+            None
+        };
+
+        Ok(BackupInfo {
+            path: path.to_path_buf(),
+            timestamp,
+            size: metadata.len(),
+            snippet_count,
+        })
+    }
+
+    fn acquire_lock(&self) -> Result<File, StorageError> {
+        let path = self.lock_path();
+        let file = OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(&path)?;
+
+        let started = Instant::now();
+        loop {
+            match file.try_lock() {
+                Ok(()) => return Ok(file),
+                Err(TryLockError::WouldBlock) if started.elapsed() < self.lock_timeout => {
+                    thread::sleep(LOCK_RETRY_INTERVAL);
+                }
+                Err(TryLockError::WouldBlock) => return Err(StorageError::Locked(path)),
+                Err(TryLockError::Error(e)) => return Err(StorageError::Io(e)),
+            }
+        }
+    }
+}
+
+/// The time in a backup's name and its same-second number, 1 for the first
+/// backup of that second, or `None` for names markit did not write.
+fn backup_name_order(path: &Path) -> Option<(NaiveDateTime, u32)> {
+    let stem = path.file_stem()?.to_string_lossy();
+    let (timestamp, number) = match stem.split_once('_') {
+        Some((timestamp, number)) => (timestamp, number.parse().ok()?),
+        None => (stem.as_ref(), 1),
+    };
+    let timestamp = NaiveDateTime::parse_from_str(timestamp, BACKUP_TIMESTAMP_FORMAT).ok()?;
+    Some((timestamp, number))
 }
 
 impl Storage for FileStorage {
@@ -72,25 +246,22 @@ impl Storage for FileStorage {
     }
 
     fn save(&self, snippet: Snippet) -> Result<(), StorageError> {
-        let mut store = self.load_store()?;
+        let _lock = self.lock()?;
+        let mut store = self.read_store()?;
         self.backup_current_store(&store)?;
 
+        store.assign_missing_ids();
         store.snippets.push(snippet);
-
-        let file = File::create(self.storage_path()).map_err(StorageError::Io)?;
-        serde_yaml::to_writer(file, &store).map_err(StorageError::Serde)?;
+        self.write_store_purging_trash(store)?;
 
         println!("✅ Snippet saved.");
         Ok(())
     }
 
     fn save_all(&self, store: &SnippetStore) -> Result<(), StorageError> {
-        self.backup_current_store(&self.load_store()?)?;
-
-        let file = File::create(self.storage_path()).map_err(StorageError::Io)?;
-        serde_yaml::to_writer(file, store).map_err(StorageError::Serde)?;
-
-        Ok(())
+        let _lock = self.lock()?;
+        self.backup_current_store(&self.read_store()?)?;
+        self.write_store_purging_trash(store.clone())
     }
 
     fn get_backups(&self) -> Result<Vec<PathBuf>, StorageError> {
@@ -108,15 +279,401 @@ impl Storage for FileStorage {
             })
             .collect();
 
-        backups.sort_by(|a, b| b.file_name().cmp(&a.file_name()));
+        backups.sort_by(|a, b| {
+            (backup_name_order(b), b.file_name()).cmp(&(backup_name_order(a), a.file_name()))
+        });
         Ok(backups)
     }
 
     fn restore_backup(&self, path: &Path) -> Result<(), StorageError> {
-        fs::copy(path, self.storage_path())
-            .map_err(StorageError::Io)
-            .map(|_| {
-                println!("✅ Backup restored from '{}'", path.display());
+        let _lock = self.lock()?;
+        let contents = fs::read(path).map_err(StorageError::Io)?;
+
+        let current = self.storage_path();
+        if current.exists() {
+            self.write_backup(&fs::read(&current)?)?;
+        }
+
+        self.write_atomic(&current, &contents).map(|_| {
+            println!("✅ Backup restored from '{}'", path.display());
+        })
+    }
+
+    fn load_usage(&self) -> Result<UsageStats, StorageError> {
+        let path = self.usage_path();
+        if !path.exists() {
+            return Ok(UsageStats::default());
+        }
+
+        let file = File::open(&path)?;
+        serde_yaml::from_reader(file).map_err(|e| StorageError::corrupt(&path, e))
+    }
+
+    fn update_usage(&self, update: &mut dyn FnMut(&mut UsageStats)) -> Result<(), StorageError> {
+        let _lock = self.lock()?;
+        let mut usage = self.load_usage()?;
+        update(&mut usage);
+        let yaml = serde_yaml::to_string(&usage)?;
+        self.write_atomic(&self.usage_path(), yaml.as_bytes())
+    }
+
+    /// Takes the advisory lock on the store. The lock is re-entrant within this
+    /// process and released once every returned guard has been dropped.
+    fn lock(&self) -> Result<StoreLock, StorageError> {
+        if let Some(file) = self.held_lock.borrow().upgrade() {
+            return Ok(StoreLock::new(file));
+        }
+
+        let file = Rc::new(self.acquire_lock()?);
+        self.held_lock.replace(Rc::downgrade(&file));
+        Ok(StoreLock::new(file))
+    }
+}
+
+impl BackupManager for FileStorage {
+    fn get_backup_details(&self) -> Result<Vec<BackupInfo>, StorageError> {
+        self.get_backups()?
+            .iter()
+            .map(|path| self.backup_info(path, true))
+            .collect()
+    }
+
+    fn prune_backups(
+        &self,
+        policy: &RetentionPolicy,
+        dry_run: bool,
+    ) -> Result<Vec<PathBuf>, StorageError> {
+        let backups = self
+            .get_backups()?
+            .iter()
+            .map(|path| self.backup_info(path, false))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let prunable = select_prunable(&backups, policy);
+        if !dry_run {
+            for path in &prunable {
+                self.delete_backup(path)?;
+            }
+        }
+
+        Ok(prunable)
+    }
+
+    fn delete_backup(&self, path: &Path) -> Result<(), StorageError> {
+        if path.parent() != Some(self.backup_dir().as_path()) {
+            return Err(StorageError::Io(std::io::Error::other(format!(
+                "'{}' is not a markit backup",
+                path.display()
+            ))));
+        }
+
+        fs::remove_file(path).map_err(StorageError::Io)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeZone, Utc};
+
+    fn snippet(name: &str) -> Snippet {
+        Snippet {
+            id: Snippet::new_id(),
+            name: name.to_string(),
+            description: "desc".to_string(),
+            content: "echo hi".to_string(),
+            language: None,
+            executable: true,
+            tags: vec![],
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            revisions: vec![],
+        }
+    }
+
+    #[test]
+    fn test_save_writes_store_and_backup() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = FileStorage::with_base_path(dir.path().to_path_buf());
+
+        storage.save(snippet("a")).unwrap();
+        storage.save(snippet("b")).unwrap();
+
+        let store = storage.load().unwrap();
+        assert_eq!(store.snippets.len(), 2);
+        assert!(!storage.get_backups().unwrap().is_empty());
+
+        let leftovers: Vec<_> = fs::read_dir(dir.path())
+            .unwrap()
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_name().to_string_lossy().starts_with(".tmp"))
+            .collect();
+        assert!(leftovers.is_empty());
+    }
+
+    #[test]
+    fn test_usage_is_kept_outside_the_store() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = FileStorage::with_base_path(dir.path().to_path_buf());
+        storage.save(snippet("a")).unwrap();
+        let backups = storage.get_backups().unwrap().len();
+
+        storage
+            .update_usage(&mut |usage| {
+                usage.record("a", crate::storage::usage::UsageKind::Run, Utc::now())
+            })
+            .unwrap();
+
+        assert_eq!(storage.load_usage().unwrap().get("a").runs, 1);
+        assert_eq!(storage.get_backups().unwrap().len(), backups);
+    }
+
+    const LEGACY_STORE: &str =
+        "snippets:\n- name: a\n  description: ''\n  content: ls\n  executable: true\n  tags: []\n";
+
+    #[test]
+    fn test_missing_ids_are_stable_and_not_written_on_load() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("bookmarks.yml");
+        fs::write(&path, LEGACY_STORE).unwrap();
+        let storage = FileStorage::with_base_path(dir.path().to_path_buf());
+
+        let first = storage.load().unwrap().snippets[0].id.clone();
+        let second = storage.load().unwrap().snippets[0].id.clone();
+
+        assert!(!first.is_empty());
+        assert_eq!(first, second);
+        assert_eq!(fs::read_to_string(&path).unwrap(), LEGACY_STORE);
+    }
+
+    #[test]
+    fn test_restoring_backup_without_ids_keeps_usage() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("bookmarks.yml"), LEGACY_STORE).unwrap();
+        let storage = FileStorage::with_base_path(dir.path().to_path_buf());
+
+        let store = storage.load().unwrap();
+        let id = store.snippets[0].id.clone();
+        storage
+            .update_usage(&mut |u| u.record(&id, crate::storage::usage::UsageKind::Run, Utc::now()))
+            .unwrap();
+        // Writes the ids and keeps the store without them as a backup.
+        storage.save_all(&store).unwrap();
+
+        let backup = storage.get_backups().unwrap()[0].clone();
+        storage.restore_backup(&backup).unwrap();
+
+        let restored = storage.load().unwrap();
+        assert_eq!(restored.snippets[0].id, id);
+        assert_eq!(storage.load_usage().unwrap().get(&id).runs, 1);
+    }
+
+    #[test]
+    fn test_old_trash_is_purged_on_write_after_backup() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = FileStorage::with_base_path(dir.path().to_path_buf()).with_trash_purge(30);
+        let (old, new) = (snippet("old"), snippet("new"));
+        let mut store = SnippetStore {
+            snippets: vec![old.clone(), new.clone()],
+            trash: vec![],
+        };
+        store.move_to_trash(&old.id, Utc::now() - chrono::Duration::days(31));
+        store.move_to_trash(&new.id, Utc::now());
+        storage.write_store(&store).unwrap();
+        storage
+            .update_usage(&mut |usage| {
+                usage.record(&old.id, crate::storage::usage::UsageKind::Run, Utc::now())
             })
+            .unwrap();
+
+        let loaded = storage.load().unwrap();
+        assert_eq!(loaded.trash.len(), 2);
+        storage.save_all(&loaded).unwrap();
+
+        let trash = storage.load().unwrap().trash;
+        assert_eq!(trash.len(), 1);
+        assert_eq!(trash[0].snippet.name, "new");
+        assert_eq!(storage.load_usage().unwrap().get(&old.id).runs, 0);
+
+        let backup = storage.get_backups().unwrap()[0].clone();
+        let backed_up: SnippetStore = serde_yaml::from_reader(File::open(backup).unwrap()).unwrap();
+        assert_eq!(backed_up.trash.len(), 2);
+    }
+
+    #[test]
+    fn test_lock_is_reentrant() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = FileStorage::with_base_path(dir.path().to_path_buf());
+
+        let _outer = storage.lock().unwrap();
+        storage.save_all(&SnippetStore::default()).unwrap();
+        storage.save(snippet("a")).unwrap();
+    }
+
+    #[test]
+    fn test_lock_times_out_when_held_elsewhere() {
+        let dir = tempfile::tempdir().unwrap();
+        let first = FileStorage::with_base_path(dir.path().to_path_buf());
+        let mut second = FileStorage::with_base_path(dir.path().to_path_buf());
+        second.lock_timeout = Duration::from_millis(100);
+
+        let held = first.lock().unwrap();
+        assert!(matches!(second.lock(), Err(StorageError::Locked(_))));
+        assert!(second.save(snippet("a")).is_err());
+
+        drop(held);
+        assert!(second.lock().is_ok());
+    }
+
+    #[test]
+    fn test_backup_details_and_pruning() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = FileStorage::with_base_path(dir.path().to_path_buf());
+        fs::create_dir_all(storage.backup_dir()).unwrap();
+
+        for day in 1..=5 {
+            let path = storage
+                .backup_dir()
+                .join(format!("2025-01-0{}T10-00-00Z.yml", day));
+            fs::write(&path, "snippets: []\n").unwrap();
+        }
+
+        let details = storage.get_backup_details().unwrap();
+        assert_eq!(details.len(), 5);
+        assert_eq!(details[0].name(), "2025-01-05T10-00-00Z.yml");
+        assert_eq!(details[0].snippet_count, Some(0));
+
+        let policy = RetentionPolicy {
+            keep_last: 2,
+            keep_daily: 0,
+            keep_weekly: 0,
+            keep_monthly: 0,
+            max_total_bytes: None,
+        };
+
+        let dry_run = storage.prune_backups(&policy, true).unwrap();
+        assert_eq!(dry_run.len(), 3);
+        assert_eq!(storage.get_backups().unwrap().len(), 5);
+
+        storage.prune_backups(&policy, false).unwrap();
+        assert_eq!(storage.get_backups().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_delete_backup_rejects_outside_paths() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = FileStorage::with_base_path(dir.path().to_path_buf());
+
+        storage.save_all(&SnippetStore::default()).unwrap();
+
+        assert!(storage.delete_backup(&storage.storage_path()).is_err());
+        assert!(storage.storage_path().exists());
+    }
+
+    #[test]
+    fn test_restore_backup_keeps_copy_of_current_store() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = FileStorage::with_base_path(dir.path().to_path_buf());
+        storage.save(snippet("current")).unwrap();
+
+        let old = dir.path().join("old.yml");
+        fs::write(&old, "snippets: []\n").unwrap();
+        for backup in storage.get_backups().unwrap() {
+            fs::remove_file(backup).unwrap();
+        }
+
+        storage.restore_backup(&old).unwrap();
+
+        assert!(storage.load().unwrap().snippets.is_empty());
+        let backups = storage.get_backups().unwrap();
+        assert_eq!(backups.len(), 1);
+        assert!(fs::read_to_string(&backups[0]).unwrap().contains("current"));
+    }
+
+    #[test]
+    fn test_backups_in_the_same_second_are_all_kept() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = FileStorage::with_base_path(dir.path().to_path_buf());
+        storage.save(snippet("a")).unwrap();
+        storage.save(snippet("b")).unwrap();
+        let before = storage.get_backups().unwrap();
+        let contents: Vec<String> = before
+            .iter()
+            .map(|b| fs::read_to_string(b).unwrap())
+            .collect();
+
+        storage.restore_backup(&before[0]).unwrap();
+
+        let after = storage.get_backups().unwrap();
+        assert_eq!(after.len(), before.len() + 1);
+        for (backup, content) in before.iter().zip(&contents) {
+            assert_eq!(&fs::read_to_string(backup).unwrap(), content);
+        }
+    }
+
+    #[test]
+    fn test_backups_are_listed_newest_first() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = FileStorage::with_base_path(dir.path().to_path_buf());
+        let backup_dir = storage.backup_dir();
+        fs::create_dir_all(&backup_dir).unwrap();
+        let names = [
+            "2025-01-02T03-04-05Z.yml",
+            "2025-01-02T03-04-05Z_2.yml",
+            "2025-01-02T03-04-05Z_10.yml",
+            "2025-01-02T03-04-06Z.yml",
+        ];
+        for name in names {
+            fs::write(backup_dir.join(name), "snippets: []\n").unwrap();
+        }
+
+        let listed: Vec<String> = storage
+            .get_backups()
+            .unwrap()
+            .iter()
+            .map(|p| p.file_name().unwrap().to_string_lossy().to_string())
+            .collect();
+        assert_eq!(
+            listed,
+            vec![
+                "2025-01-02T03-04-06Z.yml",
+                "2025-01-02T03-04-05Z_10.yml",
+                "2025-01-02T03-04-05Z_2.yml",
+                "2025-01-02T03-04-05Z.yml",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_backup_timestamp_ignores_suffix() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = FileStorage::with_base_path(dir.path().to_path_buf());
+        let path = dir.path().join("2025-01-02T03-04-05Z_2.yml");
+        fs::write(&path, "snippets: []\n").unwrap();
+
+        let info = storage.backup_info(&path, false).unwrap();
+        assert_eq!(
+            info.timestamp,
+            Utc.with_ymd_and_hms(2025, 1, 2, 3, 4, 5).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_load_reports_corrupt_store_location() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = FileStorage::with_base_path(dir.path().to_path_buf());
+        fs::write(
+            storage.storage_path(),
+            "snippets:\n  - name: a\n   bad: [\n",
+        )
+        .unwrap();
+
+        match storage.load() {
+            Err(StorageError::Corrupt { path, line, .. }) => {
+                assert_eq!(path, storage.storage_path());
+                assert!(line.is_some());
+            }
+            other => panic!("expected corrupt store error, got {:?}", other.map(|_| ())),
+        }
     }
 }
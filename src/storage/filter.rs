@@ -1,26 +1,116 @@
+use std::cmp::Reverse;
+
+use nucleo_matcher::{
+    Config, Matcher, Utf32Str,
+    pattern::{CaseMatching, Normalization, Pattern},
+};
+
 use crate::models::{Snippet, SnippetStore};
 
 pub enum Filter {
     All,
-    Name(String),
     Tag(String),
 }
 
+pub struct FuzzyMatch {
+    pub snippet: Snippet,
+    pub score: u32,
+    pub name_indices: Vec<u32>,
+    pub description_indices: Vec<u32>,
+}
+
 pub fn apply_filter(store: &SnippetStore, filter: Filter) -> Vec<Snippet> {
     match filter {
         Filter::All => store.snippets.clone(),
-        Filter::Name(name) => get_by_name(store, &name),
         Filter::Tag(tag) => get_by_tag(store, &tag),
     }
 }
 
-fn get_by_name(store: &SnippetStore, name: &str) -> Vec<Snippet> {
-    store
+/// Scores every snippet against `query` across name, description, tags and
+/// content, best match first. Name matches weigh more than the other fields.
+pub fn fuzzy_rank(store: &SnippetStore, query: &str) -> Vec<FuzzyMatch> {
+    let mut matcher = Matcher::new(Config::DEFAULT);
+    let pattern = Pattern::parse(query, CaseMatching::Ignore, Normalization::Smart);
+    let mut buf = Vec::new();
+
+    let mut matches: Vec<FuzzyMatch> = store
         .snippets
         .iter()
-        .filter(|s| s.name.to_lowercase().contains(&name.to_lowercase()))
-        .cloned()
-        .collect()
+        .filter_map(|snippet| {
+            let haystack = format!(
+                "{} {} {} {}",
+                snippet.name,
+                snippet.description,
+                snippet.tags.join(" "),
+                snippet.content
+            );
+            let overall = pattern.score(Utf32Str::new(&haystack, &mut buf), &mut matcher)?;
+
+            let mut name_indices = Vec::new();
+            let name_score = pattern
+                .indices(
+                    Utf32Str::new(&snippet.name, &mut buf),
+                    &mut matcher,
+                    &mut name_indices,
+                )
+                .unwrap_or(0);
+            if name_score == 0 {
+                name_indices.clear();
+            }
+
+            let mut description_indices = Vec::new();
+            if pattern
+                .indices(
+                    Utf32Str::new(&snippet.description, &mut buf),
+                    &mut matcher,
+                    &mut description_indices,
+                )
+                .is_none()
+            {
+                description_indices.clear();
+            }
+
+            name_indices.sort_unstable();
+            name_indices.dedup();
+            description_indices.sort_unstable();
+            description_indices.dedup();
+
+            let exact_bonus = if snippet.name.eq_ignore_ascii_case(query.trim()) {
+                overall + name_score
+            } else {
+                0
+            };
+
+            Some(FuzzyMatch {
+                snippet: snippet.clone(),
+                score: overall + name_score * 2 + exact_bonus,
+                name_indices,
+                description_indices,
+            })
+        })
+        .collect();
+
+    matches.sort_by_key(|m| Reverse(m.score));
+    matches
+}
+
+/// Indices of the `items` matching `query`, best match first.
+pub fn fuzzy_filter(items: &[String], query: &str) -> Vec<usize> {
+    let mut matcher = Matcher::new(Config::DEFAULT);
+    let pattern = Pattern::parse(query, CaseMatching::Ignore, Normalization::Smart);
+    let mut buf = Vec::new();
+
+    let mut matches: Vec<(usize, u32)> = items
+        .iter()
+        .enumerate()
+        .filter_map(|(i, item)| {
+            let score = pattern.score(Utf32Str::new(item, &mut buf), &mut matcher)?;
+            Some((i, score))
+        })
+        .collect();
+
+    matches.sort_by_key(|&(_, score)| Reverse(score));
+    matches.into_iter().map(|(i, _)| i).collect()
 }
 
 fn get_by_tag(store: &SnippetStore, tag: &str) -> Vec<Snippet> {
@@ -35,3 +125,72 @@ fn get_by_tag(store: &SnippetStore, tag: &str) -> Vec<Snippet> {
         .cloned()
         .collect()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn snippet(name: &str, description: &str, content: &str, tags: &[&str]) -> Snippet {
+        Snippet {
+            id: Snippet::new_id(),
+            name: name.to_string(),
+            description: description.to_string(),
+            content: content.to_string(),
+            language: None,
+            executable: true,
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            revisions: vec![],
+        }
+    }
+
+    fn store() -> SnippetStore {
+        SnippetStore {
+            snippets: vec![
+                snippet("k8s-logs", "Tail pod logs", "kubectl logs -f", &["k8s"]),
+                snippet("docker-clean", "Prune images", "docker system prune", &[]),
+                snippet("git-undo", "Undo last commit", "git reset HEAD~1", &["git"]),
+            ],
+            trash: vec![],
+        }
+    }
+
+    #[test]
+    fn test_fuzzy_rank_orders_by_score() {
+        let results = fuzzy_rank(&store(), "dclean");
+
+        assert!(!results.is_empty());
+        assert_eq!(results[0].snippet.name, "docker-clean");
+        assert!(!results[0].name_indices.is_empty());
+    }
+
+    #[test]
+    fn test_fuzzy_rank_matches_content_and_tags() {
+        let by_content = fuzzy_rank(&store(), "reset HEAD");
+        assert_eq!(by_content.len(), 1);
+        assert_eq!(by_content[0].snippet.name, "git-undo");
+        assert!(by_content[0].name_indices.is_empty());
+
+        let by_tag = fuzzy_rank(&store(), "k8s");
+        assert_eq!(by_tag[0].snippet.name, "k8s-logs");
+    }
+
+    #[test]
+    fn test_fuzzy_rank_prefers_exact_name() {
+        let mut store = store();
+        store
+            .snippets
+            .push(snippet("logs", "Show logs", "journalctl -f", &["k8s-logs"]));
+
+        let results = fuzzy_rank(&store, "logs");
+        assert_eq!(results[0].snippet.name, "logs");
+    }
+
+    #[test]
+    fn test_fuzzy_filter_excludes_non_matches() {
+        let results = fuzzy_rank(&store(), "zzzz");
+        assert!(results.is_empty());
+    }
+}
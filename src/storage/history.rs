@@ -0,0 +1,266 @@
+use std::{
+    collections::BTreeMap,
+    fs::{self, File, OpenOptions},
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::storage::{HistoryLog, StorageError};
+
+/// One `run` of a snippet, with everything needed to replay it.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct HistoryEntry {
+    pub snippet: String,
+    /// Absent in entries written before snippets had ids.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub snippet_id: Option<String>,
+    /// The command after placeholders were substituted.
+    pub command: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub variables: BTreeMap<String, String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub args: Vec<String>,
+    /// Names of the variables set with `--env`. Their values may be secrets
+    /// and are not kept.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub env_keys: Vec<String>,
+    pub cwd: PathBuf,
+    pub started_at: DateTime<Utc>,
+    pub duration_ms: u64,
+    /// `None` when the command could not be started.
+    pub exit_code: Option<i32>,
+}
+
+impl HistoryEntry {
+    pub fn succeeded(&self) -> bool {
+        self.exit_code == Some(0)
+    }
+}
+
+/// History kept as one JSON object per line. Appends are serialised through
+/// a lock file next to the log, and the oldest entries are only rewritten
+/// away once the log is a tenth over its cap.
+pub struct FileHistoryLog {
+    path: PathBuf,
+    max_entries: usize,
+}
+
+impl FileHistoryLog {
+    pub fn new(path: PathBuf) -> Self {
+        Self {
+            path,
+            max_entries: 0,
+        }
+    }
+
+    /// Keeps only the latest `max_entries` runs; `0` keeps them all.
+    pub fn with_max_entries(mut self, max_entries: usize) -> Self {
+        self.max_entries = max_entries;
+        self
+    }
+
+    /// Blocks until no other markit process is appending to the log. The
+    /// lock is released when the returned file is dropped.
+    fn lock(&self) -> Result<File, StorageError> {
+        let file = OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(self.path.with_extension("lock"))?;
+        file.lock()?;
+        Ok(file)
+    }
+
+    fn drop_oldest(&self) -> Result<(), StorageError> {
+        let contents = fs::read_to_string(&self.path)?;
+        let lines: Vec<&str> = contents.lines().collect();
+        let margin = (self.max_entries / 10).max(1);
+        if lines.len() <= self.max_entries + margin {
+            return Ok(());
+        }
+
+        let kept = lines[lines.len() - self.max_entries..].join("\n") + "\n";
+        let dir = self.path.parent().unwrap_or(Path::new("."));
+        let mut tmp = tempfile::NamedTempFile::new_in(dir)?;
+        tmp.write_all(kept.as_bytes())?;
+        tmp.persist(&self.path)
+            .map_err(|e| StorageError::Io(e.error))?;
+        Ok(())
+    }
+}
+
+impl HistoryLog for FileHistoryLog {
+    fn append(&self, entry: &HistoryEntry) -> Result<(), StorageError> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let _lock = self.lock()?;
+
+        let line = serde_json::to_string(entry).map_err(std::io::Error::other)?;
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        writeln!(file, "{}", line)?;
+
+        if self.max_entries > 0 {
+            self.drop_oldest()?;
+        }
+        Ok(())
+    }
+
+    fn entries(&self) -> Result<Vec<HistoryEntry>, StorageError> {
+        let contents = match fs::read_to_string(&self.path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(vec![]),
+            Err(e) => return Err(StorageError::Io(e)),
+        };
+
+        // A line cut short by a crash should not hide the rest of the log.
+        let mut entries: Vec<HistoryEntry> = contents
+            .lines()
+            .filter_map(|line| serde_json::from_str(line).ok())
+            .collect();
+
+        // The file may run a little over the cap until it is next trimmed.
+        if self.max_entries > 0 && entries.len() > self.max_entries {
+            entries.drain(..entries.len() - self.max_entries);
+        }
+        Ok(entries)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(snippet: &str, exit_code: Option<i32>) -> HistoryEntry {
+        HistoryEntry {
+            snippet: snippet.to_string(),
+            snippet_id: None,
+            command: format!("echo {}", snippet),
+            language: None,
+            variables: BTreeMap::from([("env".to_string(), "prod".to_string())]),
+            args: vec![],
+            env_keys: vec!["TOKEN".to_string()],
+            cwd: PathBuf::from("/tmp"),
+            started_at: Utc::now(),
+            duration_ms: 12,
+            exit_code,
+        }
+    }
+
+    #[test]
+    fn test_append_and_read_back() {
+        let dir = tempfile::tempdir().unwrap();
+        let log = FileHistoryLog::new(dir.path().join("nested").join("history.jsonl"));
+
+        assert!(log.entries().unwrap().is_empty());
+
+        log.append(&entry("deploy", Some(0))).unwrap();
+        log.append(&entry("backup", Some(2))).unwrap();
+
+        let entries = log.entries().unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].snippet, "deploy");
+        assert!(entries[0].succeeded());
+        assert_eq!(entries[1].exit_code, Some(2));
+        assert_eq!(entries[1].variables["env"], "prod");
+    }
+
+    #[test]
+    fn test_skips_truncated_lines() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("history.jsonl");
+        let log = FileHistoryLog::new(path.clone());
+
+        log.append(&entry("deploy", Some(0))).unwrap();
+        let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+        write!(file, "{{\"snippet\": \"half").unwrap();
+
+        assert_eq!(log.entries().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_env_values_are_not_written() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("history.jsonl");
+        let log = FileHistoryLog::new(path.clone());
+
+        log.append(&entry("deploy", Some(0))).unwrap();
+
+        let line = fs::read_to_string(&path).unwrap();
+        assert!(line.contains("\"env_keys\":[\"TOKEN\"]"));
+        assert_eq!(log.entries().unwrap()[0].env_keys, vec!["TOKEN"]);
+    }
+
+    #[test]
+    fn test_old_entries_with_env_values_are_still_read() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("history.jsonl");
+        fs::write(
+            &path,
+            "{\"snippet\":\"deploy\",\"command\":\"deploy.sh\",\"env\":{\"TOKEN\":\"s3cret\"},\
+             \"cwd\":\"/tmp\",\"started_at\":\"2025-03-01T12:00:00Z\",\"duration_ms\":5,\"exit_code\":0}\n",
+        )
+        .unwrap();
+
+        let entries = FileHistoryLog::new(path).entries().unwrap();
+        assert_eq!(entries.len(), 1);
+        assert!(entries[0].env_keys.is_empty());
+    }
+
+    #[test]
+    fn test_max_entries_drops_the_oldest() {
+        let dir = tempfile::tempdir().unwrap();
+        let log = FileHistoryLog::new(dir.path().join("history.jsonl")).with_max_entries(2);
+
+        for name in ["a", "b", "c"] {
+            log.append(&entry(name, Some(0))).unwrap();
+        }
+
+        let names: Vec<String> = log
+            .entries()
+            .unwrap()
+            .into_iter()
+            .map(|e| e.snippet)
+            .collect();
+        assert_eq!(names, vec!["b", "c"]);
+
+        // The file is only rewritten once it is past the cap by a margin.
+        let lines = |log: &FileHistoryLog| fs::read_to_string(&log.path).unwrap().lines().count();
+        assert_eq!(lines(&log), 3);
+        log.append(&entry("d", Some(0))).unwrap();
+        assert_eq!(lines(&log), 2);
+    }
+
+    #[test]
+    fn test_concurrent_appends_are_all_kept() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("history.jsonl");
+
+        let writers: Vec<_> = (0..4)
+            .map(|_| {
+                let path = path.clone();
+                std::thread::spawn(move || {
+                    let log = FileHistoryLog::new(path).with_max_entries(30);
+                    for _ in 0..25 {
+                        log.append(&entry("deploy", Some(0))).unwrap();
+                    }
+                })
+            })
+            .collect();
+        for writer in writers {
+            writer.join().unwrap();
+        }
+
+        // Trimmed back to 30 whenever it passes 33 lines, so a lost line
+        // would leave fewer than the 32 that 100 appends end with.
+        assert_eq!(fs::read_to_string(&path).unwrap().lines().count(), 32);
+    }
+}
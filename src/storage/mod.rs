@@ -1,15 +1,50 @@
 use std::path::PathBuf;
-use std::{fmt, path::Path};
+use std::{fmt, fs::File, path::Path, rc::Rc};
 
 use crate::models::{Snippet, SnippetStore};
+use crate::storage::history::HistoryEntry;
+use crate::storage::retention::{BackupInfo, RetentionPolicy};
+use crate::storage::stores::StoreInfo;
+use crate::storage::usage::UsageStats;
 
 pub mod file_storage;
 pub mod filter;
+pub mod history;
+pub mod retention;
+pub mod stores;
+pub mod usage;
 
 #[derive(Debug)]
 pub enum StorageError {
     Io(std::io::Error),
     Serde(serde_yaml::Error),
+    Locked(PathBuf),
+    Corrupt {
+        path: PathBuf,
+        line: Option<usize>,
+        column: Option<usize>,
+        message: String,
+    },
+}
+
+impl StorageError {
+    pub fn corrupt(path: &Path, error: serde_yaml::Error) -> Self {
+        let location = error.location();
+        let mut message = error.to_string();
+        if let Some(l) = &location {
+            let suffix = format!(" at line {} column {}", l.line(), l.column());
+            if let Some(stripped) = message.strip_suffix(&suffix) {
+                message = stripped.to_string();
+            }
+        }
+
+        StorageError::Corrupt {
+            path: path.to_path_buf(),
+            line: location.as_ref().map(|l| l.line()),
+            column: location.as_ref().map(|l| l.column()),
+            message,
+        }
+    }
 }
 
 impl From<std::io::Error> for StorageError {
@@ -29,14 +64,88 @@ impl fmt::Display for StorageError {
         match self {
             StorageError::Io(e) => write!(f, "IO error: {}", e),
             StorageError::Serde(e) => write!(f, "Serialization error: {}", e),
+            StorageError::Locked(path) => write!(
+                f,
+                "Timed out waiting for lock '{}'; is another markit process running?",
+                path.display()
+            ),
+            StorageError::Corrupt {
+                path,
+                line,
+                column,
+                message,
+            } => match (line, column) {
+                (Some(line), Some(column)) => write!(
+                    f,
+                    "Snippet store '{}' is corrupt at line {}, column {}: {}",
+                    path.display(),
+                    line,
+                    column,
+                    message
+                ),
+                _ => write!(
+                    f,
+                    "Snippet store '{}' is corrupt: {}",
+                    path.display(),
+                    message
+                ),
+            },
         }
     }
 }
 
+/// Guard returned by [`Storage::lock`]; the lock is released when it is dropped.
+#[derive(Default)]
+pub struct StoreLock {
+    _file: Option<Rc<File>>,
+}
+
+impl StoreLock {
+    pub fn new(file: Rc<File>) -> Self {
+        Self { _file: Some(file) }
+    }
+}
+
 pub trait Storage {
     fn load(&self) -> Result<SnippetStore, StorageError>;
     fn save(&self, snippet: Snippet) -> Result<(), StorageError>;
     fn save_all(&self, store: &SnippetStore) -> Result<(), StorageError>;
     fn get_backups(&self) -> Result<Vec<PathBuf>, StorageError>;
     fn restore_backup(&self, path: &Path) -> Result<(), StorageError>;
+
+    /// Holds the store lock across a load-modify-write cycle.
+    fn lock(&self) -> Result<StoreLock, StorageError> {
+        Ok(StoreLock::default())
+    }
+
+    fn load_usage(&self) -> Result<UsageStats, StorageError> {
+        Ok(UsageStats::default())
+    }
+
+    /// Applies `update` to the usage statistics and saves them.
+    fn update_usage(&self, _update: &mut dyn FnMut(&mut UsageStats)) -> Result<(), StorageError> {
+        Ok(())
+    }
+}
+
+pub trait BackupManager {
+    fn get_backup_details(&self) -> Result<Vec<BackupInfo>, StorageError>;
+    fn prune_backups(
+        &self,
+        policy: &RetentionPolicy,
+        dry_run: bool,
+    ) -> Result<Vec<PathBuf>, StorageError>;
+    fn delete_backup(&self, path: &Path) -> Result<(), StorageError>;
+}
+
+pub trait StoreRegistry {
+    fn list_stores(&self) -> Result<Vec<StoreInfo>, StorageError>;
+    fn create_store(&self, name: &str) -> Result<PathBuf, StorageError>;
+    fn set_default_store(&self, name: &str) -> Result<(), StorageError>;
+}
+
+pub trait HistoryLog {
+    fn append(&self, entry: &HistoryEntry) -> Result<(), StorageError>;
+    /// All recorded runs, oldest first.
+    fn entries(&self) -> Result<Vec<HistoryEntry>, StorageError>;
 }
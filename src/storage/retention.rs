@@ -0,0 +1,269 @@
+use std::{cmp::Reverse, collections::HashSet, path::PathBuf};
+
+use chrono::{DateTime, Datelike, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+
+/// How many backups to keep. A backup survives pruning if any rule keeps it,
+/// and the most recent backup is always kept.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct RetentionPolicy {
+    pub keep_last: usize,
+    pub keep_daily: usize,
+    pub keep_weekly: usize,
+    pub keep_monthly: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_total_bytes: Option<u64>,
+}
+
+impl Default for RetentionPolicy {
+    fn default() -> Self {
+        Self {
+            keep_last: 20,
+            keep_daily: 7,
+            keep_weekly: 4,
+            keep_monthly: 6,
+            max_total_bytes: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct BackupInfo {
+    pub path: PathBuf,
+    pub timestamp: DateTime<Utc>,
+    pub size: u64,
+    pub snippet_count: Option<usize>,
+}
+
+impl BackupInfo {
+    pub fn name(&self) -> String {
+        self.path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default()
+    }
+}
+
+type PeriodKey = fn(&DateTime<Utc>) -> NaiveDate;
+
+/// Returns the backups `policy` does not keep. `backups` may be in any order.
+pub fn select_prunable(backups: &[BackupInfo], policy: &RetentionPolicy) -> Vec<PathBuf> {
+    let mut sorted: Vec<&BackupInfo> = backups.iter().collect();
+    sorted.sort_by_key(|b| Reverse(b.timestamp));
+
+    let mut keep: HashSet<usize> = (0..sorted.len().min(policy.keep_last.max(1))).collect();
+
+    let periods: [(usize, PeriodKey); 3] = [
+        (policy.keep_daily, |t| t.date_naive()),
+        (policy.keep_weekly, |t| {
+            let date = t.date_naive();
+            date - chrono::Days::new(date.weekday().num_days_from_monday() as u64)
+        }),
+        (policy.keep_monthly, |t| t.date_naive().with_day(1).unwrap()),
+    ];
+
+    for (count, period) in periods {
+        let mut seen: Vec<NaiveDate> = Vec::new();
+        for (i, backup) in sorted.iter().enumerate() {
+            let key = period(&backup.timestamp);
+            if seen.contains(&key) {
+                continue;
+            }
+            if seen.len() == count {
+                break;
+            }
+            seen.push(key);
+            keep.insert(i);
+        }
+    }
+
+    if let Some(max) = policy.max_total_bytes {
+        let mut total = 0;
+        for (i, backup) in sorted.iter().enumerate() {
+            if !keep.contains(&i) {
+                continue;
+            }
+            total += backup.size;
+            if total > max && i > 0 {
+                keep.remove(&i);
+            }
+        }
+    }
+
+    sorted
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| !keep.contains(i))
+        .map(|(_, b)| b.path.clone())
+        .collect()
+}
+
+/// Parses sizes such as `512`, `200K`, `50MB` or `1G` into bytes.
+pub fn parse_size(input: &str) -> Result<u64, String> {
+    let trimmed = input.trim().to_uppercase();
+    let trimmed = trimmed.trim_end_matches('B');
+    let (digits, multiplier) = match trimmed.chars().last() {
+        Some('K') => (&trimmed[..trimmed.len() - 1], 1024),
+        Some('M') => (&trimmed[..trimmed.len() - 1], 1024 * 1024),
+        Some('G') => (&trimmed[..trimmed.len() - 1], 1024 * 1024 * 1024),
+        _ => (trimmed, 1),
+    };
+
+    let n = digits
+        .trim()
+        .parse::<u64>()
+        .map_err(|_| format!("invalid size '{}'", input))?;
+    n.checked_mul(multiplier)
+        .ok_or_else(|| format!("size '{}' is too large", input))
+}
+
+pub fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KB", "MB", "GB"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+
+    if unit == 0 {
+        format!("{} {}", bytes, UNITS[0])
+    } else {
+        format!("{:.1} {}", size, UNITS[unit])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, TimeZone};
+
+    fn backups(timestamps: &[DateTime<Utc>]) -> Vec<BackupInfo> {
+        timestamps
+            .iter()
+            .map(|t| BackupInfo {
+                path: PathBuf::from(format!("{}.yml", t.format("%Y-%m-%dT%H-%M-%SZ"))),
+                timestamp: *t,
+                size: 100,
+                snippet_count: Some(1),
+            })
+            .collect()
+    }
+
+    fn keep_nothing() -> RetentionPolicy {
+        RetentionPolicy {
+            keep_last: 0,
+            keep_daily: 0,
+            keep_weekly: 0,
+            keep_monthly: 0,
+            max_total_bytes: None,
+        }
+    }
+
+    #[test]
+    fn test_keep_last() {
+        let now = Utc.with_ymd_and_hms(2025, 3, 10, 12, 0, 0).unwrap();
+        let list = backups(&[now, now - Duration::minutes(1), now - Duration::minutes(2)]);
+        let policy = RetentionPolicy {
+            keep_last: 2,
+            ..keep_nothing()
+        };
+
+        assert_eq!(select_prunable(&list, &policy), vec![list[2].path.clone()]);
+    }
+
+    #[test]
+    fn test_keep_daily_keeps_newest_per_day() {
+        let now = Utc.with_ymd_and_hms(2025, 3, 10, 12, 0, 0).unwrap();
+        let list = backups(&[
+            now,
+            now - Duration::hours(1),
+            now - Duration::days(1),
+            now - Duration::days(1) - Duration::hours(1),
+            now - Duration::days(2),
+        ]);
+        let policy = RetentionPolicy {
+            keep_daily: 2,
+            ..keep_nothing()
+        };
+
+        let pruned = select_prunable(&list, &policy);
+        assert_eq!(
+            pruned,
+            vec![
+                list[1].path.clone(),
+                list[3].path.clone(),
+                list[4].path.clone()
+            ]
+        );
+    }
+
+    #[test]
+    fn test_keep_weekly_and_monthly() {
+        let start = Utc.with_ymd_and_hms(2025, 3, 31, 12, 0, 0).unwrap();
+        let list = backups(
+            &(0..60)
+                .map(|d| start - Duration::days(d))
+                .collect::<Vec<_>>(),
+        );
+        let policy = RetentionPolicy {
+            keep_weekly: 2,
+            keep_monthly: 3,
+            ..keep_nothing()
+        };
+
+        let pruned = select_prunable(&list, &policy);
+        let kept: Vec<_> = list
+            .iter()
+            .filter(|b| !pruned.contains(&b.path))
+            .map(|b| b.timestamp.date_naive().to_string())
+            .collect();
+
+        assert_eq!(
+            kept,
+            vec!["2025-03-31", "2025-03-30", "2025-02-28", "2025-01-31"]
+        );
+    }
+
+    #[test]
+    fn test_max_total_bytes_always_keeps_newest() {
+        let now = Utc.with_ymd_and_hms(2025, 3, 10, 12, 0, 0).unwrap();
+        let list = backups(&[now, now - Duration::minutes(1), now - Duration::minutes(2)]);
+        let policy = RetentionPolicy {
+            keep_last: 3,
+            max_total_bytes: Some(150),
+            ..keep_nothing()
+        };
+
+        assert_eq!(
+            select_prunable(&list, &policy),
+            vec![list[1].path.clone(), list[2].path.clone()]
+        );
+
+        let tiny = RetentionPolicy {
+            max_total_bytes: Some(1),
+            ..policy
+        };
+        assert_eq!(select_prunable(&list, &tiny).len(), 2);
+    }
+
+    #[test]
+    fn test_parse_and_format_size() {
+        assert_eq!(parse_size("512"), Ok(512));
+        assert_eq!(parse_size("2K"), Ok(2048));
+        assert_eq!(parse_size("10MB"), Ok(10 * 1024 * 1024));
+        assert!(parse_size("lots").is_err());
+        assert_eq!(format_size(512), "512 B");
+        assert_eq!(format_size(1536), "1.5 KB");
+    }
+
+    #[test]
+    fn test_parse_size_rejects_overflow() {
+        assert_eq!(
+            parse_size("99999999999G"),
+            Err("size '99999999999G' is too large".to_string())
+        );
+        assert_eq!(parse_size("16777215G"), Ok(16777215 * 1024 * 1024 * 1024));
+    }
+}
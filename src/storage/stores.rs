@@ -0,0 +1,145 @@
+use std::{fs, path::PathBuf};
+
+use crate::{
+    config::Config,
+    paths::{DEFAULT_STORE, Paths, is_valid_store_name},
+    storage::{Storage, StorageError, StoreRegistry, file_storage::FileStorage},
+};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct StoreInfo {
+    pub name: String,
+    pub path: PathBuf,
+    pub snippet_count: Option<usize>,
+    pub is_default: bool,
+}
+
+pub struct FileStoreRegistry {
+    paths: Paths,
+    default_store: String,
+}
+
+impl FileStoreRegistry {
+    pub fn new(paths: Paths, default_store: Option<String>) -> Self {
+        Self {
+            paths,
+            default_store: default_store.unwrap_or_else(|| DEFAULT_STORE.to_string()),
+        }
+    }
+
+    fn info(&self, name: String) -> StoreInfo {
+        let path = self.paths.named_store_dir(&name);
+        let snippet_count = FileStorage::with_base_path(path.clone())
+            .load()
+            .ok()
+            .map(|store| store.snippets.len());
+
+        StoreInfo {
+            is_default: name == self.default_store,
+            name,
+            path,
+            snippet_count,
+        }
+    }
+}
+
+impl StoreRegistry for FileStoreRegistry {
+    fn list_stores(&self) -> Result<Vec<StoreInfo>, StorageError> {
+        let mut names: Vec<String> = match fs::read_dir(self.paths.stores_dir()) {
+            Ok(entries) => entries
+                .filter_map(|entry| {
+                    let entry = entry.ok()?;
+                    let name = entry.file_name().into_string().ok()?;
+                    (entry.path().is_dir() && is_valid_store_name(&name)).then_some(name)
+                })
+                .collect(),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => vec![],
+            Err(e) => return Err(StorageError::Io(e)),
+        };
+        names.sort();
+
+        Ok(std::iter::once(DEFAULT_STORE.to_string())
+            .chain(names)
+            .map(|name| self.info(name))
+            .collect())
+    }
+
+    fn create_store(&self, name: &str) -> Result<PathBuf, StorageError> {
+        if !is_valid_store_name(name) {
+            return Err(StorageError::Io(std::io::Error::other(format!(
+                "invalid store name '{}', use letters, digits, '-' and '_'",
+                name
+            ))));
+        }
+
+        let path = self.paths.named_store_dir(name);
+        if name == DEFAULT_STORE || path.exists() {
+            return Err(StorageError::Io(std::io::Error::other(format!(
+                "store '{}' already exists",
+                name
+            ))));
+        }
+
+        fs::create_dir_all(&path).map_err(StorageError::Io)?;
+        Ok(path)
+    }
+
+    fn set_default_store(&self, name: &str) -> Result<(), StorageError> {
+        let config_path = self.paths.config_file();
+        let mut config =
+            Config::load(&config_path).map_err(|e| StorageError::Io(std::io::Error::other(e)))?;
+
+        config.default_store = (name != DEFAULT_STORE).then(|| name.to_string());
+        config
+            .save(&config_path)
+            .map_err(|e| StorageError::Io(std::io::Error::other(e)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn registry(dir: &std::path::Path, default_store: Option<&str>) -> FileStoreRegistry {
+        FileStoreRegistry::new(
+            Paths {
+                data_dir: dir.to_path_buf(),
+                config_dir: dir.to_path_buf(),
+            },
+            default_store.map(str::to_string),
+        )
+    }
+
+    #[test]
+    fn test_create_and_list_stores() {
+        let dir = tempfile::tempdir().unwrap();
+        let registry = registry(dir.path(), Some("work"));
+
+        registry.create_store("work").unwrap();
+        assert!(registry.create_store("work").is_err());
+        assert!(registry.create_store("default").is_err());
+        assert!(registry.create_store("../escape").is_err());
+
+        let stores = registry.list_stores().unwrap();
+        let names: Vec<_> = stores.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["default", "work"]);
+        assert!(stores[1].is_default);
+        assert_eq!(stores[1].snippet_count, Some(0));
+    }
+
+    #[test]
+    fn test_set_default_store_keeps_other_settings() {
+        let dir = tempfile::tempdir().unwrap();
+        let registry = registry(dir.path(), None);
+        fs::write(dir.path().join("config.yml"), "backups:\n  keep_last: 3\n").unwrap();
+
+        registry.set_default_store("work").unwrap();
+        let config = Config::load(&dir.path().join("config.yml")).unwrap();
+        assert_eq!(config.default_store.as_deref(), Some("work"));
+        assert_eq!(config.backups.keep_last, 3);
+
+        registry.set_default_store("default").unwrap();
+        let config = Config::load(&dir.path().join("config.yml")).unwrap();
+        assert_eq!(config.default_store, None);
+    }
+}
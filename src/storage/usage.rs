@@ -0,0 +1,132 @@
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum UsageKind {
+    Run,
+    Copy,
+    Show,
+}
+
+/// How often and how recently a snippet was used.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(default)]
+pub struct Usage {
+    pub runs: u32,
+    pub copies: u32,
+    pub shows: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_used: Option<DateTime<Utc>>,
+}
+
+impl Usage {
+    pub fn total(&self) -> u32 {
+        self.runs + self.copies + self.shows
+    }
+
+    /// Use count weighted by how recently the snippet was last used, using the
+    /// same buckets as zoxide: the last hour counts four times, the last day
+    /// twice, the last week half and anything older a quarter.
+    pub fn frecency(&self, now: DateTime<Utc>) -> f64 {
+        let Some(last_used) = self.last_used else {
+            return 0.0;
+        };
+
+        let age = now - last_used;
+        let weight = if age < Duration::hours(1) {
+            4.0
+        } else if age < Duration::days(1) {
+            2.0
+        } else if age < Duration::weeks(1) {
+            0.5
+        } else {
+            0.25
+        };
+        self.total() as f64 * weight
+    }
+}
+
+/// Usage per snippet id, kept next to the store so using a snippet does not
+/// rewrite (and back up) the snippets file.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(transparent)]
+pub struct UsageStats(BTreeMap<String, Usage>);
+
+impl UsageStats {
+    pub fn get(&self, id: &str) -> Usage {
+        self.0.get(id).cloned().unwrap_or_default()
+    }
+
+    pub fn record(&mut self, id: &str, kind: UsageKind, now: DateTime<Utc>) {
+        let usage = self.0.entry(id.to_string()).or_default();
+        match kind {
+            UsageKind::Run => usage.runs += 1,
+            UsageKind::Copy => usage.copies += 1,
+            UsageKind::Show => usage.shows += 1,
+        }
+        usage.last_used = Some(now);
+    }
+
+    pub fn remove(&mut self, id: &str) {
+        self.0.remove(id);
+    }
+
+    /// Sorts `items` by descending frecency. The sort is stable, so items that
+    /// score the same keep their order, e.g. the fuzzy match ranking.
+    pub fn sort_by_frecency<T>(&self, items: &mut [T], id: impl Fn(&T) -> &str) {
+        let now = Utc::now();
+        items.sort_by(|a, b| {
+            let a = self.get(id(a)).frecency(now);
+            let b = self.get(id(b)).frecency(now);
+            b.total_cmp(&a)
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_counts_each_kind() {
+        let mut stats = UsageStats::default();
+        let now = Utc::now();
+
+        stats.record("deploy", UsageKind::Run, now);
+        stats.record("deploy", UsageKind::Run, now);
+        stats.record("deploy", UsageKind::Copy, now);
+
+        let usage = stats.get("deploy");
+        assert_eq!((usage.runs, usage.copies, usage.shows), (2, 1, 0));
+        assert_eq!(usage.total(), 3);
+        assert_eq!(usage.last_used, Some(now));
+        assert_eq!(stats.get("unknown"), Usage::default());
+    }
+
+    #[test]
+    fn test_recent_use_outranks_old_frequent_use() {
+        let now = Utc::now();
+        let mut stats = UsageStats::default();
+        for _ in 0..6 {
+            stats.record("old", UsageKind::Run, now - Duration::days(30));
+        }
+        stats.record("today", UsageKind::Run, now - Duration::minutes(5));
+
+        let mut names = vec!["never", "old", "today"];
+        stats.sort_by_frecency(&mut names, |n| n);
+
+        assert_eq!(names, vec!["today", "old", "never"]);
+    }
+
+    #[test]
+    fn test_remove() {
+        let mut stats = UsageStats::default();
+        stats.record("a", UsageKind::Show, Utc::now());
+        assert_eq!(stats.get("a").shows, 1);
+
+        stats.remove("a");
+        assert_eq!(stats.get("a").total(), 0);
+    }
+}
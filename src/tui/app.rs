@@ -0,0 +1,288 @@
+use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+use crate::{
+    models::{Snippet, SnippetStore},
+    storage::filter::{FuzzyMatch, fuzzy_rank},
+};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Action {
+    Run(String),
+    Copy(String),
+    Edit(String),
+    Delete(String),
+    Quit,
+}
+
+/// State of the TUI: the snippets, the filter typed so far and the selection.
+pub struct App {
+    snippets: Vec<Snippet>,
+    visible: Vec<FuzzyMatch>,
+    pub query: String,
+    pub tag: Option<String>,
+    pub selected: usize,
+}
+
+impl App {
+    pub fn new(snippets: Vec<Snippet>) -> Self {
+        let mut app = Self {
+            snippets,
+            visible: vec![],
+            query: String::new(),
+            tag: None,
+            selected: 0,
+        };
+        app.refresh();
+        app
+    }
+
+    /// Replaces the snippets after an action changed the store, keeping the
+    /// selection on the same snippet when it still exists.
+    pub fn set_snippets(&mut self, snippets: Vec<Snippet>) {
+        let current = self.selected_snippet().map(|s| s.name.clone());
+        self.snippets = snippets;
+        self.refresh();
+
+        if let Some(name) = current
+            && let Some(index) = self.visible.iter().position(|m| m.snippet.name == name)
+        {
+            self.selected = index;
+        }
+    }
+
+    pub fn visible(&self) -> &[FuzzyMatch] {
+        &self.visible
+    }
+
+    pub fn selected_snippet(&self) -> Option<&Snippet> {
+        self.visible.get(self.selected).map(|m| &m.snippet)
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) -> Option<Action> {
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+
+        match key.code {
+            KeyCode::Esc => return Some(Action::Quit),
+            KeyCode::Char('c') if ctrl => return Some(Action::Quit),
+            KeyCode::Enter => return self.selected_name().map(Action::Run),
+            KeyCode::Char('y') if ctrl => return self.selected_name().map(Action::Copy),
+            KeyCode::Char('e') if ctrl => return self.selected_name().map(Action::Edit),
+            KeyCode::Char('d') if ctrl => return self.selected_name().map(Action::Delete),
+            KeyCode::Char('t') if ctrl => self.cycle_tag(),
+            KeyCode::Up => self.move_selection(-1),
+            KeyCode::Char('p') if ctrl => self.move_selection(-1),
+            KeyCode::Down => self.move_selection(1),
+            KeyCode::Char('n') if ctrl => self.move_selection(1),
+            KeyCode::Backspace => {
+                self.query.pop();
+                self.refresh();
+            }
+            KeyCode::Char(c) if !ctrl => {
+                self.query.push(c);
+                self.refresh();
+            }
+            _ => {}
+        }
+
+        None
+    }
+
+    /// All tags in use, sorted.
+    pub fn tags(&self) -> Vec<String> {
+        let mut tags: Vec<String> = self
+            .snippets
+            .iter()
+            .flat_map(|s| s.tags.iter().cloned())
+            .collect();
+        tags.sort();
+        tags.dedup();
+        tags
+    }
+
+    fn selected_name(&self) -> Option<String> {
+        self.selected_snippet().map(|s| s.name.clone())
+    }
+
+    fn move_selection(&mut self, delta: isize) {
+        if self.visible.is_empty() {
+            return;
+        }
+        let last = self.visible.len() as isize - 1;
+        self.selected = (self.selected as isize + delta).clamp(0, last) as usize;
+    }
+
+    /// Steps through the tags in use, then back to showing every snippet.
+    fn cycle_tag(&mut self) {
+        let tags = self.tags();
+        self.tag = match &self.tag {
+            None => tags.first().cloned(),
+            Some(current) => tags
+                .iter()
+                .position(|t| t == current)
+                .and_then(|i| tags.get(i + 1).cloned()),
+        };
+        self.refresh();
+    }
+
+    fn refresh(&mut self) {
+        let snippets: Vec<Snippet> = self
+            .snippets
+            .iter()
+            .filter(|s| self.tag.as_ref().is_none_or(|tag| s.tags.contains(tag)))
+            .cloned()
+            .collect();
+
+        self.visible = if self.query.trim().is_empty() {
+            snippets
+                .into_iter()
+                .map(|snippet| FuzzyMatch {
+                    snippet,
+                    score: 0,
+                    name_indices: vec![],
+                    description_indices: vec![],
+                })
+                .collect()
+        } else {
+            fuzzy_rank(
+                &SnippetStore {
+                    snippets,
+                    trash: vec![],
+                },
+                &self.query,
+            )
+        };
+
+        self.selected = self.selected.min(self.visible.len().saturating_sub(1));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn snippet(name: &str, tags: &[&str]) -> Snippet {
+        Snippet {
+            id: Snippet::new_id(),
+            name: name.to_string(),
+            description: "desc".to_string(),
+            content: format!("echo {}", name),
+            language: None,
+            executable: true,
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            revisions: vec![],
+        }
+    }
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn ctrl(c: char) -> KeyEvent {
+        KeyEvent::new(KeyCode::Char(c), KeyModifiers::CONTROL)
+    }
+
+    fn app() -> App {
+        App::new(vec![
+            snippet("docker-clean", &["docker"]),
+            snippet("git-undo", &["git"]),
+            snippet("docker-logs", &["docker", "logs"]),
+        ])
+    }
+
+    #[test]
+    fn test_typing_filters_as_you_go() {
+        let mut app = app();
+
+        for c in "gitun".chars() {
+            app.handle_key(key(KeyCode::Char(c)));
+        }
+        assert_eq!(app.selected_snippet().unwrap().name, "git-undo");
+
+        for _ in 0..5 {
+            app.handle_key(key(KeyCode::Backspace));
+        }
+        assert_eq!(app.visible().len(), 3);
+    }
+
+    #[test]
+    fn test_selection_moves_within_bounds() {
+        let mut app = app();
+
+        app.handle_key(key(KeyCode::Up));
+        assert_eq!(app.selected, 0);
+
+        for _ in 0..5 {
+            app.handle_key(key(KeyCode::Down));
+        }
+        assert_eq!(app.selected, 2);
+        assert_eq!(
+            app.handle_key(key(KeyCode::Enter)),
+            Some(Action::Run("docker-logs".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_action_keys() {
+        let mut app = app();
+
+        assert_eq!(
+            app.handle_key(ctrl('y')),
+            Some(Action::Copy("docker-clean".to_string()))
+        );
+        assert_eq!(
+            app.handle_key(ctrl('e')),
+            Some(Action::Edit("docker-clean".to_string()))
+        );
+        assert_eq!(
+            app.handle_key(ctrl('d')),
+            Some(Action::Delete("docker-clean".to_string()))
+        );
+        assert_eq!(app.handle_key(key(KeyCode::Esc)), Some(Action::Quit));
+    }
+
+    #[test]
+    fn test_tag_filter_cycles() {
+        let mut app = app();
+
+        app.handle_key(ctrl('t'));
+        assert_eq!(app.tag.as_deref(), Some("docker"));
+        assert_eq!(app.visible().len(), 2);
+
+        app.handle_key(ctrl('t'));
+        app.handle_key(ctrl('t'));
+        assert_eq!(app.tag.as_deref(), Some("logs"));
+        assert_eq!(app.visible().len(), 1);
+
+        app.handle_key(ctrl('t'));
+        assert_eq!(app.tag, None);
+        assert_eq!(app.visible().len(), 3);
+    }
+
+    #[test]
+    fn test_no_action_without_matches() {
+        let mut app = app();
+
+        for c in "zzzz".chars() {
+            app.handle_key(key(KeyCode::Char(c)));
+        }
+
+        assert!(app.visible().is_empty());
+        assert_eq!(app.handle_key(key(KeyCode::Enter)), None);
+    }
+
+    #[test]
+    fn test_set_snippets_keeps_selection() {
+        let mut app = app();
+        app.handle_key(key(KeyCode::Down));
+
+        app.set_snippets(vec![
+            snippet("git-undo", &["git"]),
+            snippet("docker-logs", &["docker"]),
+        ]);
+
+        assert_eq!(app.selected_snippet().unwrap().name, "git-undo");
+    }
+}
@@ -0,0 +1,77 @@
+use std::io::{self, BufRead, Write};
+
+use ratatui::{
+    DefaultTerminal,
+    crossterm::{
+        event::{self, Event, KeyEventKind},
+        execute,
+        terminal::{EnterAlternateScreen, enable_raw_mode},
+    },
+};
+
+use crate::models::Snippet;
+
+pub mod app;
+pub mod view;
+
+use app::{Action, App};
+
+/// Carries out the actions chosen in the TUI. Each action runs with the
+/// terminal restored, so it may print and prompt like a regular command.
+pub trait ActionHandler {
+    fn handle(&mut self, action: &Action);
+    fn reload(&self) -> Option<Vec<Snippet>>;
+}
+
+pub fn run(snippets: Vec<Snippet>, handler: &mut dyn ActionHandler) -> io::Result<()> {
+    let mut terminal = ratatui::try_init()?;
+    let result = event_loop(&mut terminal, App::new(snippets), handler);
+    ratatui::restore();
+    result
+}
+
+fn event_loop(
+    terminal: &mut DefaultTerminal,
+    mut app: App,
+    handler: &mut dyn ActionHandler,
+) -> io::Result<()> {
+    loop {
+        terminal.draw(|frame| view::draw(frame, &app))?;
+
+        let Event::Key(key) = event::read()? else {
+            continue;
+        };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+
+        match app.handle_key(key) {
+            None => {}
+            Some(Action::Quit) => return Ok(()),
+            Some(action) => {
+                ratatui::restore();
+                handler.handle(&action);
+                wait_for_enter()?;
+                resume(terminal)?;
+
+                match handler.reload() {
+                    Some(snippets) => app.set_snippets(snippets),
+                    None => return Ok(()),
+                }
+            }
+        }
+    }
+}
+
+fn wait_for_enter() -> io::Result<()> {
+    print!("\n⏎ Press Enter to return to markit...");
+    io::stdout().flush()?;
+    io::stdin().lock().read_line(&mut String::new())?;
+    Ok(())
+}
+
+fn resume(terminal: &mut DefaultTerminal) -> io::Result<()> {
+    enable_raw_mode()?;
+    execute!(io::stdout(), EnterAlternateScreen)?;
+    terminal.clear()
+}
@@ -0,0 +1,123 @@
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Modifier, Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Wrap},
+};
+
+use crate::{storage::filter::FuzzyMatch, tui::app::App};
+
+const HELP: &str = "Enter run · ^Y copy · ^E edit · ^D delete · ^T tag · ↑/↓ move · Esc quit";
+
+pub fn draw(frame: &mut Frame, app: &App) {
+    let [search, body, help] = Layout::vertical([
+        Constraint::Length(3),
+        Constraint::Min(0),
+        Constraint::Length(1),
+    ])
+    .areas(frame.area());
+    let [list, preview] =
+        Layout::horizontal([Constraint::Percentage(40), Constraint::Percentage(60)]).areas(body);
+
+    draw_search(frame, app, search);
+    draw_list(frame, app, list);
+    draw_preview(frame, app, preview);
+    frame.render_widget(Line::from(HELP).dark_gray(), help);
+}
+
+fn draw_search(frame: &mut Frame, app: &App, area: Rect) {
+    let title = match &app.tag {
+        Some(tag) => format!(" Filter · tag: {} ", tag),
+        None => " Filter ".to_string(),
+    };
+
+    let input = Paragraph::new(format!("🔍 {}", app.query))
+        .block(Block::default().borders(Borders::ALL).title(title));
+    frame.render_widget(input, area);
+}
+
+fn draw_list(frame: &mut Frame, app: &App, area: Rect) {
+    let items: Vec<ListItem> = app.visible().iter().map(list_item).collect();
+    let title = format!(" Snippets ({}) ", app.visible().len());
+
+    let list = List::new(items)
+        .block(Block::default().borders(Borders::ALL).title(title))
+        .highlight_style(Style::default().bg(Color::DarkGray))
+        .highlight_symbol("▶ ");
+
+    let mut state = ListState::default();
+    if !app.visible().is_empty() {
+        state.select(Some(app.selected));
+    }
+    frame.render_stateful_widget(list, area, &mut state);
+}
+
+fn list_item(m: &FuzzyMatch) -> ListItem<'static> {
+    let mut spans: Vec<Span> = m
+        .snippet
+        .name
+        .chars()
+        .enumerate()
+        .map(|(i, c)| {
+            if m.name_indices.binary_search(&(i as u32)).is_ok() {
+                Span::styled(
+                    c.to_string(),
+                    Style::default()
+                        .fg(Color::Yellow)
+                        .add_modifier(Modifier::BOLD),
+                )
+            } else {
+                Span::raw(c.to_string())
+            }
+        })
+        .collect();
+
+    if !m.snippet.tags.is_empty() {
+        spans.push(Span::raw(format!("  {}", m.snippet.tags.join(", "))).dark_gray());
+    }
+
+    ListItem::new(Line::from(spans))
+}
+
+fn draw_preview(frame: &mut Frame, app: &App, area: Rect) {
+    let block = Block::default().borders(Borders::ALL).title(" Preview ");
+
+    let Some(snippet) = app.selected_snippet() else {
+        frame.render_widget(
+            Paragraph::new("📭 No matching snippets.").block(block),
+            area,
+        );
+        return;
+    };
+
+    let field = |label: &'static str, value: String| {
+        Line::from(vec![Span::raw(label).cyan(), Span::raw(value)])
+    };
+
+    let mut lines = vec![
+        Line::from(snippet.name.clone()).bold(),
+        field("Description: ", snippet.description.clone()),
+        field("Tags:        ", snippet.tags.join(", ")),
+        field(
+            "Language:    ",
+            snippet
+                .language
+                .clone()
+                .unwrap_or_else(|| "shell".to_string()),
+        ),
+        field(
+            "Executable:  ",
+            if snippet.executable { "yes" } else { "no" }.to_string(),
+        ),
+        field("Created at:  ", snippet.created_at.to_string()),
+        field("Updated at:  ", snippet.updated_at.to_string()),
+        Line::from(""),
+    ];
+    lines.extend(snippet.content.lines().map(|l| Line::from(l.to_string())));
+
+    let preview = Paragraph::new(lines)
+        .block(block)
+        .wrap(Wrap { trim: false });
+    frame.render_widget(preview, area);
+}
@@ -1,14 +1,24 @@
 use dialoguer::Confirm;
 
-use crate::ui::ConfirmPrompt;
+use crate::{config::ConfirmConfig, ui::ConfirmPrompt};
 
-pub struct DialoguerConfirm;
+pub struct DialoguerConfirm {
+    default: bool,
+}
+
+impl DialoguerConfirm {
+    pub fn new(config: &ConfirmConfig) -> Self {
+        Self {
+            default: config.default,
+        }
+    }
+}
 
 impl ConfirmPrompt for DialoguerConfirm {
     fn confirm(&self, message: &str) -> bool {
         Confirm::new()
             .with_prompt(message)
-            .default(false)
+            .default(self.default)
             .interact()
             .unwrap_or(false)
     }
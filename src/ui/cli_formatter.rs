@@ -0,0 +1,155 @@
+use crate::{
+    models::Snippet,
+    ui::{OutputFormat, OutputFormatter},
+};
+
+const COLUMNS: [&str; 9] = [
+    "id",
+    "name",
+    "description",
+    "content",
+    "language",
+    "executable",
+    "tags",
+    "created_at",
+    "updated_at",
+];
+
+pub struct CliFormatter {
+    format: OutputFormat,
+}
+
+impl CliFormatter {
+    pub fn new(format: OutputFormat) -> Self {
+        Self { format }
+    }
+
+    fn fields(snippet: &Snippet) -> [String; 9] {
+        [
+            snippet.id.clone(),
+            snippet.name.clone(),
+            snippet.description.clone(),
+            snippet.content.clone(),
+            snippet.language.clone().unwrap_or_default(),
+            snippet.executable.to_string(),
+            snippet.tags.join(","),
+            snippet.created_at.to_rfc3339(),
+            snippet.updated_at.to_rfc3339(),
+        ]
+    }
+
+    fn csv_field(value: &str) -> String {
+        if value.contains([',', '"', '\n', '\r']) {
+            format!("\"{}\"", value.replace('"', "\"\""))
+        } else {
+            value.to_string()
+        }
+    }
+
+    fn tsv_field(value: &str) -> String {
+        value
+            .replace('\\', "\\\\")
+            .replace('\t', "\\t")
+            .replace('\n', "\\n")
+            .replace('\r', "\\r")
+    }
+
+    fn delimited(&self, snippets: &[Snippet]) -> String {
+        let (separator, escape): (&str, fn(&str) -> String) = match self.format {
+            OutputFormat::Tsv => ("\t", Self::tsv_field),
+            _ => (",", Self::csv_field),
+        };
+
+        let mut lines = vec![COLUMNS.join(separator)];
+        for snippet in snippets {
+            let row: Vec<String> = Self::fields(snippet).iter().map(|f| escape(f)).collect();
+            lines.push(row.join(separator));
+        }
+
+        lines.join("\n")
+    }
+}
+
+impl OutputFormatter for CliFormatter {
+    fn format_snippets(&self, snippets: &[Snippet]) -> Result<String, String> {
+        match self.format {
+            OutputFormat::Json => serde_json::to_string_pretty(snippets).map_err(|e| e.to_string()),
+            OutputFormat::Yaml => serde_yaml::to_string(snippets).map_err(|e| e.to_string()),
+            OutputFormat::Csv | OutputFormat::Tsv => Ok(self.delimited(snippets)),
+            OutputFormat::Table => Err("Table output is rendered by the table UI.".to_string()),
+        }
+    }
+
+    fn format_snippet(&self, snippet: &Snippet) -> Result<String, String> {
+        match self.format {
+            OutputFormat::Json => serde_json::to_string_pretty(snippet).map_err(|e| e.to_string()),
+            OutputFormat::Yaml => serde_yaml::to_string(snippet).map_err(|e| e.to_string()),
+            _ => self.format_snippets(std::slice::from_ref(snippet)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeZone, Utc};
+
+    fn snippet() -> Snippet {
+        let timestamp = Utc.with_ymd_and_hms(2025, 1, 2, 3, 4, 5).unwrap();
+        Snippet {
+            id: "6f1c2a9e-3b7d-4e0a-9c5b-2d8e4f7a1b3c".to_string(),
+            name: "greet".to_string(),
+            description: "Say \"hi\", loudly".to_string(),
+            content: "echo hi\techo there\n".to_string(),
+            language: None,
+            executable: true,
+            tags: vec!["a".to_string(), "b".to_string()],
+            created_at: timestamp,
+            updated_at: timestamp,
+            revisions: vec![],
+        }
+    }
+
+    #[test]
+    fn test_format_json_includes_timestamps() {
+        let output = CliFormatter::new(OutputFormat::Json)
+            .format_snippets(&[snippet()])
+            .unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&output).unwrap();
+
+        assert_eq!(parsed[0]["name"], "greet");
+        assert_eq!(parsed[0]["created_at"], "2025-01-02T03:04:05Z");
+    }
+
+    #[test]
+    fn test_format_csv_quotes_fields() {
+        let output = CliFormatter::new(OutputFormat::Csv)
+            .format_snippets(&[snippet()])
+            .unwrap();
+
+        assert_eq!(
+            output,
+            "id,name,description,content,language,executable,tags,created_at,updated_at\n\
+             6f1c2a9e-3b7d-4e0a-9c5b-2d8e4f7a1b3c,greet,\"Say \"\"hi\"\", loudly\",\"echo hi\techo there\n\",,true,\"a,b\",\
+             2025-01-02T03:04:05+00:00,2025-01-02T03:04:05+00:00"
+        );
+    }
+
+    #[test]
+    fn test_format_tsv_escapes_control_characters() {
+        let snippet = Snippet {
+            language: Some("python".to_string()),
+            ..snippet()
+        };
+        let output = CliFormatter::new(OutputFormat::Tsv)
+            .format_snippet(&snippet)
+            .unwrap();
+        let row = output.lines().nth(1).unwrap();
+
+        let fields: Vec<&str> = row.split('\t').collect();
+
+        assert_eq!(fields[0], "6f1c2a9e-3b7d-4e0a-9c5b-2d8e4f7a1b3c");
+        assert_eq!(fields[3], "echo hi\\techo there\\n");
+        assert_eq!(fields[4], "python");
+    }
+}
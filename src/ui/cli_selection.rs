@@ -1,4 +1,6 @@
-use dialoguer::{Select, theme::ColorfulTheme};
+use std::io::IsTerminal;
+
+use dialoguer::{FuzzySelect, MultiSelect, Select, theme::ColorfulTheme};
 
 use crate::{models::Snippet, ui::SelectionUI};
 
@@ -19,10 +21,6 @@ impl CliSelection {
 
 impl SelectionUI for CliSelection {
     fn with_snippet_list(&self, snippets: Vec<Snippet>) -> Option<Snippet> {
-        if snippets.len() == 1 {
-            return snippets.first().cloned();
-        }
-
         let options: Vec<&str> = snippets.iter().map(|s| s.name.as_str()).collect();
 
         let selection = Select::with_theme(&ColorfulTheme::default())
@@ -43,4 +41,26 @@ impl SelectionUI for CliSelection {
             .interact()
             .ok()
     }
+
+    fn with_multi_select(&self, prompt: &str, items: &[String]) -> Vec<usize> {
+        MultiSelect::with_theme(&ColorfulTheme::default())
+            .with_prompt(prompt)
+            .items(items)
+            .interact()
+            .unwrap_or_default()
+    }
+
+    fn with_fuzzy_select(&self, prompt: &str, items: &[String]) -> Option<usize> {
+        FuzzySelect::with_theme(&ColorfulTheme::default())
+            .with_prompt(prompt)
+            .items(items)
+            .default(0)
+            .interact_opt()
+            .ok()
+            .flatten()
+    }
+
+    fn is_interactive(&self) -> bool {
+        std::io::stdin().is_terminal() && std::io::stderr().is_terminal()
+    }
 }
@@ -1,51 +1,245 @@
-use crate::{models::Snippet, ui::TableUI};
+use crate::{
+    config::{TableColumn, TableConfig},
+    models::Snippet,
+    storage::{
+        filter::FuzzyMatch,
+        history::HistoryEntry,
+        retention::{BackupInfo, format_size},
+        usage::{Usage, UsageStats},
+    },
+    ui::TableUI,
+};
 use comfy_table::{Cell, Color, Row, Table, presets::UTF8_FULL};
+use std::io::IsTerminal;
 
 pub struct CliTable {
     table: Table,
     header_color: Color,
+    text_color: Color,
+    columns: Vec<TableColumn>,
 }
 
 impl CliTable {
-    pub fn new() -> Self {
+    pub fn new(config: &TableConfig) -> Self {
         let mut table = Table::new();
         table.load_preset(UTF8_FULL);
 
-        let header_color = Color::Rgb {
-            r: 100,
-            g: 255,
-            b: 255,
-        };
-
         Self {
             table,
-            header_color,
+            header_color: parse_color(&config.header_color).unwrap_or(Color::Cyan),
+            text_color: parse_color(&config.text_color).unwrap_or(Color::White),
+            columns: config.columns.clone(),
+        }
+    }
+
+    fn header(&self, titles: &[&str]) -> Vec<Cell> {
+        titles
+            .iter()
+            .map(|title| Cell::new(title).fg(self.header_color))
+            .collect()
+    }
+
+    fn column_title(column: TableColumn) -> &'static str {
+        match column {
+            TableColumn::Name => "Name",
+            TableColumn::Description => "Description",
+            TableColumn::Executable => "Executable",
+            TableColumn::CreatedAt => "Created at",
+            TableColumn::UpdatedAt => "Updated at",
+            TableColumn::Tags => "Tags",
+            TableColumn::Runs => "Runs",
+            TableColumn::Copies => "Copies",
+            TableColumn::Shows => "Shows",
+            TableColumn::LastUsed => "Last used",
+        }
+    }
+
+    fn column_value(column: TableColumn, snippet: &Snippet, usage: &Usage) -> String {
+        match column {
+            TableColumn::Name => snippet.name.clone(),
+            TableColumn::Description => snippet.description.clone(),
+            TableColumn::Executable => yes_no(snippet.executable).to_string(),
+            TableColumn::CreatedAt => snippet.created_at.to_string(),
+            TableColumn::UpdatedAt => snippet.updated_at.to_string(),
+            TableColumn::Tags => snippet.tags.join(", "),
+            TableColumn::Runs => usage.runs.to_string(),
+            TableColumn::Copies => usage.copies.to_string(),
+            TableColumn::Shows => usage.shows.to_string(),
+            TableColumn::LastUsed => match usage.last_used {
+                Some(last_used) => last_used
+                    .with_timezone(&chrono::Local)
+                    .format("%Y-%m-%d %H:%M:%S")
+                    .to_string(),
+                None => "never".to_string(),
+            },
+        }
+    }
+
+    fn highlight(text: &str, indices: &[u32]) -> String {
+        if indices.is_empty() || !std::io::stdout().is_terminal() {
+            return text.to_string();
         }
+
+        text.chars()
+            .enumerate()
+            .map(|(i, c)| {
+                if indices.binary_search(&(i as u32)).is_ok() {
+                    format!("\x1b[1;33m{}\x1b[0m", c)
+                } else {
+                    c.to_string()
+                }
+            })
+            .collect()
     }
 }
 
 impl TableUI for CliTable {
-    fn with_snippet_list(&mut self, snippets: Vec<Snippet>) -> Table {
-        self.table.set_header(vec![
-            Cell::new("Name").fg(self.header_color),
-            Cell::new("Description").fg(self.header_color),
-            Cell::new("Executable").fg(self.header_color),
-            Cell::new("Created at").fg(self.header_color),
-            Cell::new("Updated at").fg(self.header_color),
-            Cell::new("Tags").fg(self.header_color),
-        ]);
+    fn with_snippet_list(&mut self, snippets: Vec<Snippet>, usage: &UsageStats) -> Table {
+        let titles: Vec<&str> = self
+            .columns
+            .iter()
+            .map(|c| Self::column_title(*c))
+            .collect();
+        self.table.set_header(self.header(&titles));
 
         for snippet in snippets {
+            let usage = usage.get(&snippet.id);
+            self.table.add_row(Row::from(
+                self.columns
+                    .iter()
+                    .map(|c| {
+                        Cell::new(Self::column_value(*c, &snippet, &usage)).fg(self.text_color)
+                    })
+                    .collect::<Vec<_>>(),
+            ));
+        }
+
+        self.table.clone()
+    }
+
+    fn with_search_results(&mut self, results: Vec<FuzzyMatch>) -> Table {
+        self.table
+            .set_header(self.header(&["Score", "Name", "Description", "Executable", "Tags"]));
+
+        for result in results {
+            let snippet = result.snippet;
             self.table.add_row(Row::from(vec![
-                Cell::new(snippet.name).fg(Color::White),
-                Cell::new(snippet.description).fg(Color::White),
-                Cell::new(if snippet.executable { "yes" } else { "no" }).fg(Color::White),
-                Cell::new(snippet.created_at).fg(Color::White),
-                Cell::new(snippet.updated_at).fg(Color::White),
-                Cell::new(snippet.tags.join(", ")).fg(Color::White),
+                Cell::new(result.score).fg(self.text_color),
+                Cell::new(Self::highlight(&snippet.name, &result.name_indices)).fg(self.text_color),
+                Cell::new(Self::highlight(
+                    &snippet.description,
+                    &result.description_indices,
+                ))
+                .fg(self.text_color),
+                Cell::new(yes_no(snippet.executable)).fg(self.text_color),
+                Cell::new(snippet.tags.join(", ")).fg(self.text_color),
             ]));
         }
 
         self.table.clone()
     }
+
+    fn with_backup_list(&mut self, backups: Vec<BackupInfo>) -> Table {
+        self.table
+            .set_header(self.header(&["Backup", "Created at", "Snippets", "Size"]));
+
+        for backup in backups {
+            let count = match backup.snippet_count {
+                Some(count) => count.to_string(),
+                None => "unreadable".to_string(),
+            };
+
+            self.table.add_row(Row::from(vec![
+                Cell::new(backup.name()).fg(self.text_color),
+                Cell::new(backup.timestamp).fg(self.text_color),
+                Cell::new(count).fg(self.text_color),
+                Cell::new(format_size(backup.size)).fg(self.text_color),
+            ]));
+        }
+
+        self.table.clone()
+    }
+
+    fn with_history(&mut self, entries: Vec<(usize, HistoryEntry)>) -> Table {
+        self.table
+            .set_header(self.header(&["#", "Snippet", "Started", "Duration", "Exit", "Command"]));
+
+        for (number, entry) in entries {
+            let exit = match entry.exit_code {
+                Some(code) => code.to_string(),
+                None => "not started".to_string(),
+            };
+            let mut lines = entry.command.lines();
+            let mut command = lines.next().unwrap_or_default().to_string();
+            if lines.next().is_some() {
+                command.push_str(" …");
+            }
+
+            self.table.add_row(Row::from(vec![
+                Cell::new(number).fg(self.text_color),
+                Cell::new(&entry.snippet).fg(self.text_color),
+                Cell::new(
+                    entry
+                        .started_at
+                        .with_timezone(&chrono::Local)
+                        .format("%Y-%m-%d %H:%M:%S"),
+                )
+                .fg(self.text_color),
+                Cell::new(format_duration(entry.duration_ms)).fg(self.text_color),
+                Cell::new(exit).fg(self.text_color),
+                Cell::new(command).fg(self.text_color),
+            ]));
+        }
+
+        self.table.clone()
+    }
+}
+
+fn format_duration(ms: u64) -> String {
+    if ms < 1000 {
+        format!("{} ms", ms)
+    } else {
+        format!("{:.1} s", ms as f64 / 1000.0)
+    }
+}
+
+fn yes_no(value: bool) -> &'static str {
+    if value { "yes" } else { "no" }
+}
+
+/// Parses a colour name such as `cyan` or `dark_grey`, or a `#rrggbb` value.
+pub fn parse_color(value: &str) -> Option<Color> {
+    if let Some(hex) = value.strip_prefix('#') {
+        if hex.len() != 6 {
+            return None;
+        }
+        let channel = |i: usize| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok();
+        return Some(Color::Rgb {
+            r: channel(0)?,
+            g: channel(2)?,
+            b: channel(4)?,
+        });
+    }
+
+    let color = match value.to_ascii_lowercase().replace('-', "_").as_str() {
+        "reset" => Color::Reset,
+        "black" => Color::Black,
+        "dark_grey" | "dark_gray" => Color::DarkGrey,
+        "red" => Color::Red,
+        "dark_red" => Color::DarkRed,
+        "green" => Color::Green,
+        "dark_green" => Color::DarkGreen,
+        "yellow" => Color::Yellow,
+        "dark_yellow" => Color::DarkYellow,
+        "blue" => Color::Blue,
+        "dark_blue" => Color::DarkBlue,
+        "magenta" => Color::Magenta,
+        "dark_magenta" => Color::DarkMagenta,
+        "cyan" => Color::Cyan,
+        "dark_cyan" => Color::DarkCyan,
+        "white" => Color::White,
+        "grey" | "gray" => Color::Grey,
+        _ => return None,
+    };
+    Some(color)
 }
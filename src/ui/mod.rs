@@ -1,18 +1,84 @@
+use clap::ValueEnum;
 use comfy_table::Table;
 
-use crate::models::Snippet;
+use crate::{
+    models::Snippet,
+    storage::{
+        filter::FuzzyMatch, history::HistoryEntry, retention::BackupInfo, usage::UsageStats,
+    },
+};
 
 pub mod cli_confirm;
+pub mod cli_formatter;
 pub mod cli_selection;
 pub mod cli_table;
+pub mod preselected;
 
 pub trait TableUI {
-    fn with_snippet_list(&mut self, snippets: Vec<Snippet>) -> Table;
+    fn with_snippet_list(&mut self, snippets: Vec<Snippet>, usage: &UsageStats) -> Table;
+    fn with_search_results(&mut self, results: Vec<FuzzyMatch>) -> Table;
+    fn with_backup_list(&mut self, backups: Vec<BackupInfo>) -> Table;
+    /// Runs with their history numbers, as used by `rerun`.
+    fn with_history(&mut self, entries: Vec<(usize, HistoryEntry)>) -> Table;
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, ValueEnum)]
+pub enum OutputFormat {
+    #[default]
+    Table,
+    Json,
+    Yaml,
+    Csv,
+    Tsv,
+}
+
+/// Order of `list`. Without one, snippets are listed in file order.
+#[derive(Clone, Copy, Debug, PartialEq, ValueEnum)]
+pub enum ListSort {
+    Name,
+    Created,
+    Updated,
+    /// Most used and most recently used first.
+    Frecency,
+}
+
+/// Output of `history`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, ValueEnum)]
+pub enum HistoryFormat {
+    #[default]
+    Table,
+    Json,
+}
+
+/// Output of `run --dry-run`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, ValueEnum)]
+pub enum PlanFormat {
+    #[default]
+    Text,
+    Json,
+}
+
+pub trait OutputFormatter {
+    fn format_snippets(&self, snippets: &[Snippet]) -> Result<String, String>;
+    fn format_snippet(&self, snippet: &Snippet) -> Result<String, String>;
 }
 
 pub trait SelectionUI {
     fn with_snippet_list(&self, snippets: Vec<Snippet>) -> Option<Snippet>;
     fn with_backup_list(&self, backups: &[String]) -> Option<usize>;
+    fn with_multi_select(&self, prompt: &str, items: &[String]) -> Vec<usize>;
+
+    /// Picks one of `items` by typing part of it. Nothing is picked where
+    /// there is no one to ask.
+    fn with_fuzzy_select(&self, _prompt: &str, _items: &[String]) -> Option<usize> {
+        None
+    }
+
+    /// Whether the user can be asked to choose. When false, an ambiguous
+    /// match is an error instead of a prompt.
+    fn is_interactive(&self) -> bool {
+        true
+    }
 }
 
 pub trait ConfirmPrompt {
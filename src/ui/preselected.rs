@@ -0,0 +1,29 @@
+use crate::{models::Snippet, ui::SelectionUI};
+
+/// Picks a snippet that was already chosen elsewhere, e.g. in the TUI, so the
+/// regular commands can run without prompting again.
+pub struct PreselectedSelection {
+    name: String,
+}
+
+impl PreselectedSelection {
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+        }
+    }
+}
+
+impl SelectionUI for PreselectedSelection {
+    fn with_snippet_list(&self, snippets: Vec<Snippet>) -> Option<Snippet> {
+        snippets.into_iter().find(|s| s.name == self.name)
+    }
+
+    fn with_backup_list(&self, _backups: &[String]) -> Option<usize> {
+        None
+    }
+
+    fn with_multi_select(&self, _prompt: &str, _items: &[String]) -> Vec<usize> {
+        vec![]
+    }
+}